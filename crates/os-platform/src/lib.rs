@@ -98,6 +98,13 @@ impl tenda_runtime::Platform for Platform {
         }
     }
 
+    fn canonicalize(&self, path: &str) -> Result<String, tenda_runtime::FileErrorKind> {
+        match std::fs::canonicalize(path) {
+            Ok(path) => Ok(path.to_string_lossy().to_string()),
+            Err(error) => Err(map_file_error_kind(error.kind())),
+        }
+    }
+
     fn file_append(&self, path: &str, content: &str) -> Result<(), tenda_runtime::FileErrorKind> {
         match std::fs::OpenOptions::new().append(true).open(path) {
             Ok(mut file) => match file.write_all(content.as_bytes()) {
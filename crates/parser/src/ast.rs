@@ -40,6 +40,9 @@ pub enum Stmt {
     Return(Return),
     Break(Break),
     Continue(Continue),
+    Try(Try),
+    Throw(Throw),
+    Yield(Yield),
 }
 
 impl Stmt {
@@ -49,6 +52,11 @@ impl Stmt {
             Stmt::Decl(decl) => match decl {
                 Decl::Local(local) => &local.span,
                 Decl::Function(function) => &function.span,
+                Decl::Import(import) => &import.span,
+                Decl::Record(record) => &record.span,
+                Decl::Enum(enumeration) => &enumeration.span,
+                Decl::Class(class) => &class.span,
+                Decl::Destructuring(destructuring) => &destructuring.span,
             },
             Stmt::Cond(cond) => &cond.span,
             Stmt::While(while_stmt) => &while_stmt.span,
@@ -57,6 +65,25 @@ impl Stmt {
             Stmt::Return(return_stmt) => &return_stmt.span,
             Stmt::Break(break_stmt) => &break_stmt.span,
             Stmt::Continue(continue_stmt) => &continue_stmt.span,
+            Stmt::Try(try_stmt) => &try_stmt.span,
+            Stmt::Throw(throw_stmt) => &throw_stmt.span,
+            Stmt::Yield(yield_stmt) => &yield_stmt.span,
+        }
+    }
+
+    /// Returns whether the statement produces values with `produza`, without
+    /// looking into nested functions. `produza` is only allowed in blocks,
+    /// conditionals and loops, so those are the only statements searched.
+    pub fn contains_yield(&self) -> bool {
+        match self {
+            Stmt::Yield(_) => true,
+            Stmt::Block(Block { inner, .. }) => inner.inner.iter().any(Stmt::contains_yield),
+            Stmt::Cond(Cond { then, or_else, .. }) => {
+                then.contains_yield() || or_else.as_ref().is_some_and(|s| s.contains_yield())
+            }
+            Stmt::While(While { body, .. }) => body.contains_yield(),
+            Stmt::ForEach(ForEach { body, .. }) => body.contains_yield(),
+            _ => false,
         }
     }
 }
@@ -75,23 +102,49 @@ impl Return {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Break {
+    pub label: Option<String>,
     pub span: SourceSpan,
 }
 
 impl Break {
-    pub fn new(span: SourceSpan) -> Self {
-        Break { span }
+    pub fn new(label: Option<String>, span: SourceSpan) -> Self {
+        Break { label, span }
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Continue {
+    pub label: Option<String>,
     pub span: SourceSpan,
 }
 
 impl Continue {
-    pub fn new(span: SourceSpan) -> Self {
-        Continue { span }
+    pub fn new(label: Option<String>, span: SourceSpan) -> Self {
+        Continue { label, span }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Throw {
+    pub value: Expr,
+    pub span: SourceSpan,
+}
+
+impl Throw {
+    pub fn new(value: Expr, span: SourceSpan) -> Self {
+        Throw { value, span }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Yield {
+    pub value: Expr,
+    pub span: SourceSpan,
+}
+
+impl Yield {
+    pub fn new(value: Expr, span: SourceSpan) -> Self {
+        Yield { value, span }
     }
 }
 
@@ -111,21 +164,35 @@ impl Block {
 pub enum Decl {
     Local(LocalDecl),
     Function(FunctionDecl),
+    Import(ImportDecl),
+    Record(RecordDecl),
+    Enum(EnumDecl),
+    Class(ClassDecl),
+    Destructuring(DestructuringDecl),
 }
 
 impl Decl {
-    pub fn get_name(&self) -> &str {
+    pub fn get_bindings(&self) -> Vec<(&str, usize)> {
         match self {
-            Decl::Local(local) => &local.name,
-            Decl::Function(function) => &function.name,
+            Decl::Local(local) => vec![(&local.name, local.uid)],
+            Decl::Function(function) => vec![(&function.name, function.uid)],
+            Decl::Import(import) => vec![(&import.name, import.uid)],
+            Decl::Record(record) => vec![(&record.name, record.uid)],
+            Decl::Enum(enumeration) => vec![(&enumeration.name, enumeration.uid)],
+            Decl::Class(class) => vec![(&class.name, class.uid)],
+            Decl::Destructuring(destructuring) => destructuring
+                .pattern
+                .get_bindings()
+                .into_iter()
+                .map(|binding| (binding.name.as_str(), binding.uid))
+                .collect(),
         }
     }
 
-    pub fn get_uid(&self) -> usize {
-        match self {
-            Decl::Local(local) => local.uid,
-            Decl::Function(function) => function.uid,
-        }
+    pub fn declares(&self, name: &str) -> bool {
+        self.get_bindings()
+            .iter()
+            .any(|(declared, _)| *declared == name)
     }
 }
 
@@ -133,6 +200,7 @@ impl Decl {
 pub struct LocalDecl {
     pub name: String,
     pub value: Expr,
+    pub constant: bool,
     pub captured: bool,
     pub uid: usize,
     pub span: SourceSpan,
@@ -143,6 +211,123 @@ impl LocalDecl {
         LocalDecl {
             name,
             value,
+            constant: false,
+            captured: false,
+            uid,
+            span,
+        }
+    }
+
+    pub fn new_constant(name: String, value: Expr, uid: usize, span: SourceSpan) -> Self {
+        LocalDecl {
+            constant: true,
+            ..LocalDecl::new(name, value, uid, span)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DestructuringDecl {
+    pub pattern: Pattern,
+    pub value: Expr,
+    pub span: SourceSpan,
+}
+
+impl DestructuringDecl {
+    pub fn new(pattern: Pattern, value: Expr, span: SourceSpan) -> Self {
+        DestructuringDecl {
+            pattern,
+            value,
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportDecl {
+    pub path: String,
+    pub name: String,
+    pub captured: bool,
+    pub uid: usize,
+    pub span: SourceSpan,
+}
+
+impl ImportDecl {
+    pub fn new(path: String, name: String, uid: usize, span: SourceSpan) -> Self {
+        ImportDecl {
+            path,
+            name,
+            captured: false,
+            uid,
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RecordDecl {
+    pub name: String,
+    pub fields: Vec<String>,
+    pub captured: bool,
+    pub uid: usize,
+    pub span: SourceSpan,
+}
+
+impl RecordDecl {
+    pub fn new(name: String, fields: Vec<String>, uid: usize, span: SourceSpan) -> Self {
+        RecordDecl {
+            name,
+            fields,
+            captured: false,
+            uid,
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnumDecl {
+    pub name: String,
+    pub variants: Vec<String>,
+    pub captured: bool,
+    pub uid: usize,
+    pub span: SourceSpan,
+}
+
+impl EnumDecl {
+    pub fn new(name: String, variants: Vec<String>, uid: usize, span: SourceSpan) -> Self {
+        EnumDecl {
+            name,
+            variants,
+            captured: false,
+            uid,
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassDecl {
+    pub name: String,
+    pub params: Vec<FunctionParam>,
+    pub body: Ast,
+    pub captured: bool,
+    pub uid: usize,
+    pub span: SourceSpan,
+}
+
+impl ClassDecl {
+    pub fn new(
+        name: String,
+        params: Vec<FunctionParam>,
+        body: Ast,
+        uid: usize,
+        span: SourceSpan,
+    ) -> Self {
+        ClassDecl {
+            name,
+            params,
+            body,
             captured: false,
             uid,
             span,
@@ -153,20 +338,31 @@ impl LocalDecl {
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionParam {
     pub name: String,
+    pub default: Option<Expr>,
+    pub variadic: bool,
     pub uid: usize,
     pub captured: bool,
     pub span: SourceSpan,
 }
 
 impl FunctionParam {
-    pub fn new(name: String, uid: usize, span: SourceSpan) -> Self {
+    pub fn new(name: String, default: Option<Expr>, uid: usize, span: SourceSpan) -> Self {
         FunctionParam {
             name,
+            default,
+            variadic: false,
             uid,
             captured: false,
             span,
         }
     }
+
+    pub fn new_variadic(name: String, uid: usize, span: SourceSpan) -> Self {
+        FunctionParam {
+            variadic: true,
+            ..FunctionParam::new(name, None, uid, span)
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -223,30 +419,97 @@ impl Cond {
 pub struct While {
     pub cond: Expr,
     pub body: Box<Stmt>,
+    pub label: Option<String>,
     pub span: SourceSpan,
 }
 
 impl While {
-    pub fn new(cond: Expr, body: Stmt, span: SourceSpan) -> Self {
+    pub fn new(cond: Expr, body: Stmt, label: Option<String>, span: SourceSpan) -> Self {
         While {
             cond,
             body: Box::new(body),
+            label,
             span,
         }
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct ForEachItem {
+pub struct ForEach {
+    pub item: ForEachItem,
+    pub iterable: Expr,
+    pub body: Box<Stmt>,
+    pub label: Option<String>,
+    pub span: SourceSpan,
+}
+
+impl ForEach {
+    pub fn new(
+        item: ForEachItem,
+        iterable: Expr,
+        body: Stmt,
+        label: Option<String>,
+        span: SourceSpan,
+    ) -> Self {
+        ForEach {
+            item,
+            iterable,
+            body: Box::new(body),
+            label,
+            span,
+        }
+    }
+}
+
+/// The loop variables of a `para cada`: either a single pattern, or a key
+/// and a value, as in `para cada chave, valor em dicionário`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ForEachItem {
+    Single(Pattern),
+    Pair(Pattern, Pattern),
+}
+
+impl ForEachItem {
+    pub fn get_bindings(&self) -> Vec<&PatternBinding> {
+        match self {
+            ForEachItem::Single(pattern) => pattern.get_bindings(),
+            ForEachItem::Pair(key, value) => key
+                .get_bindings()
+                .into_iter()
+                .chain(value.get_bindings())
+                .collect(),
+        }
+    }
+
+    pub fn get_bindings_mut(&mut self) -> Vec<&mut PatternBinding> {
+        match self {
+            ForEachItem::Single(pattern) => pattern.get_bindings_mut(),
+            ForEachItem::Pair(key, value) => key
+                .get_bindings_mut()
+                .into_iter()
+                .chain(value.get_bindings_mut())
+                .collect(),
+        }
+    }
+
+    pub fn binds(&self, name: &str) -> bool {
+        self.get_bindings()
+            .iter()
+            .any(|binding| binding.name == name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CatchParam {
     pub name: String,
     pub uid: usize,
     pub captured: bool,
     pub span: SourceSpan,
 }
 
-impl ForEachItem {
+impl CatchParam {
     pub fn new(name: String, uid: usize, span: SourceSpan) -> Self {
-        ForEachItem {
+        CatchParam {
             name,
             uid,
             captured: false,
@@ -256,19 +519,36 @@ impl ForEachItem {
 }
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct ForEach {
-    pub item: ForEachItem,
-    pub iterable: Expr,
+pub struct Catch {
+    pub param: Option<CatchParam>,
     pub body: Box<Stmt>,
     pub span: SourceSpan,
 }
 
-impl ForEach {
-    pub fn new(item: ForEachItem, iterable: Expr, body: Stmt, span: SourceSpan) -> Self {
-        ForEach {
-            item,
-            iterable,
+impl Catch {
+    pub fn new(param: Option<CatchParam>, body: Stmt, span: SourceSpan) -> Self {
+        Catch {
+            param,
+            body: Box::new(body),
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Try {
+    pub body: Box<Stmt>,
+    pub catch: Option<Catch>,
+    pub finally: Option<Box<Stmt>>,
+    pub span: SourceSpan,
+}
+
+impl Try {
+    pub fn new(body: Stmt, catch: Option<Catch>, finally: Option<Stmt>, span: SourceSpan) -> Self {
+        Try {
             body: Box::new(body),
+            catch,
+            finally: finally.map(Box::new),
             span,
         }
     }
@@ -287,7 +567,11 @@ pub enum Expr {
     Literal(Literal),
     Variable(Variable),
     AssociativeArray(AssociativeArray),
+    Comprehension(Comprehension),
     AnonymousFunction(AnonymousFunction),
+    Interpolation(Interpolation),
+    Match(Match),
+    DestructuringAssign(DestructuringAssign),
 }
 
 impl Expr {
@@ -304,7 +588,11 @@ impl Expr {
             Expr::Literal(literal) => &literal.span,
             Expr::Variable(variable) => &variable.span,
             Expr::AssociativeArray(associative_array) => &associative_array.span,
+            Expr::Comprehension(comprehension) => &comprehension.span,
             Expr::AnonymousFunction(anonymous_function) => &anonymous_function.span,
+            Expr::Interpolation(interpolation) => &interpolation.span,
+            Expr::Match(match_expr) => &match_expr.span,
+            Expr::DestructuringAssign(destructuring) => &destructuring.span,
         }
     }
 }
@@ -368,30 +656,64 @@ impl TernaryOp {
 pub struct Call {
     pub callee: Box<Expr>,
     pub args: Vec<Expr>,
+    pub named_args: Vec<NamedArg>,
     pub span: SourceSpan,
 }
 
 impl Call {
-    pub fn new(callee: Expr, args: Vec<Expr>, span: SourceSpan) -> Self {
+    pub fn new(callee: Expr, args: Vec<Expr>, named_args: Vec<NamedArg>, span: SourceSpan) -> Self {
         Call {
             callee: Box::new(callee),
             args,
+            named_args,
             span,
         }
     }
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamedArg {
+    pub name: String,
+    pub value: Expr,
+    pub span: SourceSpan,
+}
+
+impl NamedArg {
+    pub fn new(name: String, value: Expr, span: SourceSpan) -> Self {
+        NamedArg { name, value, span }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Assign {
     pub name: Box<Expr>,
+    pub op: Option<BinaryOperator>,
     pub value: Box<Expr>,
     pub span: SourceSpan,
 }
 
 impl Assign {
-    pub fn new(name: Expr, value: Expr, span: SourceSpan) -> Self {
+    pub fn new(name: Expr, op: Option<BinaryOperator>, value: Expr, span: SourceSpan) -> Self {
         Assign {
             name: Box::new(name),
+            op,
+            value: Box::new(value),
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DestructuringAssign {
+    pub pattern: Pattern,
+    pub value: Box<Expr>,
+    pub span: SourceSpan,
+}
+
+impl DestructuringAssign {
+    pub fn new(pattern: Pattern, value: Expr, span: SourceSpan) -> Self {
+        DestructuringAssign {
+            pattern,
             value: Box::new(value),
             span,
         }
@@ -402,14 +724,18 @@ impl Assign {
 pub struct Access {
     pub subscripted: Box<Expr>,
     pub index: Box<Expr>,
+    /// Whether the access was written with `?.` or `?[`, evaluating to `Nada`
+    /// instead of failing when the subscripted value or the element is missing.
+    pub optional: bool,
     pub span: SourceSpan,
 }
 
 impl Access {
-    pub fn new(subscripted: Expr, index: Expr, span: SourceSpan) -> Self {
+    pub fn new(subscripted: Expr, index: Expr, optional: bool, span: SourceSpan) -> Self {
         Access {
             subscripted: Box::new(subscripted),
             index: Box::new(index),
+            optional,
             span,
         }
     }
@@ -439,6 +765,294 @@ impl AssociativeArray {
     }
 }
 
+/// A list or dictionary built from the elements of an iterable, such as
+/// `[x * 2 para cada x em números se x > 0]`. The loop variables are only
+/// visible to the element and the condition.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Comprehension {
+    pub element: ComprehensionElement,
+    pub item: ForEachItem,
+    pub iterable: Box<Expr>,
+    pub condition: Option<Box<Expr>>,
+    pub span: SourceSpan,
+}
+
+impl Comprehension {
+    pub fn new(
+        element: ComprehensionElement,
+        item: ForEachItem,
+        iterable: Expr,
+        condition: Option<Expr>,
+        span: SourceSpan,
+    ) -> Self {
+        Comprehension {
+            element,
+            item,
+            iterable: Box::new(iterable),
+            condition: condition.map(Box::new),
+            span,
+        }
+    }
+
+    /// Returns the expressions evaluated once per element, in which the loop
+    /// variables are in scope.
+    pub fn get_scoped_exprs(&self) -> Vec<&Expr> {
+        let element = match &self.element {
+            ComprehensionElement::List(value) => vec![value.as_ref()],
+            ComprehensionElement::AssociativeArray(key, value) => {
+                vec![key.as_ref(), value.as_ref()]
+            }
+        };
+
+        self.condition
+            .as_deref()
+            .into_iter()
+            .chain(element)
+            .collect()
+    }
+
+    pub fn get_scoped_exprs_mut(&mut self) -> Vec<&mut Expr> {
+        let element = match &mut self.element {
+            ComprehensionElement::List(value) => vec![value.as_mut()],
+            ComprehensionElement::AssociativeArray(key, value) => {
+                vec![key.as_mut(), value.as_mut()]
+            }
+        };
+
+        self.condition
+            .as_deref_mut()
+            .into_iter()
+            .chain(element)
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ComprehensionElement {
+    List(Box<Expr>),
+    AssociativeArray(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Interpolation {
+    pub parts: Vec<Expr>,
+    pub span: SourceSpan,
+}
+
+impl Interpolation {
+    pub fn new(parts: Vec<Expr>, span: SourceSpan) -> Self {
+        Interpolation { parts, span }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Match {
+    pub value: Box<Expr>,
+    pub arms: Vec<MatchArm>,
+    pub or_else: Option<Box<Stmt>>,
+    pub span: SourceSpan,
+}
+
+impl Match {
+    pub fn new(value: Expr, arms: Vec<MatchArm>, or_else: Option<Stmt>, span: SourceSpan) -> Self {
+        Match {
+            value: Box::new(value),
+            arms,
+            or_else: or_else.map(Box::new),
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: Box<Stmt>,
+    pub span: SourceSpan,
+}
+
+impl MatchArm {
+    pub fn new(pattern: Pattern, guard: Option<Expr>, body: Stmt, span: SourceSpan) -> Self {
+        MatchArm {
+            pattern,
+            guard,
+            body: Box::new(body),
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    Wildcard(SourceSpan),
+    Literal(Literal),
+    Range(RangePattern),
+    Binding(PatternBinding),
+    List(ListPattern),
+    AssociativeArray(AssociativeArrayPattern),
+    Record(RecordPattern),
+    EnumVariant(EnumVariantPattern),
+}
+
+impl Pattern {
+    pub fn get_span(&self) -> &SourceSpan {
+        match self {
+            Pattern::Wildcard(span) => span,
+            Pattern::Literal(literal) => &literal.span,
+            Pattern::Range(range) => &range.span,
+            Pattern::Binding(binding) => &binding.span,
+            Pattern::List(list) => &list.span,
+            Pattern::AssociativeArray(associative_array) => &associative_array.span,
+            Pattern::Record(record) => &record.span,
+            Pattern::EnumVariant(variant) => &variant.span,
+        }
+    }
+
+    pub fn get_bindings(&self) -> Vec<&PatternBinding> {
+        match self {
+            Pattern::Binding(binding) => vec![binding],
+            Pattern::List(ListPattern { elements, rest, .. }) => elements
+                .iter()
+                .chain(rest.as_deref())
+                .flat_map(Pattern::get_bindings)
+                .collect(),
+            Pattern::AssociativeArray(AssociativeArrayPattern { elements, .. }) => elements
+                .iter()
+                .flat_map(|(_, pattern)| pattern.get_bindings())
+                .collect(),
+            Pattern::Record(RecordPattern { fields, .. }) => fields
+                .iter()
+                .flat_map(|(_, pattern)| pattern.get_bindings())
+                .collect(),
+            Pattern::Wildcard(_)
+            | Pattern::Literal(_)
+            | Pattern::Range(_)
+            | Pattern::EnumVariant(_) => vec![],
+        }
+    }
+
+    pub fn get_bindings_mut(&mut self) -> Vec<&mut PatternBinding> {
+        match self {
+            Pattern::Binding(binding) => vec![binding],
+            Pattern::List(ListPattern { elements, rest, .. }) => elements
+                .iter_mut()
+                .chain(rest.as_deref_mut())
+                .flat_map(Pattern::get_bindings_mut)
+                .collect(),
+            Pattern::AssociativeArray(AssociativeArrayPattern { elements, .. }) => elements
+                .iter_mut()
+                .flat_map(|(_, pattern)| pattern.get_bindings_mut())
+                .collect(),
+            Pattern::Record(RecordPattern { fields, .. }) => fields
+                .iter_mut()
+                .flat_map(|(_, pattern)| pattern.get_bindings_mut())
+                .collect(),
+            Pattern::Wildcard(_)
+            | Pattern::Literal(_)
+            | Pattern::Range(_)
+            | Pattern::EnumVariant(_) => vec![],
+        }
+    }
+
+    pub fn binds(&self, name: &str) -> bool {
+        self.get_bindings()
+            .iter()
+            .any(|binding| binding.name == name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RangePattern {
+    pub start: f64,
+    pub end: f64,
+    pub span: SourceSpan,
+}
+
+impl RangePattern {
+    pub fn new(start: f64, end: f64, span: SourceSpan) -> Self {
+        RangePattern { start, end, span }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PatternBinding {
+    pub name: String,
+    pub uid: usize,
+    pub captured: bool,
+    pub span: SourceSpan,
+}
+
+impl PatternBinding {
+    pub fn new(name: String, uid: usize, span: SourceSpan) -> Self {
+        PatternBinding {
+            name,
+            uid,
+            captured: false,
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ListPattern {
+    pub elements: Vec<Pattern>,
+    pub rest: Option<Box<Pattern>>,
+    pub span: SourceSpan,
+}
+
+impl ListPattern {
+    pub fn new(elements: Vec<Pattern>, rest: Option<Pattern>, span: SourceSpan) -> Self {
+        ListPattern {
+            elements,
+            rest: rest.map(Box::new),
+            span,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AssociativeArrayPattern {
+    pub elements: Vec<(Literal, Pattern)>,
+    pub span: SourceSpan,
+}
+
+impl AssociativeArrayPattern {
+    pub fn new(elements: Vec<(Literal, Pattern)>, span: SourceSpan) -> Self {
+        AssociativeArrayPattern { elements, span }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RecordPattern {
+    pub name: String,
+    pub fields: Vec<(String, Pattern)>,
+    pub span: SourceSpan,
+}
+
+impl RecordPattern {
+    pub fn new(name: String, fields: Vec<(String, Pattern)>, span: SourceSpan) -> Self {
+        RecordPattern { name, fields, span }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnumVariantPattern {
+    pub enum_name: String,
+    pub variant: String,
+    pub span: SourceSpan,
+}
+
+impl EnumVariantPattern {
+    pub fn new(enum_name: String, variant: String, span: SourceSpan) -> Self {
+        EnumVariantPattern {
+            enum_name,
+            variant,
+            span,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Grouping {
     pub expr: Box<Expr>,
@@ -475,6 +1089,8 @@ pub struct Variable {
 }
 
 impl Variable {
+    pub const THIS: &'static str = "este";
+
     pub fn new(name: String, id: usize, span: SourceSpan) -> Self {
         Variable {
             name,
@@ -512,8 +1128,14 @@ pub enum BinaryOperator {
     Subtract,
     Multiply,
     Divide,
+    IntegerDivision,
     Exponentiation,
     Modulo,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    ShiftLeft,
+    ShiftRight,
     Equality,
     Inequality,
     Greater,
@@ -522,7 +1144,10 @@ pub enum BinaryOperator {
     LessOrEqual,
     LogicalAnd,
     LogicalOr,
+    NilCoalescing,
     Range,
+    ExclusiveRange,
+    RangeStep,
     Has,
     Lacks,
 }
@@ -536,6 +1161,7 @@ impl From<Token> for BinaryOperator {
             TokenKind::Minus => Subtract,
             TokenKind::Star => Multiply,
             TokenKind::Slash => Divide,
+            TokenKind::Backslash => IntegerDivision,
             TokenKind::Percent => Modulo,
             TokenKind::Caret => Exponentiation,
             TokenKind::Equals => Equality,
@@ -545,16 +1171,43 @@ impl From<Token> for BinaryOperator {
             TokenKind::LessOrEqual => LessOrEqual,
             TokenKind::Or => LogicalOr,
             TokenKind::And => LogicalAnd,
+            TokenKind::DoubleQuestion => NilCoalescing,
             TokenKind::Until => Range,
+            TokenKind::Step => RangeStep,
+            TokenKind::Ampersand => BitwiseAnd,
+            TokenKind::Pipe => BitwiseOr,
+            TokenKind::Tilde => BitwiseXor,
+            TokenKind::ShiftLeft => ShiftLeft,
+            TokenKind::ShiftRight => ShiftRight,
             _ => panic!("invalid token for binary operation"),
         }
     }
 }
 
+impl BinaryOperator {
+    /// Returns the operator applied by a compound assignment token such as
+    /// `+=`, or `None` for a plain `=`.
+    pub fn from_compound_assignment(kind: TokenKind) -> Option<Self> {
+        use BinaryOperator::*;
+
+        match kind {
+            TokenKind::PlusEqual => Some(Add),
+            TokenKind::MinusEqual => Some(Subtract),
+            TokenKind::StarEqual => Some(Multiply),
+            TokenKind::SlashEqual => Some(Divide),
+            TokenKind::BackslashEqual => Some(IntegerDivision),
+            TokenKind::PercentEqual => Some(Modulo),
+            TokenKind::CaretEqual => Some(Exponentiation),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum UnaryOperator {
     Negative,
     LogicalNot,
+    BitwiseNot,
 }
 
 impl From<Token> for UnaryOperator {
@@ -564,6 +1217,7 @@ impl From<Token> for UnaryOperator {
         match value.kind {
             TokenKind::Minus => Negative,
             TokenKind::Not => LogicalNot,
+            TokenKind::Tilde => BitwiseNot,
             _ => panic!("invalid token for unary operation"),
         }
     }
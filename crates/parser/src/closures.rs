@@ -87,6 +87,59 @@ fn annotate_stmt_with_var_captures(stmt: &mut ast::Stmt, closure_list: &VarCaptu
 
             annotate_expr_with_var_captures(value, closure_list);
         }
+        Stmt::Decl(Decl::Destructuring(DestructuringDecl { pattern, value, .. })) => {
+            for binding in pattern.get_bindings_mut() {
+                if closure_list.is_enclosed_var_decl(binding.uid) {
+                    binding.captured = true;
+                }
+            }
+
+            annotate_expr_with_var_captures(value, closure_list);
+        }
+        Stmt::Decl(Decl::Import(ImportDecl {
+            uid,
+            captured: is_captured_var,
+            ..
+        }))
+        | Stmt::Decl(Decl::Record(RecordDecl {
+            uid,
+            captured: is_captured_var,
+            ..
+        }))
+        | Stmt::Decl(Decl::Enum(EnumDecl {
+            uid,
+            captured: is_captured_var,
+            ..
+        })) => {
+            if closure_list.is_enclosed_var_decl(*uid) {
+                *is_captured_var = true;
+            }
+        }
+        Stmt::Decl(Decl::Class(ClassDecl {
+            params,
+            body: Ast { inner, .. },
+            uid,
+            captured: is_captured_var,
+            ..
+        })) => {
+            if closure_list.is_enclosed_var_decl(*uid) {
+                *is_captured_var = true;
+            }
+
+            for param in params {
+                if closure_list.is_enclosed_var_decl(param.uid) {
+                    param.captured = true;
+                }
+
+                if let Some(default) = &mut param.default {
+                    annotate_expr_with_var_captures(default, closure_list);
+                }
+            }
+
+            inner
+                .iter_mut()
+                .for_each(|stmt| annotate_stmt_with_var_captures(stmt, closure_list));
+        }
         Stmt::Decl(ast::Decl::Function(ast::FunctionDecl {
             body,
             captured: is_captured_var,
@@ -110,6 +163,10 @@ fn annotate_stmt_with_var_captures(stmt: &mut ast::Stmt, closure_list: &VarCaptu
                 if closure_list.is_enclosed_var_decl(param.uid) {
                     param.captured = true;
                 }
+
+                if let Some(default) = &mut param.default {
+                    annotate_expr_with_var_captures(default, closure_list);
+                }
             }
         }
         Stmt::Cond(ast::Cond {
@@ -139,8 +196,10 @@ fn annotate_stmt_with_var_captures(stmt: &mut ast::Stmt, closure_list: &VarCaptu
             annotate_expr_with_var_captures(iterable, closure_list);
             annotate_stmt_with_var_captures(body, closure_list);
 
-            if closure_list.is_enclosed_var_decl(item.uid) {
-                item.captured = true;
+            for binding in item.get_bindings_mut() {
+                if closure_list.is_enclosed_var_decl(binding.uid) {
+                    binding.captured = true;
+                }
             }
         }
         Stmt::Block(Block {
@@ -153,8 +212,32 @@ fn annotate_stmt_with_var_captures(stmt: &mut ast::Stmt, closure_list: &VarCaptu
             value: Some(expr), ..
         }) => annotate_expr_with_var_captures(expr, closure_list),
         Stmt::Return(Return { value: None, .. }) => {}
+        Stmt::Throw(Throw { value, .. }) => annotate_expr_with_var_captures(value, closure_list),
+        Stmt::Yield(Yield { value, .. }) => annotate_expr_with_var_captures(value, closure_list),
         Stmt::Continue(_) => {}
         Stmt::Break(_) => {}
+        Stmt::Try(Try {
+            body,
+            catch,
+            finally,
+            ..
+        }) => {
+            annotate_stmt_with_var_captures(body, closure_list);
+
+            if let Some(Catch { param, body, .. }) = catch {
+                annotate_stmt_with_var_captures(body, closure_list);
+
+                if let Some(param) = param {
+                    if closure_list.is_enclosed_var_decl(param.uid) {
+                        param.captured = true;
+                    }
+                }
+            }
+
+            if let Some(finally) = finally {
+                annotate_stmt_with_var_captures(finally, closure_list);
+            }
+        }
     }
 }
 
@@ -171,9 +254,17 @@ fn annotate_expr_with_var_captures(expr: &mut ast::Expr, closure_list: &VarCaptu
                 *is_captured_var = true;
             }
         }
-        Expr::Call(Call { args, callee, .. }) => {
+        Expr::Call(Call {
+            args,
+            named_args,
+            callee,
+            ..
+        }) => {
             args.iter_mut()
                 .for_each(|arg| annotate_expr_with_var_captures(arg, closure_list));
+            named_args
+                .iter_mut()
+                .for_each(|arg| annotate_expr_with_var_captures(&mut arg.value, closure_list));
             annotate_expr_with_var_captures(callee, closure_list);
         }
         Expr::Access(Access {
@@ -196,6 +287,65 @@ fn annotate_expr_with_var_captures(expr: &mut ast::Expr, closure_list: &VarCaptu
                 .iter_mut()
                 .for_each(|(_, value)| annotate_expr_with_var_captures(value, closure_list));
         }
+        Expr::Comprehension(comprehension) => {
+            annotate_expr_with_var_captures(&mut comprehension.iterable, closure_list);
+
+            for expr in comprehension.get_scoped_exprs_mut() {
+                annotate_expr_with_var_captures(expr, closure_list);
+            }
+
+            for binding in comprehension.item.get_bindings_mut() {
+                if closure_list.is_enclosed_var_decl(binding.uid) {
+                    binding.captured = true;
+                }
+            }
+        }
+        Expr::Interpolation(Interpolation { parts, .. }) => {
+            parts
+                .iter_mut()
+                .for_each(|part| annotate_expr_with_var_captures(part, closure_list));
+        }
+        Expr::DestructuringAssign(DestructuringAssign { pattern, value, .. }) => {
+            for binding in pattern.get_bindings_mut() {
+                if closure_list.is_free_variable_ref(binding.uid) {
+                    binding.captured = true;
+                }
+            }
+
+            annotate_expr_with_var_captures(value, closure_list);
+        }
+        Expr::Match(Match {
+            value,
+            arms,
+            or_else,
+            ..
+        }) => {
+            annotate_expr_with_var_captures(value, closure_list);
+
+            for MatchArm {
+                pattern,
+                guard,
+                body,
+                ..
+            } in arms
+            {
+                for binding in pattern.get_bindings_mut() {
+                    if closure_list.is_enclosed_var_decl(binding.uid) {
+                        binding.captured = true;
+                    }
+                }
+
+                if let Some(guard) = guard {
+                    annotate_expr_with_var_captures(guard, closure_list);
+                }
+
+                annotate_stmt_with_var_captures(body, closure_list);
+            }
+
+            if let Some(or_else) = or_else {
+                annotate_stmt_with_var_captures(or_else, closure_list);
+            }
+        }
         Expr::Binary(BinaryOp { lhs, rhs, .. }) => {
             annotate_expr_with_var_captures(lhs, closure_list);
             annotate_expr_with_var_captures(rhs, closure_list);
@@ -230,6 +380,10 @@ fn annotate_expr_with_var_captures(expr: &mut ast::Expr, closure_list: &VarCaptu
                 if closure_list.is_enclosed_var_decl(param.uid) {
                     param.captured = true;
                 }
+
+                if let Some(default) = &mut param.default {
+                    annotate_expr_with_var_captures(default, closure_list);
+                }
             }
 
             annotate_stmt_with_var_captures(body, closure_list)
@@ -245,25 +399,22 @@ fn get_var_captures_from_ast(ast: &Ast) -> Vec<VarCapture> {
 
     let iter = ast.iter().enumerate();
     let iter = iter.flat_map(|(i, stmt)| match stmt {
-        Stmt::Decl(decl) => {
-            let name = decl.get_name();
-
-            ast[i + 1..]
-                .iter()
-                .flat_map(|sibling| get_free_vars_in_stmt(sibling, name))
-                .map(|(var_ref_id, inner_fn_decl_id)| {
-                    VarCapture::new(
-                        inner_fn_decl_id,
-                        var_ref_id,
-                        decl.get_uid(),
-                        name.to_string(),
-                    )
-                })
-                .chain(get_var_captures_from_fn_body(decl))
-                .chain(get_var_captures_from_fn_args(decl))
-                .chain(get_var_captures_from_local_decl(decl))
-                .collect()
-        }
+        Stmt::Decl(decl) => decl
+            .get_bindings()
+            .into_iter()
+            .flat_map(|(name, uid)| {
+                ast[i + 1..]
+                    .iter()
+                    .flat_map(|sibling| get_free_vars_in_stmt(sibling, name))
+                    .map(move |(var_ref_id, inner_fn_decl_id)| {
+                        VarCapture::new(inner_fn_decl_id, var_ref_id, uid, name.to_string())
+                    })
+            })
+            .chain(get_var_captures_from_fn_body(decl))
+            .chain(get_var_captures_from_fn_args(decl))
+            .chain(get_var_captures_from_local_decl(decl))
+            .chain(get_var_captures_from_class_body(decl))
+            .collect(),
         Stmt::Cond(Cond {
             then,
             or_else,
@@ -316,17 +467,18 @@ fn get_var_captures_from_ast(ast: &Ast) -> Vec<VarCapture> {
                 _ => unreachable!(),
             };
 
-            let var_captures_from_item = body
-                .iter()
-                .flat_map(|sibling| get_free_vars_in_stmt(sibling, &item.name))
-                .map(|(var_ref_id, inner_fn_decl_id)| {
-                    VarCapture::new(
-                        inner_fn_decl_id,
-                        var_ref_id,
-                        item.uid,
-                        item.name.to_string(),
-                    )
-                });
+            let var_captures_from_item = item.get_bindings().into_iter().flat_map(|binding| {
+                body.iter()
+                    .flat_map(|sibling| get_free_vars_in_stmt(sibling, &binding.name))
+                    .map(|(var_ref_id, inner_fn_decl_id)| {
+                        VarCapture::new(
+                            inner_fn_decl_id,
+                            var_ref_id,
+                            binding.uid,
+                            binding.name.to_string(),
+                        )
+                    })
+            });
 
             let var_captures_from_iterable = get_var_captures_from_expr(iterable);
 
@@ -342,8 +494,54 @@ fn get_var_captures_from_ast(ast: &Ast) -> Vec<VarCapture> {
             Some(expr) => get_var_captures_from_expr(expr),
             None => vec![],
         },
+        Stmt::Throw(Throw { value, .. }) => get_var_captures_from_expr(value),
+        Stmt::Yield(Yield { value, .. }) => get_var_captures_from_expr(value),
         Stmt::Break(_) => vec![],
         Stmt::Continue(_) => vec![],
+        Stmt::Try(Try {
+            body,
+            catch,
+            finally,
+            ..
+        }) => {
+            let mut closure_list = vec![];
+
+            if let Stmt::Block(Block { inner: body, .. }) = body.as_ref() {
+                closure_list.extend(get_var_captures_from_ast(body));
+            }
+
+            if let Some(Catch { param, body, .. }) = catch {
+                let body = match body.as_ref() {
+                    Stmt::Block(Block { inner, .. }) => inner,
+                    _ => unreachable!(),
+                };
+
+                closure_list.extend(get_var_captures_from_ast(body));
+
+                if let Some(param) = param {
+                    let var_captures_from_param = body
+                        .inner
+                        .iter()
+                        .flat_map(|sibling| get_free_vars_in_stmt(sibling, &param.name))
+                        .map(|(var_ref_id, inner_fn_decl_id)| {
+                            VarCapture::new(
+                                inner_fn_decl_id,
+                                var_ref_id,
+                                param.uid,
+                                param.name.to_string(),
+                            )
+                        });
+
+                    closure_list.extend(var_captures_from_param);
+                }
+            }
+
+            if let Some(Stmt::Block(Block { inner: finally, .. })) = finally.as_deref() {
+                closure_list.extend(get_var_captures_from_ast(finally));
+            }
+
+            closure_list
+        }
     });
 
     iter.collect()
@@ -354,10 +552,15 @@ fn get_var_captures_from_expr(expr: &ast::Expr) -> Vec<VarCapture> {
 
     match expr {
         Expr::AnonymousFunction(AnonymousFunction { body, params, .. }) => {
+            let var_captures_from_defaults = get_var_captures_from_defaults(params);
+
             let body = match body.as_ref() {
                 Stmt::Block(Block { inner, .. }) => inner,
                 Stmt::Expr(expr) => {
-                    return get_var_captures_from_expr(expr);
+                    return get_var_captures_from_expr(expr)
+                        .into_iter()
+                        .chain(var_captures_from_defaults)
+                        .collect();
                 }
                 _ => unreachable!(),
             };
@@ -384,6 +587,7 @@ fn get_var_captures_from_expr(expr: &ast::Expr) -> Vec<VarCapture> {
             var_capture_from_body
                 .into_iter()
                 .chain(var_captures_from_params)
+                .chain(var_captures_from_defaults)
                 .collect()
         }
         Expr::Binary(BinaryOp { lhs, rhs, .. }) => {
@@ -405,9 +609,15 @@ fn get_var_captures_from_expr(expr: &ast::Expr) -> Vec<VarCapture> {
 
             var_captures
         }
-        Expr::Call(Call { args, callee, .. }) => {
+        Expr::Call(Call {
+            args,
+            named_args,
+            callee,
+            ..
+        }) => {
             let mut var_captures = args
                 .iter()
+                .chain(named_args.iter().map(|arg| &arg.value))
                 .flat_map(get_var_captures_from_expr)
                 .collect::<Vec<_>>();
 
@@ -437,6 +647,98 @@ fn get_var_captures_from_expr(expr: &ast::Expr) -> Vec<VarCapture> {
             .iter()
             .flat_map(|(_, value)| get_var_captures_from_expr(value))
             .collect(),
+        Expr::Comprehension(comprehension) => {
+            let scoped_exprs = comprehension.get_scoped_exprs();
+
+            let var_captures_from_item =
+                comprehension
+                    .item
+                    .get_bindings()
+                    .into_iter()
+                    .flat_map(|binding| {
+                        scoped_exprs
+                            .iter()
+                            .flat_map(|expr| get_free_vars_in_expr(expr, &binding.name))
+                            .map(|(var_ref_id, inner_fn_decl_id)| {
+                                VarCapture::new(
+                                    inner_fn_decl_id,
+                                    var_ref_id,
+                                    binding.uid,
+                                    binding.name.to_string(),
+                                )
+                            })
+                    });
+
+            get_var_captures_from_expr(&comprehension.iterable)
+                .into_iter()
+                .chain(
+                    scoped_exprs
+                        .iter()
+                        .flat_map(|expr| get_var_captures_from_expr(expr)),
+                )
+                .chain(var_captures_from_item)
+                .collect()
+        }
+        Expr::Interpolation(Interpolation { parts, .. }) => {
+            parts.iter().flat_map(get_var_captures_from_expr).collect()
+        }
+        Expr::DestructuringAssign(DestructuringAssign { value, .. }) => {
+            get_var_captures_from_expr(value)
+        }
+        Expr::Match(Match {
+            value,
+            arms,
+            or_else,
+            ..
+        }) => {
+            let mut var_captures = get_var_captures_from_expr(value);
+
+            for MatchArm {
+                pattern,
+                guard,
+                body,
+                ..
+            } in arms
+            {
+                let body = match body.as_ref() {
+                    Stmt::Block(Block { inner, .. }) => inner,
+                    _ => unreachable!(),
+                };
+
+                if let Some(guard) = guard {
+                    var_captures.extend(get_var_captures_from_expr(guard));
+                }
+
+                var_captures.extend(get_var_captures_from_ast(body));
+
+                for binding in pattern.get_bindings() {
+                    let var_captures_from_binding = guard
+                        .iter()
+                        .flat_map(|guard| get_free_vars_in_expr(guard, &binding.name))
+                        .chain(
+                            body.inner
+                                .iter()
+                                .flat_map(|stmt| get_free_vars_in_stmt(stmt, &binding.name)),
+                        )
+                        .map(|(var_ref_id, inner_fn_decl_id)| {
+                            VarCapture::new(
+                                inner_fn_decl_id,
+                                var_ref_id,
+                                binding.uid,
+                                binding.name.clone(),
+                            )
+                        });
+
+                    var_captures.extend(var_captures_from_binding);
+                }
+            }
+
+            if let Some(Stmt::Block(Block { inner, .. })) = or_else.as_deref() {
+                var_captures.extend(get_var_captures_from_ast(inner));
+            }
+
+            var_captures
+        }
         Expr::Grouping(Grouping { expr, .. }) => get_var_captures_from_expr(expr),
         Expr::Literal(_) => vec![],
         Expr::Variable(_) => vec![],
@@ -449,17 +751,32 @@ fn get_var_captures_from_fn_args(decl: &ast::Decl) -> Vec<VarCapture> {
         Decl::Function(FunctionDecl { body, params, .. }) => params
             .iter()
             .flat_map(|FunctionParam { name, uid, .. }| {
-                get_free_vars_in_stmt(body, name).into_iter().map(
-                    |(var_ref_id, inner_fn_decl_id)| {
+                let free_vars_in_defaults = params
+                    .iter()
+                    .filter_map(|param| param.default.as_ref())
+                    .flat_map(|default| get_free_vars_in_expr(default, name));
+
+                get_free_vars_in_stmt(body, name)
+                    .into_iter()
+                    .chain(free_vars_in_defaults)
+                    .map(|(var_ref_id, inner_fn_decl_id)| {
                         VarCapture::new(inner_fn_decl_id, var_ref_id, *uid, name.to_string())
-                    },
-                )
+                    })
             })
+            .chain(get_var_captures_from_defaults(params))
             .collect(),
         _ => vec![],
     }
 }
 
+fn get_var_captures_from_defaults(params: &[ast::FunctionParam]) -> Vec<VarCapture> {
+    params
+        .iter()
+        .filter_map(|param| param.default.as_ref())
+        .flat_map(get_var_captures_from_expr)
+        .collect()
+}
+
 fn get_var_captures_from_fn_body(decl: &ast::Decl) -> Vec<VarCapture> {
     use ast::*;
 
@@ -477,7 +794,33 @@ fn get_var_captures_from_local_decl(decl: &ast::Decl) -> Vec<VarCapture> {
     use ast::*;
 
     match decl {
-        Decl::Local(LocalDecl { value, .. }) => get_var_captures_from_expr(value),
+        Decl::Local(LocalDecl { value, .. })
+        | Decl::Destructuring(DestructuringDecl { value, .. }) => get_var_captures_from_expr(value),
+        _ => vec![],
+    }
+}
+
+fn get_var_captures_from_class_body(decl: &ast::Decl) -> Vec<VarCapture> {
+    use ast::*;
+
+    match decl {
+        Decl::Class(ClassDecl { body, params, .. }) => {
+            let var_captures_from_params = params.iter().flat_map(|param| {
+                body.inner
+                    .iter()
+                    .filter(|member| matches!(member, Stmt::Decl(Decl::Local(_))))
+                    .flat_map(|member| get_free_vars_in_stmt(member, &param.name))
+                    .map(|(var_ref_id, inner_fn_decl_id)| {
+                        VarCapture::new(inner_fn_decl_id, var_ref_id, param.uid, param.name.clone())
+                    })
+            });
+
+            get_var_captures_from_ast(body)
+                .into_iter()
+                .chain(var_captures_from_params)
+                .chain(get_var_captures_from_defaults(params))
+                .collect()
+        }
         _ => vec![],
     }
 }
@@ -486,7 +829,7 @@ fn get_free_vars_in_stmt(stmt: &ast::Stmt, name: &str) -> Vec<FreeVarRef> {
     use ast::*;
 
     match stmt {
-        Stmt::Decl(decl) if decl.get_name() == name => vec![],
+        Stmt::Decl(decl) if decl.declares(name) => vec![],
         Stmt::Decl(decl) => match decl {
             ast::Decl::Function(ast::FunctionDecl {
                 body, uid, params, ..
@@ -495,9 +838,27 @@ fn get_free_vars_in_stmt(stmt: &ast::Stmt, name: &str) -> Vec<FreeVarRef> {
                     return vec![];
                 }
 
-                get_free_vars_in_fn_body(body, name, *uid)
+                get_var_refs_in_defaults(params, name)
+                    .into_iter()
+                    .map(|var_ref_id| (var_ref_id, *uid))
+                    .chain(get_free_vars_in_fn_body(body, name, *uid))
+                    .collect()
             }
-            ast::Decl::Local(ast::LocalDecl { value, .. }) => get_free_vars_in_expr(value, name),
+            ast::Decl::Local(ast::LocalDecl { value, .. })
+            | ast::Decl::Destructuring(ast::DestructuringDecl { value, .. }) => {
+                get_free_vars_in_expr(value, name)
+            }
+            ast::Decl::Class(ast::ClassDecl { body, params, .. }) => params
+                .iter()
+                .filter_map(|param| param.default.as_ref())
+                .flat_map(|default| get_free_vars_in_expr(default, name))
+                .chain(
+                    body.inner
+                        .iter()
+                        .flat_map(|member| get_free_vars_in_stmt(member, name)),
+                )
+                .collect(),
+            ast::Decl::Import(_) | ast::Decl::Record(_) | ast::Decl::Enum(_) => vec![],
         },
         Stmt::Cond(ast::Cond { then, or_else, .. }) => {
             let mut references = get_free_vars_in_stmt(then, name);
@@ -515,7 +876,7 @@ fn get_free_vars_in_stmt(stmt: &ast::Stmt, name: &str) -> Vec<FreeVarRef> {
             iterable,
             ..
         }) => {
-            let free_vars_in_body = if item.name != name {
+            let free_vars_in_body = if !item.binds(name) {
                 get_free_vars_in_stmt(body, name)
             } else {
                 vec![]
@@ -539,9 +900,31 @@ fn get_free_vars_in_stmt(stmt: &ast::Stmt, name: &str) -> Vec<FreeVarRef> {
             Some(expr) => get_free_vars_in_expr(expr, name),
             None => vec![],
         },
+        Stmt::Throw(ast::Throw { value, .. }) => get_free_vars_in_expr(value, name),
+        Stmt::Yield(ast::Yield { value, .. }) => get_free_vars_in_expr(value, name),
         Stmt::Expr(expr) => get_free_vars_in_expr(expr, name),
         Stmt::Break(_) => vec![],
         Stmt::Continue(_) => vec![],
+        Stmt::Try(ast::Try {
+            body,
+            catch,
+            finally,
+            ..
+        }) => {
+            let mut references = get_free_vars_in_stmt(body, name);
+
+            if let Some(ast::Catch { param, body, .. }) = catch {
+                if !matches!(param, Some(param) if param.name == name) {
+                    references.extend(get_free_vars_in_stmt(body, name));
+                }
+            }
+
+            if let Some(finally) = finally {
+                references.extend(get_free_vars_in_stmt(finally, name));
+            }
+
+            references
+        }
     }
 }
 
@@ -549,9 +932,13 @@ fn get_free_vars_in_expr(expr: &ast::Expr, name: &str) -> Vec<FreeVarRef> {
     use ast::*;
 
     match expr {
-        Expr::AnonymousFunction(AnonymousFunction { body, uid, .. }) => {
-            get_free_vars_in_fn_body(body, name, *uid)
-        }
+        Expr::AnonymousFunction(AnonymousFunction {
+            body, uid, params, ..
+        }) => get_var_refs_in_defaults(params, name)
+            .into_iter()
+            .map(|var_ref_id| (var_ref_id, *uid))
+            .chain(get_free_vars_in_fn_body(body, name, *uid))
+            .collect(),
         Expr::Binary(ast::BinaryOp { lhs, rhs, .. }) => {
             let mut references = get_free_vars_in_expr(lhs, name);
 
@@ -573,9 +960,15 @@ fn get_free_vars_in_expr(expr: &ast::Expr, name: &str) -> Vec<FreeVarRef> {
 
             references
         }
-        Expr::Call(ast::Call { args, callee, .. }) => {
+        Expr::Call(ast::Call {
+            args,
+            named_args,
+            callee,
+            ..
+        }) => {
             let mut references = args
                 .iter()
+                .chain(named_args.iter().map(|arg| &arg.value))
                 .flat_map(|arg| get_free_vars_in_expr(arg, name))
                 .collect::<Vec<_>>();
 
@@ -611,6 +1004,46 @@ fn get_free_vars_in_expr(expr: &ast::Expr, name: &str) -> Vec<FreeVarRef> {
             .iter()
             .flat_map(|(_, value)| get_free_vars_in_expr(value, name))
             .collect(),
+        Expr::Comprehension(comprehension) => {
+            let mut references = get_free_vars_in_expr(&comprehension.iterable, name);
+
+            if !comprehension.item.binds(name) {
+                for expr in comprehension.get_scoped_exprs() {
+                    references.extend(get_free_vars_in_expr(expr, name));
+                }
+            }
+
+            references
+        }
+        Expr::Interpolation(ast::Interpolation { parts, .. }) => parts
+            .iter()
+            .flat_map(|part| get_free_vars_in_expr(part, name))
+            .collect(),
+        Expr::DestructuringAssign(ast::DestructuringAssign { value, .. }) => {
+            get_free_vars_in_expr(value, name)
+        }
+        Expr::Match(ast::Match {
+            value,
+            arms,
+            or_else,
+            ..
+        }) => {
+            let mut references = get_free_vars_in_expr(value, name);
+
+            for arm in arms.iter().filter(|arm| !arm.pattern.binds(name)) {
+                if let Some(guard) = &arm.guard {
+                    references.extend(get_free_vars_in_expr(guard, name));
+                }
+
+                references.extend(get_free_vars_in_stmt(&arm.body, name));
+            }
+
+            if let Some(or_else) = or_else {
+                references.extend(get_free_vars_in_stmt(or_else, name));
+            }
+
+            references
+        }
         Expr::Grouping(ast::Grouping { expr, .. }) => get_free_vars_in_expr(expr, name),
         Expr::Literal(_) => vec![],
         Expr::Variable(_) => vec![],
@@ -625,14 +1058,35 @@ fn get_free_vars_in_fn_body(stmt: &ast::Stmt, name: &str, closure_fn: usize) ->
             .iter()
             .map(|expr| (*expr, closure_fn))
             .collect::<Vec<_>>(),
-        Stmt::Decl(decl) if decl.get_name() == name => vec![],
-        Stmt::Decl(Decl::Function(ast::FunctionDecl { body, uid, .. })) => {
-            get_free_vars_in_fn_body(body, name, *uid)
-        }
-        Stmt::Decl(Decl::Local(ast::LocalDecl { value, .. })) => get_var_refs_in_expr(value, name)
+        Stmt::Decl(decl) if decl.declares(name) => vec![],
+        Stmt::Decl(Decl::Function(ast::FunctionDecl {
+            body, uid, params, ..
+        })) => get_var_refs_in_defaults(params, name)
             .into_iter()
-            .map(|expr| (expr, closure_fn))
-            .collect::<Vec<_>>(),
+            .map(|var_ref_id| (var_ref_id, closure_fn))
+            .chain(get_free_vars_in_fn_body(body, name, *uid))
+            .collect(),
+        Stmt::Decl(Decl::Local(ast::LocalDecl { value, .. }))
+        | Stmt::Decl(Decl::Destructuring(ast::DestructuringDecl { value, .. })) => {
+            get_var_refs_in_expr(value, name)
+                .into_iter()
+                .map(|expr| (expr, closure_fn))
+                .collect::<Vec<_>>()
+        }
+        Stmt::Decl(Decl::Class(ast::ClassDecl { body, params, .. })) => {
+            get_var_refs_in_defaults(params, name)
+                .into_iter()
+                .map(|var_ref_id| (var_ref_id, closure_fn))
+                .chain(
+                    body.inner
+                        .iter()
+                        .flat_map(|member| get_free_vars_in_fn_body(member, name, closure_fn)),
+                )
+                .collect()
+        }
+        Stmt::Decl(Decl::Import(_)) | Stmt::Decl(Decl::Record(_)) | Stmt::Decl(Decl::Enum(_)) => {
+            vec![]
+        }
         Stmt::Cond(ast::Cond {
             cond,
             then,
@@ -680,7 +1134,7 @@ fn get_free_vars_in_fn_body(stmt: &ast::Stmt, name: &str, closure_fn: usize) ->
             ..
         }) => block
             .iter()
-            .take_while(|stmt| !matches!(stmt, Stmt::Decl(decl) if decl.get_name() == name))
+            .take_while(|stmt| !matches!(stmt, Stmt::Decl(decl) if decl.declares(name)))
             .flat_map(|stmt| get_free_vars_in_fn_body(stmt, name, closure_fn))
             .collect::<Vec<_>>(),
         Stmt::Return(ast::Return {
@@ -690,8 +1144,34 @@ fn get_free_vars_in_fn_body(stmt: &ast::Stmt, name: &str, closure_fn: usize) ->
             .map(|expr| (*expr, closure_fn))
             .collect::<Vec<_>>(),
         Stmt::Return(ast::Return { value: None, .. }) => vec![],
+        Stmt::Throw(ast::Throw { value, .. }) | Stmt::Yield(ast::Yield { value, .. }) => {
+            get_var_refs_in_expr(value, name)
+                .into_iter()
+                .map(|expr| (expr, closure_fn))
+                .collect::<Vec<_>>()
+        }
         Stmt::Break(_) => vec![],
         Stmt::Continue(_) => vec![],
+        Stmt::Try(ast::Try {
+            body,
+            catch,
+            finally,
+            ..
+        }) => {
+            let mut references = get_free_vars_in_fn_body(body, name, closure_fn);
+
+            if let Some(ast::Catch { param, body, .. }) = catch {
+                if !matches!(param, Some(param) if param.name == name) {
+                    references.extend(get_free_vars_in_fn_body(body, name, closure_fn));
+                }
+            }
+
+            if let Some(finally) = finally {
+                references.extend(get_free_vars_in_fn_body(finally, name, closure_fn));
+            }
+
+            references
+        }
     }
 }
 
@@ -714,8 +1194,14 @@ fn get_var_refs_in_expr(expr: &ast::Expr, name: &str) -> Vec<usize> {
             .chain(get_var_refs_in_expr(then, name))
             .chain(get_var_refs_in_expr(or_else, name))
             .collect(),
-        Call(ast::Call { args, callee, .. }) => args
+        Call(ast::Call {
+            args,
+            named_args,
+            callee,
+            ..
+        }) => args
             .iter()
+            .chain(named_args.iter().map(|arg| &arg.value))
             .flat_map(|arg| get_var_refs_in_expr(arg, name))
             .chain(get_var_refs_in_expr(callee, name))
             .collect::<Vec<_>>(),
@@ -741,6 +1227,50 @@ fn get_var_refs_in_expr(expr: &ast::Expr, name: &str) -> Vec<usize> {
             .iter()
             .flat_map(|(_, value)| get_var_refs_in_expr(value, name))
             .collect(),
+        Comprehension(comprehension) => {
+            let mut references = get_var_refs_in_expr(&comprehension.iterable, name);
+
+            if !comprehension.item.binds(name) {
+                for expr in comprehension.get_scoped_exprs() {
+                    references.extend(get_var_refs_in_expr(expr, name));
+                }
+            }
+
+            references
+        }
+        Interpolation(ast::Interpolation { parts, .. }) => parts
+            .iter()
+            .flat_map(|part| get_var_refs_in_expr(part, name))
+            .collect(),
+        DestructuringAssign(ast::DestructuringAssign { pattern, value, .. }) => pattern
+            .get_bindings()
+            .into_iter()
+            .filter(|binding| binding.name == name)
+            .map(|binding| binding.uid)
+            .chain(get_var_refs_in_expr(value, name))
+            .collect(),
+        Match(ast::Match {
+            value,
+            arms,
+            or_else,
+            ..
+        }) => {
+            let mut references = get_var_refs_in_expr(value, name);
+
+            for arm in arms.iter().filter(|arm| !arm.pattern.binds(name)) {
+                if let Some(guard) = &arm.guard {
+                    references.extend(get_var_refs_in_expr(guard, name));
+                }
+
+                references.extend(get_var_refs_in_stmt(&arm.body, name));
+            }
+
+            if let Some(or_else) = or_else {
+                references.extend(get_var_refs_in_stmt(or_else, name));
+            }
+
+            references
+        }
         Grouping(ast::Grouping { expr, .. }) => get_var_refs_in_expr(expr, name),
         Literal(_) => vec![],
         AnonymousFunction(ast::AnonymousFunction { body, params, .. }) => {
@@ -748,15 +1278,21 @@ fn get_var_refs_in_expr(expr: &ast::Expr, name: &str) -> Vec<usize> {
                 return vec![];
             }
 
+            let references_in_defaults = get_var_refs_in_defaults(params, name);
+
             match body.as_ref() {
                 ast::Stmt::Block(ast::Block {
                     inner: ast::Ast { inner, .. },
                     ..
-                }) => inner
-                    .iter()
-                    .flat_map(|stmt| get_var_refs_in_stmt(stmt, name))
+                }) => references_in_defaults
+                    .into_iter()
+                    .chain(
+                        inner
+                            .iter()
+                            .flat_map(|stmt| get_var_refs_in_stmt(stmt, name)),
+                    )
                     .collect(),
-                _ => vec![],
+                _ => references_in_defaults,
             }
         }
         Variable(ast::Variable {
@@ -777,21 +1313,43 @@ fn get_var_refs_in_stmt(stmt: &ast::Stmt, name: &str) -> Vec<usize> {
     use ast::Stmt::*;
 
     match stmt {
-        Decl(ast::Decl::Local(ast::LocalDecl { value, .. })) => get_var_refs_in_expr(value, name),
+        Decl(ast::Decl::Local(ast::LocalDecl { value, .. }))
+        | Decl(ast::Decl::Destructuring(ast::DestructuringDecl { value, .. })) => {
+            get_var_refs_in_expr(value, name)
+        }
+        Decl(ast::Decl::Class(ast::ClassDecl { body, params, .. })) => {
+            get_var_refs_in_defaults(params, name)
+                .into_iter()
+                .chain(
+                    body.inner
+                        .iter()
+                        .flat_map(|member| get_var_refs_in_stmt(member, name)),
+                )
+                .collect()
+        }
+        Decl(ast::Decl::Import(_)) | Decl(ast::Decl::Record(_)) | Decl(ast::Decl::Enum(_)) => {
+            vec![]
+        }
         Decl(ast::Decl::Function(ast::FunctionDecl { body, params, .. })) => {
             if params.iter().any(|param| param.name == name) {
                 return vec![];
             }
 
+            let references_in_defaults = get_var_refs_in_defaults(params, name);
+
             match body.as_ref() {
                 ast::Stmt::Block(ast::Block {
                     inner: ast::Ast { inner, .. },
                     ..
-                }) => inner
-                    .iter()
-                    .flat_map(|stmt| get_var_refs_in_stmt(stmt, name))
+                }) => references_in_defaults
+                    .into_iter()
+                    .chain(
+                        inner
+                            .iter()
+                            .flat_map(|stmt| get_var_refs_in_stmt(stmt, name)),
+                    )
                     .collect(),
-                _ => vec![],
+                _ => references_in_defaults,
             }
         }
         Expr(expr) => get_var_refs_in_expr(expr, name),
@@ -842,7 +1400,35 @@ fn get_var_refs_in_stmt(stmt: &ast::Stmt, name: &str) -> Vec<usize> {
             Some(expr) => get_var_refs_in_expr(expr, name),
             None => vec![],
         },
+        Throw(ast::Throw { value, .. }) => get_var_refs_in_expr(value, name),
+        Yield(ast::Yield { value, .. }) => get_var_refs_in_expr(value, name),
         Break(_) => vec![],
         Continue(_) => vec![],
+        Try(ast::Try {
+            body,
+            catch,
+            finally,
+            ..
+        }) => {
+            let mut references = get_var_refs_in_stmt(body, name);
+
+            if let Some(ast::Catch { body, .. }) = catch {
+                references.extend(get_var_refs_in_stmt(body, name));
+            }
+
+            if let Some(finally) = finally {
+                references.extend(get_var_refs_in_stmt(finally, name));
+            }
+
+            references
+        }
     }
 }
+
+fn get_var_refs_in_defaults(params: &[ast::FunctionParam], name: &str) -> Vec<usize> {
+    params
+        .iter()
+        .filter_map(|param| param.default.as_ref())
+        .flat_map(|default| get_var_refs_in_expr(default, name))
+        .collect()
+}
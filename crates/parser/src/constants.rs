@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+
+use tenda_common::span::SourceSpan;
+
+use crate::{
+    ast::{self, Ast},
+    parser_error::{ParserError, Result},
+};
+
+/// The names visible in each lexical scope, mapped to whether they were
+/// declared with `constante`.
+struct Scopes(Vec<HashMap<String, bool>>);
+
+impl Scopes {
+    fn new() -> Self {
+        Scopes(vec![HashMap::new()])
+    }
+
+    fn push(&mut self) {
+        self.0.push(HashMap::new());
+    }
+
+    fn pop(&mut self) {
+        self.0.pop();
+    }
+
+    fn declare(&mut self, name: &str, constant: bool) {
+        self.0
+            .last_mut()
+            .unwrap()
+            .insert(name.to_string(), constant);
+    }
+
+    fn is_constant(&self, name: &str) -> bool {
+        self.0
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    fn declare_params(&mut self, params: &[ast::FunctionParam]) -> Result<()> {
+        for param in params {
+            if let Some(default) = &param.default {
+                check_expr(default, self)?;
+            }
+
+            self.declare(&param.name, false);
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects assignments to names declared with `constante` whenever the
+/// declaration is lexically visible from the assignment. Assignments the
+/// parser cannot resolve are still checked by the runtime.
+pub fn check_constant_assignments(ast: &Ast) -> Result<()> {
+    let mut scopes = Scopes::new();
+
+    check_ast(ast, &mut scopes)
+}
+
+fn check_ast(ast: &Ast, scopes: &mut Scopes) -> Result<()> {
+    ast.inner
+        .iter()
+        .try_for_each(|stmt| check_stmt(stmt, scopes))
+}
+
+fn check_stmt(stmt: &ast::Stmt, scopes: &mut Scopes) -> Result<()> {
+    use ast::*;
+
+    match stmt {
+        Stmt::Expr(expr) => check_expr(expr, scopes),
+        Stmt::Decl(Decl::Local(LocalDecl {
+            name,
+            value,
+            constant,
+            ..
+        })) => {
+            check_expr(value, scopes)?;
+            scopes.declare(name, *constant);
+
+            Ok(())
+        }
+        Stmt::Decl(Decl::Destructuring(DestructuringDecl { pattern, value, .. })) => {
+            check_expr(value, scopes)?;
+
+            for binding in pattern.get_bindings() {
+                scopes.declare(&binding.name, false);
+            }
+
+            Ok(())
+        }
+        Stmt::Decl(Decl::Function(FunctionDecl {
+            name, params, body, ..
+        })) => {
+            scopes.declare(name, false);
+            scopes.push();
+
+            let result = scopes
+                .declare_params(params)
+                .and_then(|_| check_stmt(body, scopes));
+
+            scopes.pop();
+
+            result
+        }
+        Stmt::Decl(Decl::Class(ClassDecl {
+            name, params, body, ..
+        })) => {
+            scopes.declare(name, false);
+            scopes.push();
+
+            let result = scopes
+                .declare_params(params)
+                .and_then(|_| check_ast(body, scopes));
+
+            scopes.pop();
+
+            result
+        }
+        Stmt::Decl(decl @ (Decl::Record(_) | Decl::Enum(_) | Decl::Import(_))) => {
+            for (name, _) in decl.get_bindings() {
+                scopes.declare(name, false);
+            }
+
+            Ok(())
+        }
+        Stmt::Cond(Cond {
+            cond,
+            then,
+            or_else,
+            ..
+        }) => {
+            check_expr(cond, scopes)?;
+            check_stmt(then, scopes)?;
+
+            match or_else {
+                Some(or_else) => check_stmt(or_else, scopes),
+                None => Ok(()),
+            }
+        }
+        Stmt::While(While { cond, body, .. }) => {
+            check_expr(cond, scopes)?;
+            check_stmt(body, scopes)
+        }
+        Stmt::ForEach(ForEach {
+            item,
+            iterable,
+            body,
+            ..
+        }) => {
+            check_expr(iterable, scopes)?;
+
+            scopes.push();
+
+            for binding in item.get_bindings() {
+                scopes.declare(&binding.name, false);
+            }
+
+            let result = check_stmt(body, scopes);
+
+            scopes.pop();
+
+            result
+        }
+        Stmt::Block(Block { inner, .. }) => {
+            scopes.push();
+
+            let result = check_ast(inner, scopes);
+
+            scopes.pop();
+
+            result
+        }
+        Stmt::Return(Return { value, .. }) => match value {
+            Some(value) => check_expr(value, scopes),
+            None => Ok(()),
+        },
+        Stmt::Throw(Throw { value, .. }) | Stmt::Yield(Yield { value, .. }) => {
+            check_expr(value, scopes)
+        }
+        Stmt::Break(_) | Stmt::Continue(_) => Ok(()),
+        Stmt::Try(Try {
+            body,
+            catch,
+            finally,
+            ..
+        }) => {
+            check_stmt(body, scopes)?;
+
+            if let Some(Catch { param, body, .. }) = catch {
+                scopes.push();
+
+                if let Some(param) = param {
+                    scopes.declare(&param.name, false);
+                }
+
+                let result = check_stmt(body, scopes);
+
+                scopes.pop();
+                result?;
+            }
+
+            match finally {
+                Some(finally) => check_stmt(finally, scopes),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+fn check_expr(expr: &ast::Expr, scopes: &mut Scopes) -> Result<()> {
+    use ast::*;
+
+    match expr {
+        Expr::Assign(Assign { name, value, .. }) => {
+            match name.as_ref() {
+                Expr::Variable(Variable { name, span, .. }) => {
+                    check_assignment(name, span, scopes)?
+                }
+                target => check_expr(target, scopes)?,
+            }
+
+            check_expr(value, scopes)
+        }
+        Expr::DestructuringAssign(DestructuringAssign { pattern, value, .. }) => {
+            for binding in pattern.get_bindings() {
+                check_assignment(&binding.name, &binding.span, scopes)?;
+            }
+
+            check_expr(value, scopes)
+        }
+        Expr::Binary(BinaryOp { lhs, rhs, .. }) => {
+            check_expr(lhs, scopes)?;
+            check_expr(rhs, scopes)
+        }
+        Expr::Unary(UnaryOp { rhs, .. }) => check_expr(rhs, scopes),
+        Expr::Ternary(TernaryOp {
+            cond,
+            then,
+            or_else,
+            ..
+        }) => {
+            check_expr(cond, scopes)?;
+            check_expr(then, scopes)?;
+            check_expr(or_else, scopes)
+        }
+        Expr::Call(Call {
+            callee,
+            args,
+            named_args,
+            ..
+        }) => {
+            check_expr(callee, scopes)?;
+
+            args.iter()
+                .chain(named_args.iter().map(|arg| &arg.value))
+                .try_for_each(|arg| check_expr(arg, scopes))
+        }
+        Expr::Access(Access {
+            subscripted, index, ..
+        }) => {
+            check_expr(subscripted, scopes)?;
+            check_expr(index, scopes)
+        }
+        Expr::List(List { elements, .. }) => elements
+            .iter()
+            .try_for_each(|element| check_expr(element, scopes)),
+        Expr::AssociativeArray(AssociativeArray { elements, .. }) => elements
+            .iter()
+            .try_for_each(|(_, value)| check_expr(value, scopes)),
+        Expr::Comprehension(comprehension) => {
+            check_expr(&comprehension.iterable, scopes)?;
+
+            scopes.push();
+
+            for binding in comprehension.item.get_bindings() {
+                scopes.declare(&binding.name, false);
+            }
+
+            let result = comprehension
+                .get_scoped_exprs()
+                .into_iter()
+                .try_for_each(|expr| check_expr(expr, scopes));
+
+            scopes.pop();
+
+            result
+        }
+        Expr::Interpolation(Interpolation { parts, .. }) => {
+            parts.iter().try_for_each(|part| check_expr(part, scopes))
+        }
+        Expr::Grouping(Grouping { expr, .. }) => check_expr(expr, scopes),
+        Expr::AnonymousFunction(AnonymousFunction { params, body, .. }) => {
+            scopes.push();
+
+            let result = scopes
+                .declare_params(params)
+                .and_then(|_| check_stmt(body, scopes));
+
+            scopes.pop();
+
+            result
+        }
+        Expr::Match(Match {
+            value,
+            arms,
+            or_else,
+            ..
+        }) => {
+            check_expr(value, scopes)?;
+
+            for arm in arms {
+                scopes.push();
+
+                for binding in arm.pattern.get_bindings() {
+                    scopes.declare(&binding.name, false);
+                }
+
+                let result = match &arm.guard {
+                    Some(guard) => check_expr(guard, scopes),
+                    None => Ok(()),
+                }
+                .and_then(|_| check_stmt(&arm.body, scopes));
+
+                scopes.pop();
+                result?;
+            }
+
+            match or_else {
+                Some(or_else) => check_stmt(or_else, scopes),
+                None => Ok(()),
+            }
+        }
+        Expr::Literal(_) | Expr::Variable(_) => Ok(()),
+    }
+}
+
+fn check_assignment(name: &str, span: &SourceSpan, scopes: &Scopes) -> Result<()> {
+    if scopes.is_constant(name) {
+        return Err(vec![ParserError::ConstantReassignment {
+            name: name.to_string(),
+            span: span.clone(),
+            help: Some(format!(
+                "declare '{}' com `seja` se precisar atribuir um novo valor a ela",
+                name
+            )),
+        }]);
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 pub mod ast;
 mod closures;
+mod constants;
 mod parser;
 mod parser_error;
 mod scope_tracker;
@@ -1,3 +1,4 @@
+use num_traits::ToPrimitive;
 use tenda_common::{
     source::IdentifiedSource,
     span::{SourceSpan, Span},
@@ -6,7 +7,7 @@ use tenda_scanner::{self, Token, TokenKind};
 
 use crate::{
     ast::{self, FunctionParam},
-    closures,
+    closures, constants,
     parser_error::{unexpected_token, ParserError, Result},
     scope_tracker::{BlockScope, ScopeTracker},
     token_iter::TokenIterator,
@@ -40,6 +41,7 @@ impl<'a> Parser<'a> {
         };
 
         closures::annotate_ast_with_var_captures(&mut ast);
+        constants::check_constant_assignments(&ast)?;
 
         Ok(ast)
     }
@@ -90,15 +92,30 @@ impl<'a> Parser<'a> {
         };
 
         let result = match token.kind {
-            TokenKind::Let => self.parse_declaration(),
+            TokenKind::Let => {
+                if self.tokens.check_sequence(token_slice![Let, LeftBracket])
+                    || self.tokens.check_sequence(token_slice![Let, LeftBrace])
+                {
+                    self.parse_destructuring_declaration()
+                } else {
+                    self.parse_declaration()
+                }
+            }
             TokenKind::If => match self.try_parse_if_expression_as_stmt()? {
                 Some(expr_stmt) => Ok(expr_stmt),
                 None => self.parse_if_statement(),
             },
-            TokenKind::While => self.parse_while_statement(),
+            TokenKind::While => self.parse_while_statement(None),
+            TokenKind::Identifier => {
+                if self.tokens.check_sequence(token_slice![Identifier, Colon]) {
+                    self.parse_labeled_loop()
+                } else {
+                    self.parse_expression().map(ast::Stmt::Expr)
+                }
+            }
             TokenKind::ForOrBreak => {
                 if self.tokens.check_sequence(token_slice![ForOrBreak, Each]) {
-                    self.parse_for_each_statement()
+                    self.parse_for_each_statement(None)
                 } else {
                     self.parse_break_statement()
                 }
@@ -106,6 +123,14 @@ impl<'a> Parser<'a> {
             TokenKind::Do => self.parse_do_statement(),
             TokenKind::Return => self.parse_return_statement(),
             TokenKind::Continue => self.parse_continue_statement(),
+            TokenKind::Try => self.parse_try_statement(),
+            TokenKind::Throw => self.parse_throw_statement(),
+            TokenKind::Yield => self.parse_yield_statement(),
+            TokenKind::Import => self.parse_import_declaration(),
+            TokenKind::Record => self.parse_record_declaration(),
+            TokenKind::Enum => self.parse_enum_declaration(),
+            TokenKind::Class => self.parse_class_declaration(),
+            TokenKind::Const => self.parse_constant_declaration(),
             _ => self.parse_expression().map(ast::Stmt::Expr),
         }?;
 
@@ -116,7 +141,12 @@ impl<'a> Parser<'a> {
             Some(token)
                 if matches!(
                     token.kind,
-                    TokenKind::Eof | TokenKind::BlockEnd | TokenKind::Else
+                    TokenKind::Eof
+                        | TokenKind::BlockEnd
+                        | TokenKind::Else
+                        | TokenKind::Case
+                        | TokenKind::Catch
+                        | TokenKind::Finally
                 ) => {}
             Some(token) => {
                 return Err(vec![unexpected_token!(token)]);
@@ -254,7 +284,38 @@ impl<'a> Parser<'a> {
         Ok(ast::Stmt::Cond(stmt))
     }
 
-    fn parse_while_statement(&mut self) -> Result<ast::Stmt> {
+    /// Parses a loop preceded by `rótulo:`, which `pare` and `continua` in
+    /// nested loops can name to act on it instead of on the innermost loop.
+    fn parse_labeled_loop(&mut self) -> Result<ast::Stmt> {
+        let (label, label_span) = self.consume_identifier()?;
+
+        self.skip_token(TokenKind::Colon)?;
+
+        if self
+            .scope
+            .has_scope_in_function(BlockScope::Label(label.clone()))
+        {
+            return Err(vec![ParserError::DuplicateLoopLabel {
+                label,
+                span: label_span,
+            }]);
+        }
+
+        let _guard = self.scope.guard(BlockScope::Label(label.clone()));
+
+        if self.tokens.is_next_token(TokenKind::While) {
+            self.parse_while_statement(Some(label))
+        } else if self.tokens.check_sequence(token_slice![ForOrBreak, Each]) {
+            self.parse_for_each_statement(Some(label))
+        } else {
+            Err(vec![ParserError::LabelWithoutLoop {
+                label,
+                span: label_span,
+            }])
+        }
+    }
+
+    fn parse_while_statement(&mut self, label: Option<String>) -> Result<ast::Stmt> {
         let span_start = self.tokens.next().unwrap().span.start();
         let condition = self.parse_expression()?;
 
@@ -268,18 +329,18 @@ impl<'a> Parser<'a> {
         let span_end = body.get_span().end();
         let span = SourceSpan::new(span_start, span_end, self.source_id);
 
-        let while_stmt = ast::While::new(condition, body, span);
+        let while_stmt = ast::While::new(condition, body, label, span);
         let while_stmt = ast::Stmt::While(while_stmt);
 
         Ok(while_stmt)
     }
 
-    fn parse_for_each_statement(&mut self) -> Result<ast::Stmt> {
+    fn parse_for_each_statement(&mut self, label: Option<String>) -> Result<ast::Stmt> {
         let span_start = self.tokens.next().unwrap().span.start();
 
         self.skip_token(TokenKind::Each)?;
 
-        let (name, name_span) = self.consume_identifier()?;
+        let item = self.parse_for_each_item()?;
 
         self.skip_token(TokenKind::In)?;
 
@@ -296,13 +357,97 @@ impl<'a> Parser<'a> {
         let span_end = body.get_span().end();
         let span = SourceSpan::new(span_start, span_end, self.source_id);
 
-        let for_each_item = ast::ForEachItem::new(name, self.gen_uid(), name_span);
-        let for_each_stmt = ast::ForEach::new(for_each_item, iterable, body, span);
+        let for_each_stmt = ast::ForEach::new(item, iterable, body, label, span);
         let for_each_stmt = ast::Stmt::ForEach(for_each_stmt);
 
         Ok(for_each_stmt)
     }
 
+    fn parse_for_each_item(&mut self) -> Result<ast::ForEachItem> {
+        let item = self.parse_destructuring_pattern()?;
+
+        match self.tokens.consume_one_of(token_slice![Comma]) {
+            Some(_) => Ok(ast::ForEachItem::Pair(
+                item,
+                self.parse_destructuring_pattern()?,
+            )),
+            None => Ok(ast::ForEachItem::Single(item)),
+        }
+    }
+
+    fn parse_try_statement(&mut self) -> Result<ast::Stmt> {
+        let try_token = self.tokens.next().unwrap();
+        let span_start = try_token.span.start();
+
+        let (body, mut block_end_delimiter) = self.parse_block_contents(
+            token_slice![Catch, Finally, BlockEnd],
+            BlockScope::Try,
+            Some(span_start),
+        )?;
+
+        let mut span_end = body.get_span().end();
+
+        let catch = match block_end_delimiter {
+            TokenKind::Catch => {
+                let param = match self.tokens.consume_one_of(token_slice![Identifier]) {
+                    Some(token) => {
+                        let name = match token.literal.as_ref().unwrap() {
+                            tenda_scanner::Literal::String(string) => string.clone(),
+                            _ => unreachable!(),
+                        };
+
+                        Some(ast::CatchParam::new(
+                            name,
+                            self.gen_uid(),
+                            token.span.clone(),
+                        ))
+                    }
+                    None => None,
+                };
+
+                let (catch_body, delimiter) = self.parse_block_contents(
+                    token_slice![Finally, BlockEnd],
+                    BlockScope::Catch,
+                    None,
+                )?;
+
+                block_end_delimiter = delimiter;
+                span_end = catch_body.get_span().end();
+
+                let catch_start = param
+                    .as_ref()
+                    .map_or(catch_body.get_span().start(), |param| param.span.start());
+                let span = SourceSpan::new(catch_start, span_end, self.source_id);
+
+                Some(ast::Catch::new(param, catch_body, span))
+            }
+            _ => None,
+        };
+
+        let finally = match block_end_delimiter {
+            TokenKind::Finally => {
+                let (finally_body, _) =
+                    self.parse_block_contents(token_slice![BlockEnd], BlockScope::Finally, None)?;
+
+                span_end = finally_body.get_span().end();
+
+                Some(finally_body)
+            }
+            _ => None,
+        };
+
+        if catch.is_none() && finally.is_none() {
+            return Err(vec![ParserError::MissingCatchOrFinally {
+                span: try_token.span.clone(),
+            }]);
+        }
+
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+        let try_stmt = ast::Try::new(body, catch, finally, span);
+
+        Ok(ast::Stmt::Try(try_stmt))
+    }
+
     fn parse_declaration(&mut self) -> Result<ast::Stmt> {
         let span_start = self.tokens.next().unwrap().span.start();
         let (name, _) = self.consume_identifier()?;
@@ -358,6 +503,262 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_constant_declaration(&mut self) -> Result<ast::Stmt> {
+        let span_start = self.tokens.next().unwrap().span.start();
+        let (name, _) = self.consume_identifier()?;
+
+        self.skip_token(TokenKind::EqualSign)?;
+
+        let expr = self.parse_expression()?;
+
+        let span_end = expr.get_span().end();
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let constant_decl = ast::LocalDecl::new_constant(name, expr, self.gen_uid(), span);
+        let constant_decl = ast::Decl::Local(constant_decl);
+
+        Ok(ast::Stmt::Decl(constant_decl))
+    }
+
+    fn parse_destructuring_declaration(&mut self) -> Result<ast::Stmt> {
+        let span_start = self.tokens.next().unwrap().span.start();
+        let pattern = self.parse_destructuring_pattern()?;
+
+        self.skip_token(TokenKind::EqualSign)?;
+
+        let value = self.parse_expression()?;
+
+        let span_end = value.get_span().end();
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let destructuring_decl = ast::DestructuringDecl::new(pattern, value, span);
+        let destructuring_decl = ast::Decl::Destructuring(destructuring_decl);
+
+        Ok(ast::Stmt::Decl(destructuring_decl))
+    }
+
+    fn parse_import_declaration(&mut self) -> Result<ast::Stmt> {
+        let span_start = self.tokens.next().unwrap().span.start();
+
+        let path_token = match self.tokens.next() {
+            Some(token) if token.kind == TokenKind::String => token,
+            Some(token) => return Err(vec![unexpected_token!(token)]),
+            None => {
+                return Err(vec![ParserError::UnexpectedEoi {
+                    span: self.tokens.last_token().span.clone(),
+                }])
+            }
+        };
+
+        let path = match path_token.literal.as_ref().unwrap() {
+            tenda_scanner::Literal::String(string) => string.clone(),
+            _ => unreachable!(),
+        };
+
+        let (name, span_end) = if self.tokens.consume_one_of(token_slice![As]).is_some() {
+            let (name, name_span) = self.consume_identifier()?;
+
+            (name, name_span.end())
+        } else {
+            let name = std::path::Path::new(&path)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|stem| is_valid_identifier(stem))
+                .ok_or_else(|| {
+                    vec![ParserError::InvalidModuleName {
+                        path: path.clone(),
+                        span: path_token.span.clone(),
+                        help: Some(format!(
+                            "dê um nome ao módulo usando `como`: `importe \"{}\" como nome`",
+                            path
+                        )),
+                    }]
+                })?;
+
+            (name.to_string(), path_token.span.end())
+        };
+
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let import_decl = ast::ImportDecl::new(path, name, self.gen_uid(), span);
+        let import_decl = ast::Decl::Import(import_decl);
+
+        Ok(ast::Stmt::Decl(import_decl))
+    }
+
+    fn parse_record_declaration(&mut self) -> Result<ast::Stmt> {
+        let span_start = self.tokens.next().unwrap().span.start();
+        let (name, _) = self.consume_identifier()?;
+
+        self.skip_token(TokenKind::LeftParen)?;
+
+        let mut fields: Vec<String> = vec![];
+
+        {
+            let _guard = self.tokens.set_ignoring_newline();
+
+            if self
+                .tokens
+                .consume_one_of(token_slice![RightParen])
+                .is_none()
+            {
+                loop {
+                    let (field_name, field_span) = self.consume_identifier()?;
+
+                    if fields.contains(&field_name) {
+                        return Err(vec![ParserError::DuplicateField {
+                            name: field_name,
+                            span: field_span,
+                        }]);
+                    }
+
+                    fields.push(field_name);
+
+                    if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
+                        break;
+                    }
+                }
+
+                self.skip_token(TokenKind::RightParen)?;
+            }
+        }
+
+        self.tokens.advance_while(token_slice![Newline]);
+
+        let span_end = self.skip_token(TokenKind::BlockEnd)?.span.end();
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let record_decl = ast::RecordDecl::new(name, fields, self.gen_uid(), span);
+        let record_decl = ast::Decl::Record(record_decl);
+
+        Ok(ast::Stmt::Decl(record_decl))
+    }
+
+    fn parse_enum_declaration(&mut self) -> Result<ast::Stmt> {
+        let span_start = self.tokens.next().unwrap().span.start();
+        let (name, _) = self.consume_identifier()?;
+
+        if self.tokens.consume_one_of(token_slice![Colon]).is_none() {
+            return Err(vec![ParserError::MissingColon {
+                span: self.tokens.next().unwrap().span.clone(),
+            }]);
+        }
+
+        let mut variants: Vec<String> = vec![];
+
+        {
+            let _guard = self.tokens.set_ignoring_newline();
+
+            loop {
+                let (variant_name, variant_span) = self.consume_identifier()?;
+
+                if variants.contains(&variant_name) {
+                    return Err(vec![ParserError::DuplicateEnumVariant {
+                        name: variant_name,
+                        span: variant_span,
+                    }]);
+                }
+
+                variants.push(variant_name);
+
+                if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
+                    break;
+                }
+            }
+        }
+
+        self.tokens.advance_while(token_slice![Newline]);
+
+        let span_end = self.skip_token(TokenKind::BlockEnd)?.span.end();
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let enum_decl = ast::EnumDecl::new(name, variants, self.gen_uid(), span);
+        let enum_decl = ast::Decl::Enum(enum_decl);
+
+        Ok(ast::Stmt::Decl(enum_decl))
+    }
+
+    fn parse_class_declaration(&mut self) -> Result<ast::Stmt> {
+        let span_start = self.tokens.next().unwrap().span.start();
+        let (name, _) = self.consume_identifier()?;
+
+        let params = if self.tokens.is_next_token(TokenKind::LeftParen) {
+            self.parse_function_parameters_signature()?
+        } else {
+            vec![]
+        };
+
+        let mut members: Vec<ast::Stmt> = vec![];
+
+        let body_start = self.tokens.peek().unwrap().span.start();
+
+        let span_end = loop {
+            self.tokens.advance_while(token_slice![Newline]);
+
+            let token = self.tokens.peek().unwrap().clone_ref();
+
+            let member = match token.kind {
+                TokenKind::BlockEnd => break self.tokens.next().unwrap().span.end(),
+                TokenKind::Let
+                    if self
+                        .tokens
+                        .check_sequence(token_slice![Let, Identifier, LeftParen]) =>
+                {
+                    let _guard = self.scope.guard(BlockScope::Class);
+
+                    self.parse_declaration()?
+                }
+                TokenKind::Let => self.parse_declaration()?,
+                TokenKind::Eof => {
+                    return Err(vec![ParserError::UnexpectedEoi {
+                        span: token.span.clone(),
+                    }])
+                }
+                _ => return Err(vec![unexpected_token!(token)]),
+            };
+
+            let member_name = match &member {
+                ast::Stmt::Decl(ast::Decl::Local(local)) => local.name.clone(),
+                ast::Stmt::Decl(ast::Decl::Function(function)) => function.name.clone(),
+                _ => unreachable!(),
+            };
+
+            let is_duplicate = params.iter().any(|param| param.name == member_name)
+                || members.iter().any(|other| match other {
+                    ast::Stmt::Decl(decl) => decl.declares(&member_name),
+                    _ => false,
+                });
+
+            if is_duplicate {
+                return Err(vec![ParserError::DuplicateClassMember {
+                    name: member_name,
+                    span: member.get_span().clone(),
+                }]);
+            }
+
+            members.push(member);
+
+            match self.tokens.peek() {
+                Some(token) if matches!(token.kind, TokenKind::Newline | TokenKind::BlockEnd) => {}
+                Some(token) => {
+                    let token = token.clone_ref();
+                    return Err(vec![unexpected_token!(token)]);
+                }
+                None => unreachable!(),
+            }
+        };
+
+        let body_span = SourceSpan::new(body_start, span_end, self.source_id);
+        let body = ast::Ast::from(members, body_span);
+
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let class_decl = ast::ClassDecl::new(name, params, body, self.gen_uid(), span);
+        let class_decl = ast::Decl::Class(class_decl);
+
+        Ok(ast::Stmt::Decl(class_decl))
+    }
+
     fn parse_do_statement(&mut self) -> Result<ast::Stmt> {
         let span_start = self.tokens.next().unwrap().span.start();
 
@@ -407,7 +808,13 @@ impl<'a> Parser<'a> {
             }]);
         }
 
-        let break_stmt = ast::Break::new(break_token.span.clone());
+        let label = self.parse_loop_label()?;
+        let span_end = label
+            .as_ref()
+            .map_or(break_token.span.end(), |(_, span)| span.end());
+        let span = SourceSpan::new(break_token.span.start(), span_end, self.source_id);
+
+        let break_stmt = ast::Break::new(label.map(|(label, _)| label), span);
         let break_stmt = ast::Stmt::Break(break_stmt);
 
         Ok(break_stmt)
@@ -422,12 +829,88 @@ impl<'a> Parser<'a> {
             }]);
         }
 
-        let continue_stmt = ast::Continue::new(continue_token.span.clone());
+        let label = self.parse_loop_label()?;
+        let span_end = label
+            .as_ref()
+            .map_or(continue_token.span.end(), |(_, span)| span.end());
+        let span = SourceSpan::new(continue_token.span.start(), span_end, self.source_id);
+
+        let continue_stmt = ast::Continue::new(label.map(|(label, _)| label), span);
         let continue_stmt = ast::Stmt::Continue(continue_stmt);
 
         Ok(continue_stmt)
     }
 
+    /// Parses the optional label after `pare` or `continua`, which must name
+    /// a loop enclosing it in the same function.
+    fn parse_loop_label(&mut self) -> Result<Option<(String, SourceSpan)>> {
+        if !self.tokens.is_next_token(TokenKind::Identifier) {
+            return Ok(None);
+        }
+
+        let (label, span) = self.consume_identifier()?;
+
+        if !self
+            .scope
+            .has_scope_in_function(BlockScope::Label(label.clone()))
+        {
+            return Err(vec![ParserError::UndefinedLoopLabel { label, span }]);
+        }
+
+        Ok(Some((label, span)))
+    }
+
+    fn parse_throw_statement(&mut self) -> Result<ast::Stmt> {
+        let throw_token = self.tokens.next().unwrap();
+        let expr = self.parse_expression()?;
+
+        let span_start = throw_token.span.start();
+        let span_end = expr.get_span().end();
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let throw_stmt = ast::Throw::new(expr, span);
+
+        Ok(ast::Stmt::Throw(throw_stmt))
+    }
+
+    fn parse_yield_statement(&mut self) -> Result<ast::Stmt> {
+        let yield_token = self.tokens.next().unwrap();
+
+        if !self.scope.has_scope(BlockScope::Function) {
+            return Err(vec![ParserError::IllegalYield {
+                span: yield_token.span.clone(),
+            }]);
+        }
+
+        let is_unsupported_position = [
+            BlockScope::Try,
+            BlockScope::Catch,
+            BlockScope::Finally,
+            BlockScope::Match,
+        ]
+        .into_iter()
+        .any(|scope| self.scope.has_scope_in_function(scope));
+
+        if is_unsupported_position {
+            return Err(vec![ParserError::UnsupportedYieldPosition {
+                span: yield_token.span.clone(),
+                help: Some(
+                    "guarde o valor em uma variável e use 'produza' depois do bloco".to_string(),
+                ),
+            }]);
+        }
+
+        let expr = self.parse_expression()?;
+
+        let span_start = yield_token.span.start();
+        let span_end = expr.get_span().end();
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let yield_stmt = ast::Yield::new(expr, span);
+
+        Ok(ast::Stmt::Yield(yield_stmt))
+    }
+
     fn parse_expression(&mut self) -> Result<ast::Expr> {
         let _guard = self.tokens.set_ignoring_newline();
 
@@ -435,18 +918,43 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_assignment(&mut self) -> Result<ast::Expr> {
+        if let Some(destructuring) = self.try_parse_destructuring_assignment()? {
+            return Ok(destructuring);
+        }
+
         let expr = self.parse_ternary()?;
 
-        if let Some(equal_sign) = self.tokens.consume_one_of(token_slice![EqualSign]) {
+        if let Some(equal_sign) = self.tokens.consume_one_of(token_slice![
+            EqualSign,
+            PlusEqual,
+            MinusEqual,
+            StarEqual,
+            SlashEqual,
+            BackslashEqual,
+            PercentEqual,
+            CaretEqual
+        ]) {
+            let op = ast::BinaryOperator::from_compound_assignment(equal_sign.kind);
             let value = self.parse_assignment()?;
 
             return match expr {
-                ast::Expr::Variable(_) | ast::Expr::Access(_) => {
+                ast::Expr::Variable(ast::Variable { ref name, .. })
+                    if name == ast::Variable::THIS =>
+                {
+                    Err(vec![ParserError::InvalidAssignmentTarget {
+                        span: equal_sign.span.clone(),
+                        token: equal_sign.clone_ref(),
+                    }])
+                }
+                ast::Expr::Variable(_)
+                | ast::Expr::Access(ast::Access {
+                    optional: false, ..
+                }) => {
                     let span_start = expr.get_span().start();
                     let span_end = value.get_span().end();
                     let span = SourceSpan::new(span_start, span_end, self.source_id);
 
-                    let assign_expr = ast::Assign::new(expr, value, span);
+                    let assign_expr = ast::Assign::new(expr, op, value, span);
                     let assign_expr = ast::Expr::Assign(assign_expr);
 
                     Ok(assign_expr)
@@ -461,16 +969,79 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
+    fn try_parse_destructuring_assignment(&mut self) -> Result<Option<ast::Expr>> {
+        if !self.tokens.is_next_token(TokenKind::LeftBracket)
+            && !self.tokens.is_next_token(TokenKind::LeftBrace)
+        {
+            return Ok(None);
+        }
+
+        let saved_tokens = self.tokens.clone();
+
+        let pattern = match self.parse_pattern() {
+            Ok(pattern) if self.tokens.is_next_token(TokenKind::EqualSign) => pattern,
+            _ => {
+                self.tokens = saved_tokens;
+                return Ok(None);
+            }
+        };
+
+        validate_destructuring_pattern(&pattern)?;
+
+        self.skip_token(TokenKind::EqualSign)?;
+
+        let value = self.parse_assignment()?;
+
+        let span_start = pattern.get_span().start();
+        let span_end = value.get_span().end();
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+        let assign_expr = ast::DestructuringAssign::new(pattern, value, span);
+
+        Ok(Some(ast::Expr::DestructuringAssign(assign_expr)))
+    }
+
     fn parse_ternary(&mut self) -> Result<ast::Expr> {
         if let Some(token) = self.tokens.consume_one_of(token_slice![If]) {
             let span_start = token.span.start();
-            let condition = self.parse_logical_or()?;
+            let condition = self.parse_pipeline()?;
 
             self.skip_token(TokenKind::Then)?;
             self.parse_ternary_branches(condition, span_start)
         } else {
-            self.parse_logical_or()
+            self.parse_pipeline()
+        }
+    }
+
+    fn parse_pipeline(&mut self) -> Result<ast::Expr> {
+        let mut expr = self.parse_nil_coalescing()?;
+
+        while self.tokens.consume_one_of(token_slice![Pipeline]).is_some() {
+            let stage = self.parse_nil_coalescing()?;
+
+            expr = pipe_into(expr, stage);
+        }
+
+        Ok(expr)
+    }
+
+    fn parse_nil_coalescing(&mut self) -> Result<ast::Expr> {
+        let mut expr = self.parse_logical_or()?;
+
+        while let Some(op) = self.tokens.consume_one_of(token_slice![DoubleQuestion]) {
+            let lhs = expr;
+            let rhs = self.parse_logical_or()?;
+
+            let span_start = lhs.get_span().start();
+            let span_end = rhs.get_span().end();
+            let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+            let binary_op = ast::BinaryOp::new(lhs, op.into(), rhs, span);
+
+            expr = ast::Expr::Binary(binary_op);
         }
+
+        Ok(expr)
     }
 
     fn parse_logical_or(&mut self) -> Result<ast::Expr> {
@@ -588,10 +1159,33 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_range(&mut self) -> Result<ast::Expr> {
-        let lhs = self.parse_term()?;
+        let lhs = self.parse_bitwise_or()?;
 
         if let Some(op) = self.tokens.consume_one_of(token_slice![Until]) {
-            let rhs = self.parse_term()?;
+            let rhs = self.parse_bitwise_or()?;
+
+            let span_start = lhs.get_span().start();
+            let mut span_end = rhs.get_span().end();
+
+            let op = match self.tokens.consume_one_of(token_slice![Exclusive]) {
+                Some(exclusive) => {
+                    span_end = exclusive.span.end();
+                    ast::BinaryOperator::ExclusiveRange
+                }
+                None => op.into(),
+            };
+
+            let span = SourceSpan::new(span_start, span_end, self.source_id);
+            let mut expr = ast::Expr::Binary(ast::BinaryOp::new(lhs, op, rhs, span));
+
+            if let Some(op) = self.tokens.consume_one_of(token_slice![Step]) {
+                let step = self.parse_bitwise_or()?;
+
+                let span_end = step.get_span().end();
+                let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+                expr = ast::Expr::Binary(ast::BinaryOp::new(expr, op.into(), step, span));
+            }
 
             if let Some(op) = self.tokens.consume_one_of(token_slice![Until]) {
                 return Err(vec![ParserError::InvalidChaining {
@@ -602,16 +1196,89 @@ impl<'a> Parser<'a> {
                 }]);
             }
 
+            return Ok(expr);
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_bitwise_or(&mut self) -> Result<ast::Expr> {
+        let mut expr = self.parse_bitwise_xor()?;
+
+        while let Some(op) = self.tokens.consume_one_of(token_slice![Pipe]) {
+            let lhs = expr;
+            let rhs = self.parse_bitwise_xor()?;
+
             let span_start = lhs.get_span().start();
             let span_end = rhs.get_span().end();
             let span = SourceSpan::new(span_start, span_end, self.source_id);
 
             let binary_op = ast::BinaryOp::new(lhs, op.into(), rhs, span);
 
-            return Ok(ast::Expr::Binary(binary_op));
+            expr = ast::Expr::Binary(binary_op);
         }
 
-        Ok(lhs)
+        Ok(expr)
+    }
+
+    fn parse_bitwise_xor(&mut self) -> Result<ast::Expr> {
+        let mut expr = self.parse_bitwise_and()?;
+
+        while let Some(op) = self.tokens.consume_one_of(token_slice![Tilde]) {
+            let lhs = expr;
+            let rhs = self.parse_bitwise_and()?;
+
+            let span_start = lhs.get_span().start();
+            let span_end = rhs.get_span().end();
+            let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+            let binary_op = ast::BinaryOp::new(lhs, op.into(), rhs, span);
+
+            expr = ast::Expr::Binary(binary_op);
+        }
+
+        Ok(expr)
+    }
+
+    fn parse_bitwise_and(&mut self) -> Result<ast::Expr> {
+        let mut expr = self.parse_shift()?;
+
+        while let Some(op) = self.tokens.consume_one_of(token_slice![Ampersand]) {
+            let lhs = expr;
+            let rhs = self.parse_shift()?;
+
+            let span_start = lhs.get_span().start();
+            let span_end = rhs.get_span().end();
+            let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+            let binary_op = ast::BinaryOp::new(lhs, op.into(), rhs, span);
+
+            expr = ast::Expr::Binary(binary_op);
+        }
+
+        Ok(expr)
+    }
+
+    fn parse_shift(&mut self) -> Result<ast::Expr> {
+        let mut expr = self.parse_term()?;
+
+        while let Some(op) = self
+            .tokens
+            .consume_one_of(token_slice![ShiftLeft, ShiftRight])
+        {
+            let lhs = expr;
+            let rhs = self.parse_term()?;
+
+            let span_start = lhs.get_span().start();
+            let span_end = rhs.get_span().end();
+            let span = SourceSpan::new(span_start, span_end, self.source_id);
+
+            let binary_op = ast::BinaryOp::new(lhs, op.into(), rhs, span);
+
+            expr = ast::Expr::Binary(binary_op);
+        }
+
+        Ok(expr)
     }
 
     fn parse_term(&mut self) -> Result<ast::Expr> {
@@ -638,7 +1305,7 @@ impl<'a> Parser<'a> {
 
         while let Some(op) = self
             .tokens
-            .consume_one_of(token_slice![Star, Slash, Percent])
+            .consume_one_of(token_slice![Star, Slash, Backslash, Percent])
         {
             let lhs = expr;
             let rhs = self.parse_exponent()?;
@@ -675,7 +1342,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_unary(&mut self) -> Result<ast::Expr> {
-        if let Some(op) = self.tokens.consume_one_of(token_slice![Minus, Not]) {
+        if let Some(op) = self.tokens.consume_one_of(token_slice![Minus, Not, Tilde]) {
             let rhs = self.parse_unary()?;
 
             let span_start = op.span.start();
@@ -693,14 +1360,25 @@ impl<'a> Parser<'a> {
     fn parse_postfix(&mut self) -> Result<ast::Expr> {
         let mut lhs = self.parse_primary()?;
 
-        while let Some(token) =
-            self.tokens
-                .consume_one_of(token_slice![LeftParen, LeftBracket, Dot])
-        {
+        loop {
+            // calls and indexing must start on the same line as their target, so that a
+            // statement beginning with `[` or `(` is not read as part of the previous line
+            let operators: &[TokenKind] = match self.tokens.is_next_raw_newline() {
+                true => token_slice![Dot, QuestionDot],
+                false => token_slice![LeftParen, LeftBracket, Dot, QuestionBracket, QuestionDot],
+            };
+
+            let token = match self.tokens.consume_one_of(operators) {
+                Some(token) => token,
+                None => break,
+            };
+
             match token.kind {
                 TokenKind::LeftParen => lhs = self.parse_function_call(lhs)?,
-                TokenKind::LeftBracket => lhs = self.parse_access(lhs)?,
-                TokenKind::Dot => lhs = self.parse_dot_access(lhs)?,
+                TokenKind::LeftBracket => lhs = self.parse_access(lhs, false)?,
+                TokenKind::Dot => lhs = self.parse_dot_access(lhs, false)?,
+                TokenKind::QuestionBracket => lhs = self.parse_access(lhs, true)?,
+                TokenKind::QuestionDot => lhs = self.parse_dot_access(lhs, true)?,
                 _ => unreachable!(),
             }
         }
@@ -709,11 +1387,45 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_function_call(&mut self, name: ast::Expr) -> Result<ast::Expr> {
+        let _guard = self.tokens.set_ignoring_newline();
+
         let mut arguments = vec![];
+        let mut named_arguments: Vec<ast::NamedArg> = vec![];
 
         if !self.tokens.is_next_token(TokenKind::RightParen) {
             loop {
-                arguments.push(self.parse_expression()?);
+                if self.tokens.check_sequence(token_slice![Identifier, Colon]) {
+                    let (arg_name, name_span) = self.consume_identifier()?;
+
+                    self.skip_token(TokenKind::Colon)?;
+
+                    if named_arguments.iter().any(|arg| arg.name == arg_name) {
+                        return Err(vec![ParserError::DuplicateNamedArgument {
+                            name: arg_name,
+                            span: name_span,
+                        }]);
+                    }
+
+                    let value = self.parse_expression()?;
+                    let span =
+                        SourceSpan::new(name_span.start(), value.get_span().end(), self.source_id);
+
+                    named_arguments.push(ast::NamedArg::new(arg_name, value, span));
+                } else {
+                    let argument = self.parse_expression()?;
+
+                    if !named_arguments.is_empty() {
+                        return Err(vec![ParserError::PositionalArgumentAfterNamed {
+                            span: argument.get_span().clone(),
+                            help: Some(
+                                "passe os argumentos posicionais antes dos argumentos nomeados"
+                                    .to_string(),
+                            ),
+                        }]);
+                    }
+
+                    arguments.push(argument);
+                }
 
                 if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
                     break;
@@ -740,13 +1452,13 @@ impl<'a> Parser<'a> {
         let span_end = right_paran.span.end();
         let span = SourceSpan::new(span_start, span_end, self.source_id);
 
-        let call_expr = ast::Call::new(name, arguments, span);
+        let call_expr = ast::Call::new(name, arguments, named_arguments, span);
         let call_expr = ast::Expr::Call(call_expr);
 
         Ok(call_expr)
     }
 
-    fn parse_access(&mut self, name: ast::Expr) -> Result<ast::Expr> {
+    fn parse_access(&mut self, name: ast::Expr, optional: bool) -> Result<ast::Expr> {
         let index = self.parse_expression()?;
 
         if self.tokens.is_next_eof() {
@@ -768,7 +1480,7 @@ impl<'a> Parser<'a> {
         let span_end = closing_bracket.span.end();
         let span = SourceSpan::new(span_start, span_end, self.source_id);
 
-        let access_expr = ast::Access::new(name, index, span);
+        let access_expr = ast::Access::new(name, index, optional, span);
         let access_expr = ast::Expr::Access(access_expr);
 
         Ok(access_expr)
@@ -786,11 +1498,14 @@ impl<'a> Parser<'a> {
 
         match token.kind {
             Number | True | False | String | Nil => self.parse_literal(),
+            InterpolatedString => self.parse_interpolated_string(),
             Identifier => self.parse_variable(),
             LeftParen => self.parse_grouping(),
             LeftBracket => self.parse_list(),
             LeftBrace => self.parse_associative_array(),
             Function => self.parse_anonymous_function(),
+            This => self.parse_this(),
+            Match => self.parse_match(),
             Eof => {
                 self.tokens.next().unwrap();
                 Err(vec![ParserError::UnexpectedEoi { span }])
@@ -815,6 +1530,52 @@ impl<'a> Parser<'a> {
         Ok(literal_expr)
     }
 
+    fn parse_interpolated_string(&mut self) -> Result<ast::Expr> {
+        let token = self.tokens.next().unwrap();
+
+        let parts = match token.literal.as_ref() {
+            Some(tenda_scanner::Literal::Interpolation(parts)) => parts,
+            _ => unreachable!(),
+        };
+
+        let mut exprs = Vec::with_capacity(parts.len());
+
+        for part in parts {
+            match part {
+                tenda_scanner::StringPart::Text(text) if text.is_empty() => {}
+                tenda_scanner::StringPart::Text(text) => {
+                    let literal = tenda_scanner::Literal::String(text.clone());
+                    let literal = ast::Literal::new(literal, token.span.clone());
+
+                    exprs.push(ast::Expr::Literal(literal));
+                }
+                tenda_scanner::StringPart::Expr(tokens) => {
+                    exprs.push(self.parse_embedded_expression(tokens)?);
+                }
+            }
+        }
+
+        let interpolation = ast::Interpolation::new(exprs, token.span.clone());
+
+        Ok(ast::Expr::Interpolation(interpolation))
+    }
+
+    fn parse_embedded_expression(&mut self, tokens: &'a [Token]) -> Result<ast::Expr> {
+        let outer_tokens = std::mem::replace(&mut self.tokens, tokens.into());
+
+        let result = self
+            .parse_expression()
+            .and_then(|expr| match self.tokens.peek() {
+                Some(token) if token.kind == TokenKind::Eof => Ok(expr),
+                Some(token) => Err(vec![unexpected_token!(token)]),
+                None => unreachable!(),
+            });
+
+        self.tokens = outer_tokens;
+
+        result
+    }
+
     fn parse_variable(&mut self) -> Result<ast::Expr> {
         let token = self.tokens.next().unwrap();
         let span = token.span.clone();
@@ -830,6 +1591,24 @@ impl<'a> Parser<'a> {
         Ok(ast::Expr::Variable(variable_expr))
     }
 
+    fn parse_this(&mut self) -> Result<ast::Expr> {
+        let token = self.tokens.next().unwrap();
+
+        if !self.scope.has_scope(BlockScope::Class) {
+            return Err(vec![ParserError::IllegalThis {
+                span: token.span.clone(),
+            }]);
+        }
+
+        let variable = ast::Variable::new(
+            ast::Variable::THIS.to_string(),
+            self.gen_uid(),
+            token.span.clone(),
+        );
+
+        Ok(ast::Expr::Variable(variable))
+    }
+
     fn parse_grouping(&mut self) -> Result<ast::Expr> {
         let token = self.tokens.next().unwrap();
         let expr = self.parse_expression()?;
@@ -917,6 +1696,14 @@ impl<'a> Parser<'a> {
         let mut parameters: Vec<FunctionParam> = vec![];
 
         loop {
+            if let Some(variadic) = parameters.iter().find(|p| p.variadic) {
+                return Err(vec![ParserError::VariadicParameterNotLast {
+                    name: variadic.name.clone(),
+                    span: variadic.span.clone(),
+                }]);
+            }
+
+            let ellipsis = self.tokens.consume_one_of(token_slice![Ellipsis]);
             let (param_name, param_span) = self.consume_identifier()?;
 
             if parameters.iter().any(|p| p.name == param_name) {
@@ -926,7 +1713,44 @@ impl<'a> Parser<'a> {
                 }]);
             }
 
-            parameters.push(FunctionParam::new(param_name, self.gen_uid(), param_span));
+            if let Some(ellipsis) = ellipsis {
+                let span = SourceSpan::new(ellipsis.span.start(), param_span.end(), self.source_id);
+
+                parameters.push(FunctionParam::new_variadic(
+                    param_name,
+                    self.gen_uid(),
+                    span,
+                ));
+
+                if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
+                    break;
+                }
+
+                continue;
+            }
+
+            let default = match self.tokens.consume_one_of(token_slice![EqualSign]) {
+                Some(_) => Some(self.parse_expression()?),
+                None => None,
+            };
+
+            if default.is_none() && parameters.iter().any(|p| p.default.is_some()) {
+                return Err(vec![ParserError::RequiredParameterAfterDefault {
+                    name: param_name,
+                    span: param_span,
+                    help: Some(
+                        "declare os parâmetros com valor padrão depois dos obrigatórios"
+                            .to_string(),
+                    ),
+                }]);
+            }
+
+            parameters.push(FunctionParam::new(
+                param_name,
+                default,
+                self.gen_uid(),
+                param_span,
+            ));
 
             if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
                 break;
@@ -939,11 +1763,17 @@ impl<'a> Parser<'a> {
     fn parse_list(&mut self) -> Result<ast::Expr> {
         let span_start = self.tokens.next().unwrap().span.start();
         let mut elements = vec![];
+        let mut comprehension = None;
 
         if !self.tokens.is_next_token(TokenKind::RightBracket) {
             loop {
                 elements.push(self.parse_expression()?);
 
+                if elements.len() == 1 && self.is_next_comprehension() {
+                    comprehension = Some(self.parse_comprehension_clause()?);
+                    break;
+                }
+
                 if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
                     break;
                 }
@@ -968,6 +1798,13 @@ impl<'a> Parser<'a> {
         let span_end = closing_bracket.span.end();
         let span = SourceSpan::new(span_start, span_end, self.source_id);
 
+        if let Some((item, iterable, condition)) = comprehension {
+            let element = ast::ComprehensionElement::List(Box::new(elements.remove(0)));
+            let comprehension = ast::Comprehension::new(element, item, iterable, condition, span);
+
+            return Ok(ast::Expr::Comprehension(comprehension));
+        }
+
         let elements = elements.into_iter().collect();
         let list_expr = ast::List::new(elements, span);
         let list_expr = ast::Expr::List(list_expr);
@@ -978,14 +1815,23 @@ impl<'a> Parser<'a> {
     fn parse_associative_array(&mut self) -> Result<ast::Expr> {
         let span_start = self.tokens.next().unwrap().span.start();
         let mut elements = vec![];
+        let mut comprehension = None;
 
         if !self.tokens.is_next_token(TokenKind::RightBrace) {
             loop {
-                let key = match self.tokens.consume_one_of(token_slice![Number, String]) {
-                    Some(token) => {
-                        ast::Literal::new(token.literal.clone().unwrap(), token.span.clone())
+                let first_token = self.tokens.peek().map(|token| (*token).clone());
+
+                let key = if elements.is_empty() && !self.is_next_literal_key() {
+                    // Only the key of a comprehension may be computed
+                    self.parse_expression()?
+                } else {
+                    match self.tokens.consume_one_of(token_slice![Number, String]) {
+                        Some(token) => ast::Expr::Literal(ast::Literal::new(
+                            token.literal.clone().unwrap(),
+                            token.span.clone(),
+                        )),
+                        None => return Err(vec![unexpected_token!(self.tokens.next().unwrap())]),
                     }
-                    None => return Err(vec![unexpected_token!(self.tokens.next().unwrap())]),
                 };
 
                 if self.tokens.consume_one_of(token_slice![Colon]).is_none() {
@@ -996,7 +1842,15 @@ impl<'a> Parser<'a> {
 
                 let value = self.parse_expression()?;
 
-                elements.push((key, value));
+                if elements.is_empty() && self.is_next_comprehension() {
+                    comprehension = Some((key, value, self.parse_comprehension_clause()?));
+                    break;
+                }
+
+                match key {
+                    ast::Expr::Literal(key) => elements.push((key, value)),
+                    _ => return Err(vec![unexpected_token!(first_token.unwrap())]),
+                }
 
                 if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
                     break;
@@ -1022,6 +1876,14 @@ impl<'a> Parser<'a> {
         let span_end = closing_brace.span.end();
         let span = SourceSpan::new(span_start, span_end, self.source_id);
 
+        if let Some((key, value, (item, iterable, condition))) = comprehension {
+            let element =
+                ast::ComprehensionElement::AssociativeArray(Box::new(key), Box::new(value));
+            let comprehension = ast::Comprehension::new(element, item, iterable, condition, span);
+
+            return Ok(ast::Expr::Comprehension(comprehension));
+        }
+
         let elements = elements.into_iter().collect();
         let associative_array_expr = ast::AssociativeArray::new(elements, span);
         let associative_array_expr = ast::Expr::AssociativeArray(associative_array_expr);
@@ -1029,7 +1891,378 @@ impl<'a> Parser<'a> {
         Ok(associative_array_expr)
     }
 
-    fn parse_dot_access(&mut self, lhs: ast::Expr) -> Result<ast::Expr> {
+    fn is_next_literal_key(&mut self) -> bool {
+        self.tokens.check_sequence(token_slice![Number, Colon])
+            || self.tokens.check_sequence(token_slice![String, Colon])
+    }
+
+    fn is_next_comprehension(&mut self) -> bool {
+        self.tokens.check_sequence(token_slice![ForOrBreak, Each])
+    }
+
+    /// Parses the `para cada item em iterável` clause of a comprehension,
+    /// followed by an optional `se condição` filter.
+    fn parse_comprehension_clause(
+        &mut self,
+    ) -> Result<(ast::ForEachItem, ast::Expr, Option<ast::Expr>)> {
+        self.skip_token(TokenKind::ForOrBreak)?;
+        self.skip_token(TokenKind::Each)?;
+
+        let item = self.parse_for_each_item()?;
+
+        self.skip_token(TokenKind::In)?;
+
+        let iterable = self.parse_expression()?;
+
+        let condition = match self.tokens.consume_one_of(token_slice![If]) {
+            Some(_) => Some(self.parse_expression()?),
+            None => None,
+        };
+
+        Ok((item, iterable, condition))
+    }
+
+    fn parse_match(&mut self) -> Result<ast::Expr> {
+        let span_start = self.tokens.next().unwrap().span.start();
+        let value = self.parse_expression()?;
+
+        let mut arms = vec![];
+        let mut or_else = None;
+        let mut delimiter = self.skip_token(TokenKind::Case)?.kind;
+        let mut span_end = value.get_span().end();
+
+        loop {
+            match delimiter {
+                TokenKind::Case => {
+                    let pattern = self.parse_pattern()?;
+
+                    let guard = match self.tokens.consume_one_of(token_slice![If]) {
+                        Some(_) => Some(self.parse_pipeline()?),
+                        None => None,
+                    };
+
+                    self.skip_token(TokenKind::Then)?;
+
+                    let (body, block_end_delimiter) = self.parse_block_contents(
+                        token_slice![Case, Else, BlockEnd],
+                        BlockScope::Match,
+                        None,
+                    )?;
+
+                    span_end = body.get_span().end();
+
+                    let span =
+                        SourceSpan::new(pattern.get_span().start(), span_end, self.source_id);
+
+                    arms.push(ast::MatchArm::new(pattern, guard, body, span));
+                    delimiter = block_end_delimiter;
+                }
+                TokenKind::Else => {
+                    let (body, _) =
+                        self.parse_block_contents(token_slice![BlockEnd], BlockScope::Match, None)?;
+
+                    span_end = body.get_span().end();
+                    or_else = Some(body);
+
+                    break;
+                }
+                TokenKind::BlockEnd => break,
+                _ => unreachable!(),
+            }
+        }
+
+        let span = SourceSpan::new(span_start, span_end, self.source_id);
+        let match_expr = ast::Match::new(value, arms, or_else, span);
+
+        Ok(ast::Expr::Match(match_expr))
+    }
+
+    fn parse_pattern(&mut self) -> Result<ast::Pattern> {
+        let pattern = self.parse_pattern_element()?;
+
+        let mut seen: Vec<&str> = vec![];
+
+        for binding in pattern.get_bindings() {
+            if seen.contains(&binding.name.as_str()) {
+                return Err(vec![ParserError::DuplicateBinding {
+                    name: binding.name.clone(),
+                    span: binding.span.clone(),
+                }]);
+            }
+
+            seen.push(&binding.name);
+        }
+
+        Ok(pattern)
+    }
+
+    fn parse_destructuring_pattern(&mut self) -> Result<ast::Pattern> {
+        let pattern = self.parse_pattern()?;
+
+        validate_destructuring_pattern(&pattern)?;
+
+        Ok(pattern)
+    }
+
+    fn parse_pattern_element(&mut self) -> Result<ast::Pattern> {
+        use TokenKind::*;
+
+        let token = match self.tokens.peek() {
+            Some(token) => token.clone_ref(),
+            None => unreachable!(),
+        };
+
+        match token.kind {
+            Number | Minus => {
+                let (start, start_span) = self.parse_number_pattern()?;
+
+                if self.tokens.consume_one_of(token_slice![Until]).is_none() {
+                    return Ok(ast::Pattern::Literal(ast::Literal::new(start, start_span)));
+                }
+
+                let (end, end_span) = self.parse_number_pattern()?;
+                let span = SourceSpan::new(start_span.start(), end_span.end(), self.source_id);
+
+                Ok(ast::Pattern::Range(ast::RangePattern::new(
+                    number_literal_to_f64(&start),
+                    number_literal_to_f64(&end),
+                    span,
+                )))
+            }
+            String | True | False | Nil => {
+                self.tokens.next();
+
+                let literal = ast::Literal::new(token.literal.clone().unwrap(), token.span.clone());
+
+                Ok(ast::Pattern::Literal(literal))
+            }
+            Identifier
+                if self
+                    .tokens
+                    .check_sequence(token_slice![Identifier, LeftParen]) =>
+            {
+                self.parse_record_pattern()
+            }
+            Identifier if self.tokens.check_sequence(token_slice![Identifier, Dot]) => {
+                self.parse_enum_variant_pattern()
+            }
+            Identifier => {
+                let (name, span) = self.consume_identifier()?;
+
+                if name == "_" {
+                    return Ok(ast::Pattern::Wildcard(span));
+                }
+
+                let binding = ast::PatternBinding::new(name, self.gen_uid(), span);
+
+                Ok(ast::Pattern::Binding(binding))
+            }
+            LeftBracket => self.parse_list_pattern(),
+            LeftBrace => self.parse_associative_array_pattern(),
+            _ => Err(vec![unexpected_token!(self.tokens.next().unwrap())]),
+        }
+    }
+
+    fn parse_number_pattern(&mut self) -> Result<(tenda_scanner::Literal, SourceSpan)> {
+        use tenda_scanner::Literal;
+
+        let minus = self.tokens.consume_one_of(token_slice![Minus]);
+        let token = self.skip_token(TokenKind::Number)?;
+
+        let value = token.literal.clone().unwrap();
+        let span_end = token.span.end();
+
+        match minus {
+            Some(minus) => {
+                let span = SourceSpan::new(minus.span.start(), span_end, self.source_id);
+
+                let value = match value {
+                    Literal::Number(value) => Literal::Number(-value),
+                    Literal::Integer(value) => Literal::Integer(-value),
+                    Literal::Decimal(value) => Literal::Decimal(-value),
+                    _ => unreachable!(),
+                };
+
+                Ok((value, span))
+            }
+            None => Ok((value, token.span.clone())),
+        }
+    }
+
+    fn parse_list_pattern(&mut self) -> Result<ast::Pattern> {
+        let span_start = self.tokens.next().unwrap().span.start();
+        let mut elements = vec![];
+        let mut rest = None;
+
+        if !self.tokens.is_next_token(TokenKind::RightBracket) {
+            loop {
+                if self.tokens.consume_one_of(token_slice![Ellipsis]).is_some() {
+                    let (name, span) = self.consume_identifier()?;
+
+                    rest = Some(match name.as_str() {
+                        "_" => ast::Pattern::Wildcard(span),
+                        _ => ast::Pattern::Binding(ast::PatternBinding::new(
+                            name,
+                            self.gen_uid(),
+                            span,
+                        )),
+                    });
+
+                    break;
+                }
+
+                elements.push(self.parse_pattern_element()?);
+
+                if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
+                    break;
+                }
+            }
+        }
+
+        let closing_bracket = match self.tokens.consume_one_of(token_slice![RightBracket]) {
+            Some(token) => token,
+            _ => {
+                return Err(vec![ParserError::MissingBrackets {
+                    span: self.tokens.next().unwrap().span.clone(),
+                }]);
+            }
+        };
+
+        let span = SourceSpan::new(span_start, closing_bracket.span.end(), self.source_id);
+
+        Ok(ast::Pattern::List(ast::ListPattern::new(
+            elements, rest, span,
+        )))
+    }
+
+    fn parse_associative_array_pattern(&mut self) -> Result<ast::Pattern> {
+        let span_start = self.tokens.next().unwrap().span.start();
+        let mut elements = vec![];
+
+        if !self.tokens.is_next_token(TokenKind::RightBrace) {
+            loop {
+                let key = match self
+                    .tokens
+                    .consume_one_of(token_slice![Number, String, Identifier])
+                {
+                    Some(token) => token,
+                    None => return Err(vec![unexpected_token!(self.tokens.next().unwrap())]),
+                };
+
+                let key_literal = ast::Literal::new(key.literal.clone().unwrap(), key.span.clone());
+
+                let is_shorthand = key.kind == TokenKind::Identifier
+                    && !self.tokens.is_next_token(TokenKind::Colon);
+
+                if is_shorthand {
+                    let pattern = match key.lexeme.as_str() {
+                        "_" => ast::Pattern::Wildcard(key.span.clone()),
+                        name => ast::Pattern::Binding(ast::PatternBinding::new(
+                            name.to_string(),
+                            self.gen_uid(),
+                            key.span.clone(),
+                        )),
+                    };
+
+                    elements.push((key_literal, pattern));
+                } else {
+                    if self.tokens.consume_one_of(token_slice![Colon]).is_none() {
+                        return Err(vec![ParserError::MissingColon {
+                            span: self.tokens.next().unwrap().span.clone(),
+                        }]);
+                    }
+
+                    elements.push((key_literal, self.parse_pattern_element()?));
+                }
+
+                if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
+                    break;
+                }
+            }
+        }
+
+        let closing_brace = match self.tokens.consume_one_of(token_slice![RightBrace]) {
+            Some(token) => token,
+            _ => {
+                return Err(vec![ParserError::MissingBraces {
+                    span: self.tokens.next().unwrap().span.clone(),
+                }]);
+            }
+        };
+
+        let span = SourceSpan::new(span_start, closing_brace.span.end(), self.source_id);
+        let pattern = ast::AssociativeArrayPattern::new(elements, span);
+
+        Ok(ast::Pattern::AssociativeArray(pattern))
+    }
+
+    fn parse_record_pattern(&mut self) -> Result<ast::Pattern> {
+        let (name, name_span) = self.consume_identifier()?;
+        let mut fields = vec![];
+
+        self.skip_token(TokenKind::LeftParen)?;
+
+        if !self.tokens.is_next_token(TokenKind::RightParen) {
+            loop {
+                let (field, field_span) = self.consume_identifier()?;
+
+                let pattern = match self.tokens.consume_one_of(token_slice![Colon]) {
+                    Some(_) => self.parse_pattern_element()?,
+                    None => {
+                        let binding =
+                            ast::PatternBinding::new(field.clone(), self.gen_uid(), field_span);
+
+                        ast::Pattern::Binding(binding)
+                    }
+                };
+
+                fields.push((field, pattern));
+
+                if self.tokens.consume_one_of(token_slice![Comma]).is_none() {
+                    break;
+                }
+            }
+        }
+
+        let closing_paren = match self.tokens.consume_one_of(token_slice![RightParen]) {
+            Some(token) => token,
+            _ => {
+                return Err(vec![ParserError::MissingParentheses {
+                    span: self.tokens.next().unwrap().span.clone(),
+                }]);
+            }
+        };
+
+        let span = SourceSpan::new(name_span.start(), closing_paren.span.end(), self.source_id);
+
+        Ok(ast::Pattern::Record(ast::RecordPattern::new(
+            name, fields, span,
+        )))
+    }
+
+    fn parse_enum_variant_pattern(&mut self) -> Result<ast::Pattern> {
+        let (first, first_span) = self.consume_identifier()?;
+        let mut path = vec![first];
+        let mut span_end = first_span.end();
+
+        while self.tokens.consume_one_of(token_slice![Dot]).is_some() {
+            let (segment, segment_span) = self.consume_identifier()?;
+
+            path.push(segment);
+            span_end = segment_span.end();
+        }
+
+        let span = SourceSpan::new(first_span.start(), span_end, self.source_id);
+
+        let variant = path.pop().unwrap();
+        let enum_name = path.join(".");
+
+        Ok(ast::Pattern::EnumVariant(ast::EnumVariantPattern::new(
+            enum_name, variant, span,
+        )))
+    }
+
+    fn parse_dot_access(&mut self, lhs: ast::Expr, optional: bool) -> Result<ast::Expr> {
         let (field, field_span) = self.consume_identifier()?;
         let literal = tenda_scanner::Literal::String(field);
 
@@ -1040,7 +2273,7 @@ impl<'a> Parser<'a> {
         let span_end = index_expr.get_span().end();
         let span = SourceSpan::new(span_start, span_end, self.source_id);
 
-        let access_expr = ast::Access::new(lhs, index_expr, span);
+        let access_expr = ast::Access::new(lhs, index_expr, optional, span);
         let access_expr = ast::Expr::Access(access_expr);
 
         Ok(access_expr)
@@ -1101,3 +2334,64 @@ impl Parser<'_> {
         self.uid_counter
     }
 }
+
+fn validate_destructuring_pattern(pattern: &ast::Pattern) -> Result<()> {
+    use ast::Pattern;
+
+    match pattern {
+        Pattern::Wildcard(_) | Pattern::Binding(_) => Ok(()),
+        Pattern::List(list) => list
+            .elements
+            .iter()
+            .try_for_each(validate_destructuring_pattern),
+        Pattern::AssociativeArray(associative_array) => associative_array
+            .elements
+            .iter()
+            .try_for_each(|(_, element)| validate_destructuring_pattern(element)),
+        Pattern::Literal(_) | Pattern::Range(_) | Pattern::Record(_) | Pattern::EnumVariant(_) => {
+            Err(vec![ParserError::InvalidDestructuringPattern {
+                span: pattern.get_span().clone(),
+                help: Some(
+                    "use apenas nomes, listas `[a, b]` e dicionários `{ chave }` ao desestruturar"
+                        .to_string(),
+                ),
+            }])
+        }
+    }
+}
+
+/// Passes `value` as the first argument of a pipeline stage. A call receives
+/// it before its own arguments; any other expression is called with it alone.
+/// The call keeps the span of the stage, so errors point at the failing step.
+fn pipe_into(value: ast::Expr, stage: ast::Expr) -> ast::Expr {
+    match stage {
+        ast::Expr::Call(mut call) => {
+            call.args.insert(0, value);
+            ast::Expr::Call(call)
+        }
+        stage => {
+            let span = stage.get_span().clone();
+            ast::Expr::Call(ast::Call::new(stage, vec![value], vec![], span))
+        }
+    }
+}
+
+fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if first.is_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn number_literal_to_f64(literal: &tenda_scanner::Literal) -> f64 {
+    match literal {
+        tenda_scanner::Literal::Number(value) => *value,
+        tenda_scanner::Literal::Integer(value) => value.to_f64().unwrap_or(f64::NAN),
+        tenda_scanner::Literal::Decimal(value) => value.to_f64().unwrap_or(f64::NAN),
+        _ => unreachable!(),
+    }
+}
@@ -60,6 +60,21 @@ pub enum ParserError {
         span: SourceSpan,
     },
 
+    #[error("'produza' fora de uma função")]
+    IllegalYield {
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("'produza' não pode ser usado dentro de blocos 'tente', 'capture', 'finalmente' ou 'escolha'")]
+    UnsupportedYieldPosition {
+        #[span]
+        span: SourceSpan,
+
+        #[help]
+        help: Option<String>,
+    },
+
     #[error("'pare' fora de uma estrutura de repetição")]
     IllegalBreak {
         #[span]
@@ -72,6 +87,47 @@ pub enum ParserError {
         span: SourceSpan,
     },
 
+    #[error("rótulo '{}' não corresponde a nenhuma estrutura de repetição envolvente", .label)]
+    UndefinedLoopLabel {
+        label: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("rótulo '{}' já é usado por uma estrutura de repetição envolvente", .label)]
+    DuplicateLoopLabel {
+        label: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("rótulo '{}' deve ser seguido de 'enquanto' ou 'para cada'", .label)]
+    LabelWithoutLoop {
+        label: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("bloco 'tente' precisa de um 'capture' ou de um 'finalmente'")]
+    MissingCatchOrFinally {
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("não é possível derivar um nome válido para o módulo '{}'", .path)]
+    InvalidModuleName {
+        path: String,
+
+        #[span]
+        span: SourceSpan,
+
+        #[help]
+        help: Option<String>,
+    },
+
     #[error("parâmetro '{}' duplicado na função", .name)]
     DuplicateParameter {
         name: String,
@@ -80,6 +136,100 @@ pub enum ParserError {
         span: SourceSpan,
     },
 
+    #[error("parâmetro obrigatório '{}' declarado após parâmetro com valor padrão", .name)]
+    RequiredParameterAfterDefault {
+        name: String,
+
+        #[span]
+        span: SourceSpan,
+
+        #[help]
+        help: Option<String>,
+    },
+
+    #[error("o parâmetro variádico '{}' deve ser o último da função", .name)]
+    VariadicParameterNotLast {
+        name: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("não é possível atribuir um novo valor à constante '{}'", .name)]
+    ConstantReassignment {
+        name: String,
+
+        #[span]
+        span: SourceSpan,
+
+        #[help]
+        help: Option<String>,
+    },
+
+    #[error("argumento '{}' nomeado mais de uma vez", .name)]
+    DuplicateNamedArgument {
+        name: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("argumento posicional após argumento nomeado")]
+    PositionalArgumentAfterNamed {
+        #[span]
+        span: SourceSpan,
+
+        #[help]
+        help: Option<String>,
+    },
+
+    #[error("campo '{}' duplicado na estrutura", .name)]
+    DuplicateField {
+        name: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("variante '{}' duplicada na enumeração", .name)]
+    DuplicateEnumVariant {
+        name: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("membro '{}' duplicado na classe", .name)]
+    DuplicateClassMember {
+        name: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("variável '{}' vinculada mais de uma vez no mesmo padrão", .name)]
+    DuplicateBinding {
+        name: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
+    #[error("padrão inválido para desestruturação")]
+    InvalidDestructuringPattern {
+        #[span]
+        span: SourceSpan,
+
+        #[help]
+        help: Option<String>,
+    },
+
+    #[error("'este' fora de um método de classe")]
+    IllegalThis {
+        #[span]
+        span: SourceSpan,
+    },
+
     #[error("o operador '{}' não pode ser encadeado", .op.lexeme)]
     InvalidChaining {
         op: Token,
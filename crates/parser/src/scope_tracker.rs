@@ -8,7 +8,14 @@ pub enum BlockScope {
     If,
     Else,
     Loop,
+    /// The label of the loop opened right after it.
+    Label(String),
     Function,
+    Try,
+    Catch,
+    Finally,
+    Class,
+    Match,
     Global,
 }
 
@@ -20,7 +27,7 @@ impl ScopeTracker {
         ScopeTracker(Rc::new(RefCell::new(vec![BlockScope::Global])))
     }
 
-    pub fn get(&self) -> RefMut<Vec<BlockScope>> {
+    pub fn get(&self) -> RefMut<'_, Vec<BlockScope>> {
         self.0.as_ref().borrow_mut()
     }
 
@@ -31,6 +38,16 @@ impl ScopeTracker {
     pub fn has_scope(&self, scope: BlockScope) -> bool {
         self.get().contains(&scope)
     }
+
+    /// Returns whether `scope` encloses the current position inside the
+    /// innermost function, without crossing into an outer one.
+    pub fn has_scope_in_function(&self, scope: BlockScope) -> bool {
+        self.get()
+            .iter()
+            .rev()
+            .take_while(|enclosing| **enclosing != BlockScope::Function)
+            .any(|enclosing| *enclosing == scope)
+    }
 }
 
 impl Default for ScopeTracker {
@@ -138,6 +138,12 @@ impl TokenIterator<'_> {
         false
     }
 
+    pub fn is_next_raw_newline(&mut self) -> bool {
+        self.tokens.reset_cursor();
+
+        matches!(self.tokens.peek(), Some(token) if token.kind == TokenKind::Newline)
+    }
+
     pub fn is_next_eof(&mut self) -> bool {
         self.tokens.reset_cursor();
 
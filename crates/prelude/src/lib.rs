@@ -1,10 +1,14 @@
+use num_bigint::BigInt;
+use num_integer::Integer as _;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+use rust_decimal::{Decimal, RoundingStrategy};
 use tenda_runtime::*;
 
 macro_rules! global {
     ($env:ident, $builtin:expr) => {{
         let builtin = $builtin;
 
-        $env.set(builtin.0, ValueCell::Owned(builtin.1));
+        $env.set_constant(builtin.0, ValueCell::Owned(builtin.1));
     }};
     ($stack:ident, $($builtin:expr),+) => {{
         $(global!($stack, $builtin);)+
@@ -34,10 +38,22 @@ macro_rules! builtin_assoc_array {
     }};
 }
 
-macro_rules! assoc_array_enum {
-    ($($name:literal),+ $(,)?) => {{
-        builtin_assoc_array!($($name => Value::String($name.to_string())),+)
-    }};
+macro_rules! builtin_enum {
+    ($name:literal, [$($variant:literal),+ $(,)?]) => {
+        std::rc::Rc::new(Enum::new(
+            $name.to_string(),
+            vec![$($variant.to_string()),+],
+        ))
+    };
+}
+
+macro_rules! enum_value {
+    ($enumeration:ident) => {
+        $enumeration.with(|enumeration| Value::Enum(enumeration.clone()))
+    };
+    ($enumeration:ident, $variant:literal) => {
+        $enumeration.with(|enumeration| Value::EnumVariant(enumeration.variant($variant).unwrap()))
+    };
 }
 
 macro_rules! def_assoc_array {
@@ -50,6 +66,12 @@ macro_rules! def_assoc_array {
 }
 
 macro_rules! builtin_fn {
+    ([$($param:literal,)* ...$rest:literal], $body:expr) => {
+        Value::Function(Function::new_builtin(
+            params![$($param,)* ...$rest],
+            $body,
+        ))
+    };
     ([$($param:expr),*], $body:expr) => {
         Value::Function(Function::new_builtin(
             params![$($param),*],
@@ -65,6 +87,9 @@ macro_rules! builtin_fn {
 }
 
 macro_rules! def_fn {
+    ($name:literal, [$($param:literal,)* ...$rest:literal], $body:expr) => {
+        ($name.to_string(), builtin_fn!([$($param,)* ...$rest], $body))
+    };
     ($name:literal, [$($param:expr),*], $body:expr) => {
         ($name.to_string(), builtin_fn!([$($param),*], $body))
     };
@@ -77,10 +102,10 @@ macro_rules! args {
 }
 
 macro_rules! error_object {
-    ($kind:literal) => {
+    ($enumeration:ident, $kind:literal) => {
         builtin_assoc_array! {
             "erro" => builtin_assoc_array! {
-                "tipo" => Value::String($kind.to_string()),
+                "tipo" => enum_value!($enumeration, $kind),
             }
         }
     };
@@ -95,6 +120,25 @@ macro_rules! success_object {
 }
 
 macro_rules! ensure {
+    ($val:expr, Number($binding:pat) => $body:expr) => {{
+        match $val {
+            Value::Number($binding) => $body,
+            value @ (Value::Integer(_) | Value::Decimal(_)) => {
+                match &value.to_f64().unwrap_or(f64::NAN) {
+                    $binding => $body,
+                }
+            }
+            value => {
+                return Err(Box::new(RuntimeError::UnexpectedTypeError {
+                    expected: ValueType::Number,
+                    found: value.kind(),
+                    span: None,
+                    message: None,
+                    stacktrace: vec![],
+                }))
+            }
+        }
+    }};
     ($val:expr, $variant:ident($binding:pat) => $body:expr) => {{
         match $val {
             Value::$variant($binding) => $body,
@@ -111,10 +155,59 @@ macro_rules! ensure {
     }};
 }
 
+// Builtin functions are plain function pointers, so the enumerations they
+// return variants of live in thread-locals instead of being captured.
+thread_local! {
+    static ROUNDING_STRATEGIES: std::rc::Rc<Enum> = builtin_enum!("Decimal.arredondamentos", [
+        "MEIO_PARA_CIMA",
+        "MEIO_PARA_BAIXO",
+        "MEIO_PAR",
+        "PARA_ZERO",
+        "LONGE_DO_ZERO",
+        "PISO",
+        "TETO",
+    ]);
+    static STRING_ERRORS: std::rc::Rc<Enum> = builtin_enum!("Texto.erros", [
+        "CONVERSÃO_INVÁLIDA",
+    ]);
+    static FILE_ERRORS: std::rc::Rc<Enum> = builtin_enum!("Arquivo.erros", [
+        "NÃO_ENCONTRADO",
+        "PERMISSÃO_NEGADA",
+        "JÁ_EXISTE",
+        "OUTRO",
+    ]);
+    static DATE_ERRORS: std::rc::Rc<Enum> = builtin_enum!("Data.erros", [
+        "ISO_INVÁLIDA",
+        "TIMESTAMP_INVÁLIDO",
+        "FUSO_HORÁRIO_INVÁLIDO",
+    ]);
+}
+
+/// Converts the result of a rounding function to an integer, keeping
+/// infinities and `NaN` as floats.
+fn integral_value(number: f64) -> Value {
+    match BigInt::from_f64(number) {
+        Some(integer) if number.is_finite() => Value::Integer(integer),
+        _ => Value::Number(number),
+    }
+}
+
+fn ensure_nonzero_divisor(divisor: &BigInt) -> Result<()> {
+    match divisor.is_zero() {
+        true => Err(Box::new(RuntimeError::DivisionByZero {
+            span: None,
+            stacktrace: vec![],
+        })),
+        false => Ok(()),
+    }
+}
+
 pub fn setup_runtime_prelude(env: &mut Environment) {
     setup_io_prelude(env);
     setup_list_prelude(env);
+    setup_set_prelude(env);
     setup_math_prelude(env);
+    setup_decimal_prelude(env);
     setup_string_prelude(env);
     setup_file_prelude(env);
     setup_program_prelude(env);
@@ -124,11 +217,9 @@ pub fn setup_runtime_prelude(env: &mut Environment) {
 fn setup_io_prelude(env: &mut Environment) {
     global!(
         env,
-        def_fn!("exiba", ["texto"], |args, runtime, _| {
-            let text = match args!(args, 0) {
-                Value::String(value) => value.to_string(),
-                value => format!("{}", value),
-            };
+        def_fn!("exiba", [..."valores"], |args, runtime, _| {
+            let values = ensure!(args!(args, 0), List(values) => values.borrow());
+            let text = display_values(&values);
 
             runtime.get_platform().println(&text);
 
@@ -152,11 +243,9 @@ fn setup_io_prelude(env: &mut Environment) {
             Ok(Value::String(input))
         }),
         def_assoc_array!("Saída", {
-            "exiba" => builtin_fn!(["texto"], |args, runtime, _| {
-                let text = match args!(args, 0) {
-                    Value::String(value) => value.to_string(),
-                    value => format!("{}", value),
-                };
+            "exiba" => builtin_fn!([..."valores"], |args, runtime, _| {
+                let values = ensure!(args!(args, 0), List(values) => values.borrow());
+                let text = display_values(&values);
 
                 runtime.get_platform().println(&text);
 
@@ -200,7 +289,7 @@ fn setup_list_prelude(env: &mut Environment) {
             "tamanho" => builtin_fn!(["lista"], |args, _, _| {
                 let list = ensure!(args!(args, 0), List(list) => list.borrow());
 
-                Ok(Value::Number(list.len() as f64))
+                Ok(Value::Integer(BigInt::from(list.len())))
             }),
             "insira" => builtin_fn!(["lista", "valor"], |args, _, _| {
                 let mut list = ensure!(args!(args, 0), List(list) => list.borrow_mut());
@@ -212,7 +301,7 @@ fn setup_list_prelude(env: &mut Environment) {
             "remova" => builtin_fn!(["lista", "valor"], |args, _, _| {
                 let mut list = ensure!(args!(args, 0), List(list) => list.borrow_mut());
                 let value = args!(args, 1);
-                let index = list.iter().position(|v| v == value);
+                let index = list.iter().position(|v| values_equal(v, value));
 
                 if let Some(index) = index {
                     return Ok(list.remove(index));
@@ -224,7 +313,7 @@ fn setup_list_prelude(env: &mut Environment) {
                 let mut list = ensure!(args!(args, 0), List(list) => list.borrow_mut());
                 let value = args!(args, 1);
 
-                list.retain(|v| v != value);
+                list.retain(|v| !values_equal(v, value));
 
                 Ok(Value::Nil)
             }),
@@ -265,15 +354,17 @@ fn setup_list_prelude(env: &mut Environment) {
             "índice_de" => builtin_fn!(["lista", "valor"], |args, _, _| {
                 let list = ensure!(args!(args, 0), List(list) => list.borrow());
                 let value = args!(args, 1);
-                let index = list.iter().position(|v| v == value).map(|i| i as f64);
+                let index = list.iter().position(|v| values_equal(v, value)).map(BigInt::from);
 
-                Ok(index.map(Value::Number).unwrap_or(Value::Nil))
+                Ok(index.map(Value::Integer).unwrap_or(Value::Nil))
             }),
             "contém" => builtin_fn!(["lista", "valor"], |args, _, _| {
                 let list = ensure!(args!(args, 0), List(list) => list.borrow());
                 let value = args!(args, 1);
 
-                Ok(Value::Boolean(list.contains(value)))
+                Ok(Value::Boolean(
+                    list.iter().any(|item| values_equal(item, value)),
+                ))
             }),
             "vazio" => builtin_fn!(["lista"], |args, _, _| {
                 let list = ensure!(args!(args, 0), List(list) => list.borrow());
@@ -319,7 +410,7 @@ fn setup_list_prelude(env: &mut Environment) {
                 let function = ensure!(args!(args, 1), Function(function) => function);
 
                 for (i, value) in list.iter().enumerate() {
-                    let i = Value::Number(i as f64);
+                    let i = Value::Integer(BigInt::from(i));
                     let args = vec![value.clone(), i];
 
                     runtime.call_function(function.clone(), args, None)?;
@@ -328,8 +419,8 @@ fn setup_list_prelude(env: &mut Environment) {
                 Ok(Value::Nil)
             }),
             "de_intervalo" => builtin_fn!(["intervalo"], |args, _, _| {
-                let (from, to) = match args!(args, 0) {
-                    Value::Range(from, start) => (from, start),
+                let range = match args!(args, 0) {
+                    Value::Range(range) => range,
                     value => return Err(Box::new(RuntimeError::UnexpectedTypeError {
                         expected: ValueType::Range,
                         found: value.kind(),
@@ -339,8 +430,9 @@ fn setup_list_prelude(env: &mut Environment) {
                     }))
                 };
 
-                let list = (*from as i64..=*to as i64)
-                    .map(|i| Value::Number(i as f64))
+                let list = range
+                    .iter()
+                    .map(|i| Value::Integer(BigInt::from(i)))
                     .collect::<Vec<_>>();
 
                 Ok(Value::List(Rc::new(RefCell::new(list))))
@@ -374,6 +466,89 @@ fn setup_list_prelude(env: &mut Environment) {
     );
 }
 
+fn setup_set_prelude(env: &mut Environment) {
+    global!(
+        env,
+        def_assoc_array!("Conjunto", {
+            "novo" => builtin_fn!([..."valores"], |args, _, _| {
+                let values = ensure!(args!(args, 0), List(values) => values.borrow());
+                let set = set_from_values(values.iter().cloned())?;
+
+                Ok(Value::Set(Rc::new(RefCell::new(set))))
+            }),
+            "de_lista" => builtin_fn!(["lista"], |args, _, _| {
+                let list = ensure!(args!(args, 0), List(list) => list.borrow());
+                let set = set_from_values(list.iter().cloned())?;
+
+                Ok(Value::Set(Rc::new(RefCell::new(set))))
+            }),
+            "para_lista" => builtin_fn!(["conjunto"], |args, _, _| {
+                let set = ensure!(args!(args, 0), Set(set) => set.borrow());
+
+                Ok(Value::List(Rc::new(RefCell::new(set_values(&set)))))
+            }),
+            "tamanho" => builtin_fn!(["conjunto"], |args, _, _| {
+                let set = ensure!(args!(args, 0), Set(set) => set.borrow());
+
+                Ok(Value::Integer(BigInt::from(set.len())))
+            }),
+            "vazio" => builtin_fn!(["conjunto"], |args, _, _| {
+                let set = ensure!(args!(args, 0), Set(set) => set.borrow());
+
+                Ok(Value::Boolean(set.is_empty()))
+            }),
+            "contém" => builtin_fn!(["conjunto", "valor"], |args, _, _| {
+                let set = ensure!(args!(args, 0), Set(set) => set.borrow());
+                let contains = AssociativeArrayKey::try_from(args!(args, 1).clone())
+                    .is_ok_and(|element| set.contains(&element));
+
+                Ok(Value::Boolean(contains))
+            }),
+            "insira" => builtin_fn!(["conjunto", "valor"], |args, _, _| {
+                let mut set = ensure!(args!(args, 0), Set(set) => set.borrow_mut());
+                let element = set_element(args!(args, 1).clone())?;
+
+                Ok(Value::Boolean(set.insert(element)))
+            }),
+            "remova" => builtin_fn!(["conjunto", "valor"], |args, _, _| {
+                let mut set = ensure!(args!(args, 0), Set(set) => set.borrow_mut());
+                let removed = AssociativeArrayKey::try_from(args!(args, 1).clone())
+                    .is_ok_and(|element| set.shift_remove(&element));
+
+                Ok(Value::Boolean(removed))
+            }),
+            "limpa" => builtin_fn!(["conjunto"], |args, _, _| {
+                let mut set = ensure!(args!(args, 0), Set(set) => set.borrow_mut());
+
+                set.clear();
+
+                Ok(Value::Nil)
+            }),
+            "união" => builtin_fn!(["conjunto_1", "conjunto_2"], |args, _, _| {
+                let lhs = ensure!(args!(args, 0), Set(set) => set.borrow());
+                let rhs = ensure!(args!(args, 1), Set(set) => set.borrow());
+                let set = lhs.union(&rhs).cloned().collect();
+
+                Ok(Value::Set(Rc::new(RefCell::new(set))))
+            }),
+            "interseção" => builtin_fn!(["conjunto_1", "conjunto_2"], |args, _, _| {
+                let lhs = ensure!(args!(args, 0), Set(set) => set.borrow());
+                let rhs = ensure!(args!(args, 1), Set(set) => set.borrow());
+                let set = lhs.intersection(&rhs).cloned().collect();
+
+                Ok(Value::Set(Rc::new(RefCell::new(set))))
+            }),
+            "diferença" => builtin_fn!(["conjunto_1", "conjunto_2"], |args, _, _| {
+                let lhs = ensure!(args!(args, 0), Set(set) => set.borrow());
+                let rhs = ensure!(args!(args, 1), Set(set) => set.borrow());
+                let set = lhs.difference(&rhs).cloned().collect();
+
+                Ok(Value::Set(Rc::new(RefCell::new(set))))
+            }),
+        })
+    );
+}
+
 fn setup_math_prelude(env: &mut Environment) {
     global!(
         env,
@@ -385,24 +560,34 @@ fn setup_math_prelude(env: &mut Environment) {
             "pi" => Value::Number(std::f64::consts::PI),
             "e" => Value::Number(std::f64::consts::E),
             "absoluto" => builtin_fn!(["número"], |args, _, _| {
-                let number = ensure!(args!(args, 0), Number(value) => *value);
-
-                Ok(Value::Number(number.abs()))
+                match args!(args, 0) {
+                    Value::Integer(number) => Ok(Value::Integer(number.abs())),
+                    Value::Decimal(number) => Ok(Value::Decimal(number.abs())),
+                    number => Ok(Value::Number(ensure!(number, Number(value) => value.abs()))),
+                }
             }),
             "arredonda" => builtin_fn!(["número"], |args, _, _| {
-                let number = ensure!(args!(args, 0), Number(value) => *value);
-
-                Ok(Value::Number(number.round()))
+                match args!(args, 0) {
+                    Value::Integer(number) => Ok(Value::Integer(number.clone())),
+                    Value::Decimal(number) => Ok(Value::Decimal(
+                        number.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero),
+                    )),
+                    number => Ok(integral_value(ensure!(number, Number(value) => value.round()))),
+                }
             }),
             "teto" => builtin_fn!(["número"], |args, _, _| {
-                let number = ensure!(args!(args, 0), Number(value) => *value);
-
-                Ok(Value::Number(number.ceil()))
+                match args!(args, 0) {
+                    Value::Integer(number) => Ok(Value::Integer(number.clone())),
+                    Value::Decimal(number) => Ok(Value::Decimal(number.ceil())),
+                    number => Ok(integral_value(ensure!(number, Number(value) => value.ceil()))),
+                }
             }),
             "piso" => builtin_fn!(["número"], |args, _, _| {
-                let number = ensure!(args!(args, 0), Number(value) => *value);
-
-                Ok(Value::Number(number.floor()))
+                match args!(args, 0) {
+                    Value::Integer(number) => Ok(Value::Integer(number.clone())),
+                    Value::Decimal(number) => Ok(Value::Decimal(number.floor())),
+                    number => Ok(integral_value(ensure!(number, Number(value) => value.floor()))),
+                }
             }),
             "raiz_quadrada" => builtin_fn!(["número"], |args, _, _| {
                 let number = ensure!(args!(args, 0), Number(value) => *value);
@@ -456,22 +641,48 @@ fn setup_math_prelude(env: &mut Environment) {
                 Ok(Value::Number(number.log10()))
             }),
             "potência" => builtin_fn!(["base", "expoente"], |args, _, _| {
+                if let (Value::Integer(base), Value::Integer(exponent)) = (args!(args, 0), args!(args, 1)) {
+                    if let Some(exponent) = exponent.to_u32() {
+                        return Ok(Value::Integer(base.pow(exponent)));
+                    }
+                }
+
                 let base = ensure!(args!(args, 0), Number(value) => *value);
                 let exponent = ensure!(args!(args, 1), Number(value) => *value);
 
                 Ok(Value::Number(base.powf(exponent)))
             }),
-            "máximo" => builtin_fn!(["número_1", "número_2"], |args, _, _| {
-                let number1 = ensure!(args!(args, 0), Number(value) => *value);
-                let number2 = ensure!(args!(args, 1), Number(value) => *value);
+            "máximo" => builtin_fn!(["número", ..."números"], |args, _, _| {
+                let mut result = args!(args, 0);
+                let mut result_number = ensure!(result, Number(value) => *value);
+                let numbers = ensure!(args!(args, 1), List(values) => values.borrow());
+
+                for number in numbers.iter() {
+                    let value = ensure!(number, Number(value) => *value);
 
-                Ok(Value::Number(number1.max(number2)))
+                    if value > result_number || result_number.is_nan() {
+                        result = number;
+                        result_number = value;
+                    }
+                }
+
+                Ok(result.clone())
             }),
-            "mínimo" => builtin_fn!(["número_1", "número_2"], |args, _, _| {
-                let number1 = ensure!(args!(args, 0), Number(value) => *value);
-                let number2 = ensure!(args!(args, 1), Number(value) => *value);
+            "mínimo" => builtin_fn!(["número", ..."números"], |args, _, _| {
+                let mut result = args!(args, 0);
+                let mut result_number = ensure!(result, Number(value) => *value);
+                let numbers = ensure!(args!(args, 1), List(values) => values.borrow());
+
+                for number in numbers.iter() {
+                    let value = ensure!(number, Number(value) => *value);
 
-                Ok(Value::Number(number1.min(number2)))
+                    if value < result_number || result_number.is_nan() {
+                        result = number;
+                        result_number = value;
+                    }
+                }
+
+                Ok(result.clone())
             }),
             "aleatório" => builtin_fn!(["mínimo", "máximo"], |args, runtime, _| {
                 let min = ensure!(args!(args, 0), Number(value) => *value);
@@ -527,9 +738,10 @@ fn setup_math_prelude(env: &mut Environment) {
                 Ok(Value::Number(radians.to_degrees()))
             }),
             "trunca" => builtin_fn!(["número"], |args, _, _| {
-                let number = ensure!(args!(args, 0), Number(value) => *value);
-
-                Ok(Value::Number(number.trunc()))
+                match args!(args, 0) {
+                    Value::Integer(number) => Ok(Value::Integer(number.clone())),
+                    number => Ok(integral_value(ensure!(number, Number(value) => value.trunc()))),
+                }
             }),
             "parte_fracionária" => builtin_fn!(["número"], |args, _, _| {
                 let number = ensure!(args!(args, 0), Number(value) => *value);
@@ -543,12 +755,24 @@ fn setup_math_prelude(env: &mut Environment) {
                 Ok(Value::Number(y.atan2(x)))
             }),
             "resto" => builtin_fn!(["número_1", "número_2"], |args, _, _| {
+                if let (Value::Integer(n1), Value::Integer(n2)) = (args!(args, 0), args!(args, 1)) {
+                    ensure_nonzero_divisor(n2)?;
+
+                    return Ok(Value::Integer(n1.mod_floor(n2)));
+                }
+
                 let n1 = ensure!(args!(args, 0), Number(value) => *value);
                 let n2 = ensure!(args!(args, 1), Number(value) => *value);
 
-                Ok(Value::Number(n1 % n2))
+                Ok(Value::Number(floored_remainder(n1, n2)))
             }),
             "resto_euclidiano" => builtin_fn!(["número_1", "número_2"], |args, _, _| {
+                if let (Value::Integer(n1), Value::Integer(n2)) = (args!(args, 0), args!(args, 1)) {
+                    ensure_nonzero_divisor(n2)?;
+
+                    return Ok(Value::Integer(n1.mod_floor(&n2.abs())));
+                }
+
                 let n1 = ensure!(args!(args, 0), Number(value) => *value);
                 let n2 = ensure!(args!(args, 1), Number(value) => *value);
 
@@ -566,19 +790,20 @@ fn setup_math_prelude(env: &mut Environment) {
                 let max_val = ensure!(args!(args, 2), Number(value) => *value);
 
                 let clamped = if x < min_val {
-                    min_val
+                    args!(args, 1)
                 } else if x > max_val {
-                    max_val
+                    args!(args, 2)
                 } else {
-                    x
+                    args!(args, 0)
                 };
 
-                Ok(Value::Number(clamped))
+                Ok(clamped.clone())
             }),
             "sinal" => builtin_fn!(["número"], |args, _, _| {
-                let number = ensure!(args!(args, 0), Number(value) => *value);
-
-                Ok(Value::Number(number.signum()))
+                match args!(args, 0) {
+                    Value::Integer(number) => Ok(Value::Integer(number.signum())),
+                    number => Ok(Value::Number(ensure!(number, Number(value) => value.signum()))),
+                }
             }),
             "hipotenusa" => builtin_fn!(["número_1", "número_2"], |args, _, _| {
                 let x = ensure!(args!(args, 0), Number(value) => *value);
@@ -592,25 +817,102 @@ fn setup_math_prelude(env: &mut Environment) {
                 Ok(Value::Number(x.exp()))
             }),
             "fatorial" => builtin_fn!(["número"], |args, _, _| {
-                let n = ensure!(args!(args, 0), Number(value) => *value);
-                let int_n = n as i64;
+                let n = match args!(args, 0) {
+                    Value::Integer(n) => n.to_u64(),
+                    value => {
+                        let n = ensure!(value, Number(value) => *value);
+
+                        match n >= 0.0 && n.fract() == 0.0 {
+                            true => n.to_u64(),
+                            false => None,
+                        }
+                    }
+                };
 
-                if n < 0.0 || (n - int_n as f64).abs() > f64::EPSILON {
-                    return Err(Box::new(RuntimeError::InvalidArgument {
-                        value: Value::Number(n),
+                let n = match n {
+                    Some(n) => n,
+                    None => {
+                        return Err(Box::new(RuntimeError::InvalidArgument {
+                            value: args!(args, 0).clone(),
+                            span: None,
+                            stacktrace: vec![],
+                        }));
+                    }
+                };
+
+                let result = (1..=n).fold(BigInt::from(1), |result, i| result * i);
+
+                Ok(Value::Integer(result))
+            })
+        })
+    );
+}
+
+fn setup_decimal_prelude(env: &mut Environment) {
+    global!(
+        env,
+        def_assoc_array!("Decimal", {
+            "arredondamentos" => enum_value!(ROUNDING_STRATEGIES),
+            "de_texto" => builtin_fn!(["texto"], |args, _, _| {
+                let text = ensure!(args!(args, 0), String(value) => value);
+
+                match parse_decimal(text) {
+                    Some(value) => Ok(Value::Decimal(value)),
+                    None => Err(Box::new(RuntimeError::InvalidValueForConversion {
+                        value: args!(args, 0).clone(),
                         span: None,
                         stacktrace: vec![],
-                    }));
+                    })),
                 }
+            }),
+            "de_número" => builtin_fn!(["número"], |args, _, _| {
+                Ok(Value::Decimal(decimal_arg(args!(args, 0))?))
+            }),
+            "arredonda" => builtin_fn!(["valor", "casas", "modo"], |args, _, _| {
+                let value = decimal_arg(args!(args, 0))?;
+                let places = decimal_places(args!(args, 1))?;
+                let strategy = rounding_strategy(args!(args, 2))?;
 
-                let mut result = 1f64;
+                Ok(Value::Decimal(value.round_dp_with_strategy(places, strategy)))
+            }),
+            "divida" => builtin_fn!(["dividendo", "divisor", "casas", "modo"], |args, _, _| {
+                let dividend = decimal_arg(args!(args, 0))?;
+                let divisor = decimal_arg(args!(args, 1))?;
+                let places = decimal_places(args!(args, 2))?;
+                let strategy = rounding_strategy(args!(args, 3))?;
 
-                for i in 1..=int_n {
-                    result *= i as f64;
+                if divisor.is_zero() {
+                    return Err(Box::new(RuntimeError::DivisionByZero {
+                        span: None,
+                        stacktrace: vec![],
+                    }));
                 }
 
-                Ok(Value::Number(result))
-            })
+                match dividend.checked_div(divisor) {
+                    Some(quotient) => Ok(Value::Decimal(
+                        quotient.round_dp_with_strategy(places, strategy),
+                    )),
+                    None => Err(Box::new(RuntimeError::DecimalOverflow {
+                        span: None,
+                        stacktrace: vec![],
+                    })),
+                }
+            }),
+            "formata" => builtin_fn!(["valor", "casas"], |args, _, _| {
+                let value = decimal_arg(args!(args, 0))?;
+                let places = decimal_places(args!(args, 1))?;
+
+                Ok(Value::String(format_decimal(value, places)))
+            }),
+            "formata_moeda" => builtin_fn!(["valor"], |args, _, _| {
+                let value = decimal_arg(args!(args, 0))?;
+                let formatted = format_decimal(value.abs(), 2);
+
+                Ok(Value::String(match value.is_sign_negative() && formatted != "0,00" {
+                    true => format!("-R$ {}", formatted),
+                    false => format!("R$ {}", formatted),
+                }))
+            }),
         })
     );
 }
@@ -619,13 +921,11 @@ fn setup_string_prelude(env: &mut Environment) {
     global!(
         env,
         def_assoc_array!("Texto", {
-            "erros" => assoc_array_enum! {
-                "CONVERSÃO_INVÁLIDA",
-            },
+            "erros" => enum_value!(STRING_ERRORS),
             "tamanho" => builtin_fn!(["texto"], |args, _, _| {
                 let text = ensure!(args!(args, 0), String(value) => value);
 
-                Ok(Value::Number(text.len() as f64))
+                Ok(Value::Integer(BigInt::from(text.len())))
             }),
             "vazio" => builtin_fn!(["texto"], |args, _, _| {
                 let text = ensure!(args!(args, 0), String(value) => value);
@@ -704,9 +1004,9 @@ fn setup_string_prelude(env: &mut Environment) {
             "índice_de" => builtin_fn!(["texto", "subtexto"], |args, _, _| {
                 let text = ensure!(args!(args, 0), String(value) => value);
                 let subtext = ensure!(args!(args, 1), String(value) => value);
-                let index = text.find(subtext).map(|i| i as f64);
+                let index = text.find(subtext).map(BigInt::from);
 
-                Ok(index.map(Value::Number).unwrap_or(Value::Nil))
+                Ok(index.map(Value::Integer).unwrap_or(Value::Nil))
             }),
             "repita" => builtin_fn!(["texto", "vezes"], |args, _, _| {
                 let text = ensure!(args!(args, 0), String(value) => value);
@@ -824,9 +1124,13 @@ fn setup_string_prelude(env: &mut Environment) {
             "para_número" => builtin_fn!(["texto"], |args, _, _| {
                 let text = ensure!(args!(args, 0), String(value) => value);
 
+                if let Ok(number) = text.parse::<BigInt>() {
+                    return Ok(success_object!(Value::Integer(number)));
+                }
+
                 match text.parse::<f64>() {
                     Ok(number) => Ok(success_object!(Value::Number(number))),
-                    Err(_) => Ok(error_object!("CONVERSÃO_INVÁLIDA")),
+                    Err(_) => Ok(error_object!(STRING_ERRORS, "CONVERSÃO_INVÁLIDA")),
                 }
             })
         })
@@ -837,12 +1141,7 @@ fn setup_file_prelude(env: &mut Environment) {
     global!(
         env,
         def_assoc_array!("Arquivo", {
-            "erros" => assoc_array_enum! {
-                "NÃO_ENCONTRADO",
-                "PERMISSÃO_NEGADA",
-                "JÁ_EXISTE",
-                "OUTRO",
-            },
+            "erros" => enum_value!(FILE_ERRORS),
             "leia" => builtin_fn!(["caminho"], |args, runtime, _| {
                 let path = ensure!(args!(args, 0), String(value) => value);
 
@@ -949,11 +1248,7 @@ fn setup_date_prelude(env: &mut Environment) {
     global!(
         env,
         def_assoc_array!("Data", {
-            "erros" => assoc_array_enum! {
-                "ISO_INVÁLIDA",
-                "TIMESTAMP_INVÁLIDO",
-                "FUSO_HORÁRIO_INVÁLIDO",
-            },
+            "erros" => enum_value!(DATE_ERRORS),
             "agora" => builtin_fn!(|_, runtime, _| {
                 let now = runtime.get_platform().date_now();
                 let tz = runtime.get_platform().timezone_offset();
@@ -970,7 +1265,7 @@ fn setup_date_prelude(env: &mut Environment) {
             "para_timestamp" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.to_timestamp_millis() as f64))
+                Ok(Value::Integer(BigInt::from(date.to_timestamp_millis())))
             }),
             "de_iso" => builtin_fn!(["texto"], |args, _, _| {
                 let text = ensure!(args!(args, 0), String(value) => value);
@@ -1005,60 +1300,178 @@ fn setup_date_prelude(env: &mut Environment) {
             "ano" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.year() as f64))
+                Ok(Value::Integer(BigInt::from(date.year())))
             }),
             "mês" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.month() as f64))
+                Ok(Value::Integer(BigInt::from(date.month())))
             }),
             "dia" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.day() as f64))
+                Ok(Value::Integer(BigInt::from(date.day())))
             }),
             "hora" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.hour() as f64))
+                Ok(Value::Integer(BigInt::from(date.hour())))
             }),
             "minuto" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.minute() as f64))
+                Ok(Value::Integer(BigInt::from(date.minute())))
             }),
             "segundo" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.second() as f64))
+                Ok(Value::Integer(BigInt::from(date.second())))
             }),
             "dia_da_semana" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.weekday() as f64))
+                Ok(Value::Integer(BigInt::from(date.weekday())))
             }),
             "dia_do_ano" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.ordinal() as f64))
+                Ok(Value::Integer(BigInt::from(date.ordinal())))
             }),
             "semana_do_ano" => builtin_fn!(["data"], |args, _, _| {
                 let date = ensure!(args!(args, 0), Date(date) => date);
 
-                Ok(Value::Number(date.iso_week() as f64))
+                Ok(Value::Integer(BigInt::from(date.iso_week())))
             }),
         })
     );
 }
 
+/// Converts a numeric argument to a decimal, accepting integers and floats.
+fn decimal_arg(value: &Value) -> Result<Decimal> {
+    let decimal = match value {
+        Value::Decimal(decimal) => Some(*decimal),
+        Value::Integer(integer) => decimal_from_integer(integer),
+        Value::Number(number) => decimal_from_f64(*number),
+        value => {
+            return Err(Box::new(RuntimeError::UnexpectedTypeError {
+                expected: ValueType::Decimal,
+                found: value.kind(),
+                span: None,
+                message: None,
+                stacktrace: vec![],
+            }))
+        }
+    };
+
+    decimal.ok_or_else(|| {
+        Box::new(RuntimeError::InvalidValueForConversion {
+            value: value.clone(),
+            span: None,
+            stacktrace: vec![],
+        })
+    })
+}
+
+fn decimal_places(value: &Value) -> Result<u32> {
+    let places = ensure!(value, Number(places) => *places);
+
+    match places.fract() == 0.0 && (0.0..=28.0).contains(&places) {
+        true => Ok(places as u32),
+        false => Err(Box::new(RuntimeError::InvalidArgument {
+            value: value.clone(),
+            span: None,
+            stacktrace: vec![],
+        })),
+    }
+}
+
+fn rounding_strategy(value: &Value) -> Result<RoundingStrategy> {
+    let variant = ensure!(value, EnumVariant(variant) => variant);
+    let is_strategy = ROUNDING_STRATEGIES.with(|strategies| variant.belongs_to(strategies));
+
+    match variant.name() {
+        _ if !is_strategy => Err(Box::new(RuntimeError::InvalidArgument {
+            value: value.clone(),
+            span: None,
+            stacktrace: vec![],
+        })),
+        "MEIO_PARA_CIMA" => Ok(RoundingStrategy::MidpointAwayFromZero),
+        "MEIO_PARA_BAIXO" => Ok(RoundingStrategy::MidpointTowardZero),
+        "MEIO_PAR" => Ok(RoundingStrategy::MidpointNearestEven),
+        "PARA_ZERO" => Ok(RoundingStrategy::ToZero),
+        "LONGE_DO_ZERO" => Ok(RoundingStrategy::AwayFromZero),
+        "PISO" => Ok(RoundingStrategy::ToNegativeInfinity),
+        "TETO" => Ok(RoundingStrategy::ToPositiveInfinity),
+        _ => Err(Box::new(RuntimeError::InvalidArgument {
+            value: value.clone(),
+            span: None,
+            stacktrace: vec![],
+        })),
+    }
+}
+
+/// Parses a decimal written either with a dot as the decimal separator or in
+/// Brazilian notation, where dots group thousands and the comma separates
+/// the fraction (`1.234,56`).
+fn parse_decimal(text: &str) -> Option<Decimal> {
+    let text = text.trim();
+
+    let normalized = match text.contains(',') {
+        true => text.replace('.', "").replace(',', "."),
+        false => text.to_string(),
+    };
+
+    Decimal::from_str_exact(&normalized).ok()
+}
+
+/// Formats a decimal in Brazilian notation with a fixed number of places,
+/// rounding half away from zero.
+fn format_decimal(value: Decimal, places: u32) -> String {
+    let rounded = value.round_dp_with_strategy(places, RoundingStrategy::MidpointAwayFromZero);
+    let digits = format!("{:.*}", places as usize, rounded.abs());
+
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits.as_str(), None),
+    };
+
+    let mut grouped = String::with_capacity(integer.len() + integer.len() / 3);
+
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push('.');
+        }
+
+        grouped.push(digit);
+    }
+
+    if let Some(fraction) = fraction {
+        grouped.push(',');
+        grouped.push_str(fraction);
+    }
+
+    match rounded.is_sign_negative() && !rounded.is_zero() {
+        true => format!("-{}", grouped),
+        false => grouped,
+    }
+}
+
+fn display_values(values: &[Value]) -> String {
+    values
+        .iter()
+        .map(display_value)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn io_error_to_error_object(kind: FileErrorKind) -> Value {
     use FileErrorKind::*;
 
     match kind {
-        NotFound => error_object!("NÃO_ENCONTRADO"),
-        PermissionDenied => error_object!("PERMISSÃO_NEGADA"),
-        AlreadyExists => error_object!("JÁ_EXISTE"),
-        _ => error_object!("OUTRO"),
+        NotFound => error_object!(FILE_ERRORS, "NÃO_ENCONTRADO"),
+        PermissionDenied => error_object!(FILE_ERRORS, "PERMISSÃO_NEGADA"),
+        AlreadyExists => error_object!(FILE_ERRORS, "JÁ_EXISTE"),
+        _ => error_object!(FILE_ERRORS, "OUTRO"),
     }
 }
 
@@ -1066,9 +1479,9 @@ fn date_error_to_error_object(kind: RuntimeError) -> Value {
     use RuntimeError::*;
 
     match kind {
-        InvalidTimestamp { .. } => error_object!("TIMESTAMP_INVÁLIDO"),
-        DateIsoParseError { .. } => error_object!("ISO_INVÁLIDA"),
-        InvalidTimeZoneString { .. } => error_object!("FUSO_HORÁRIO_INVÁLIDO"),
+        InvalidTimestamp { .. } => error_object!(DATE_ERRORS, "TIMESTAMP_INVÁLIDO"),
+        DateIsoParseError { .. } => error_object!(DATE_ERRORS, "ISO_INVÁLIDA"),
+        InvalidTimeZoneString { .. } => error_object!(DATE_ERRORS, "FUSO_HORÁRIO_INVÁLIDO"),
         _ => panic!("unexpected date error kind: {:?}", kind),
     }
 }
@@ -1,7 +1,7 @@
 pub use aegean::*;
 
 pub trait Diagnostic<S: crate::Span> {
-    fn to_report(&self) -> crate::Report<S>;
+    fn to_report(&self) -> crate::Report<'_, S>;
     fn set_span(&mut self, new_span: &S);
     fn get_span(&self) -> Option<S>;
     fn get_message(&self) -> Option<String>;
@@ -1,14 +1,61 @@
 use std::fmt;
 use std::fmt::Display;
 
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
+use tenda_scanner::Literal;
+
+use crate::enumeration::EnumVariant;
 use crate::value::Value;
 
 pub type AssociativeArray = indexmap::IndexMap<AssociativeArrayKey, Value>;
 
+/// A value that can be hashed, used both as a dictionary key and as a set
+/// element. Numbers that compare equal, like `1`, `1.0` and `1.00`, map to
+/// the same key.
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum AssociativeArrayKey {
     String(String),
     Number(i64),
+    /// An integer too large for `Number`.
+    Integer(BigInt),
+    Boolean(bool),
+    EnumVariant(EnumVariant),
+}
+
+impl AssociativeArrayKey {
+    fn from_integer(value: BigInt) -> Self {
+        match value.to_i64() {
+            Some(value) => AssociativeArrayKey::Number(value),
+            None => AssociativeArrayKey::Integer(value),
+        }
+    }
+}
+
+impl TryFrom<Value> for AssociativeArrayKey {
+    type Error = Value;
+
+    /// Fails with the value itself when it cannot be hashed: numbers with a
+    /// fractional part and every type other than integers, text, logical
+    /// values and enumeration variants.
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(value) => Ok(AssociativeArrayKey::String(value)),
+            Value::Boolean(value) => Ok(AssociativeArrayKey::Boolean(value)),
+            Value::EnumVariant(value) => Ok(AssociativeArrayKey::EnumVariant(value)),
+            Value::Integer(value) => Ok(AssociativeArrayKey::from_integer(value)),
+            Value::Number(number) if number.is_finite() && number.trunc() == number => Ok(
+                AssociativeArrayKey::from_integer(BigInt::from_f64(number).unwrap()),
+            ),
+            Value::Decimal(decimal) if decimal.fract().is_zero() => {
+                match decimal.to_i128().map(BigInt::from) {
+                    Some(value) => Ok(AssociativeArrayKey::from_integer(value)),
+                    None => Err(Value::Decimal(decimal)),
+                }
+            }
+            value => Err(value),
+        }
+    }
 }
 
 impl Display for AssociativeArrayKey {
@@ -16,6 +63,22 @@ impl Display for AssociativeArrayKey {
         match self {
             AssociativeArrayKey::String(key) => write!(f, "{}", key),
             AssociativeArrayKey::Number(key) => write!(f, "{}", key),
+            AssociativeArrayKey::Integer(key) => write!(f, "{}", key),
+            AssociativeArrayKey::Boolean(true) => write!(f, "{}", Literal::TRUE_LITERAL),
+            AssociativeArrayKey::Boolean(false) => write!(f, "{}", Literal::FALSE_LITERAL),
+            AssociativeArrayKey::EnumVariant(key) => write!(f, "{}", key),
+        }
+    }
+}
+
+impl From<AssociativeArrayKey> for Value {
+    fn from(key: AssociativeArrayKey) -> Self {
+        match key {
+            AssociativeArrayKey::String(key) => Value::String(key),
+            AssociativeArrayKey::Number(key) => Value::Integer(BigInt::from(key)),
+            AssociativeArrayKey::Integer(key) => Value::Integer(key),
+            AssociativeArrayKey::Boolean(key) => Value::Boolean(key),
+            AssociativeArrayKey::EnumVariant(key) => Value::EnumVariant(key),
         }
     }
 }
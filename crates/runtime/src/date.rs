@@ -179,6 +179,6 @@ impl Ord for Date {
 
 impl PartialOrd for Date {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.ts.cmp(&other.ts))
+        Some(self.cmp(other))
     }
 }
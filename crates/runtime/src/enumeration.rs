@@ -0,0 +1,90 @@
+use std::fmt;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ENUM_ID_COUNTER: AtomicUsize = AtomicUsize::new(1);
+
+/// A type declared with `enumeração`, holding a fixed list of variants.
+/// Each declaration gets its own id, so variants of two enumerations with
+/// the same name are still distinct.
+#[derive(Debug)]
+pub struct Enum {
+    pub id: usize,
+    pub name: String,
+    pub variants: Vec<String>,
+}
+
+impl Enum {
+    pub fn new(name: String, variants: Vec<String>) -> Self {
+        Enum {
+            id: ENUM_ID_COUNTER.fetch_add(1, Ordering::SeqCst),
+            name,
+            variants,
+        }
+    }
+
+    pub fn variant(self: &Rc<Self>, name: &str) -> Option<EnumVariant> {
+        self.variants
+            .iter()
+            .position(|variant| variant == name)
+            .map(|index| EnumVariant::new(self.clone(), index))
+    }
+
+    /// Lists every variant in declaration order.
+    pub fn all_variants(self: &Rc<Self>) -> Vec<EnumVariant> {
+        (0..self.variants.len())
+            .map(|index| EnumVariant::new(self.clone(), index))
+            .collect()
+    }
+}
+
+impl PartialEq for Enum {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+/// One of the variants of an enumeration, identified by its position in the
+/// declaration.
+#[derive(Debug, Clone)]
+pub struct EnumVariant {
+    pub enumeration: Rc<Enum>,
+    pub index: usize,
+}
+
+impl EnumVariant {
+    pub fn new(enumeration: Rc<Enum>, index: usize) -> Self {
+        EnumVariant { enumeration, index }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.enumeration.variants[self.index]
+    }
+
+    pub fn belongs_to(&self, enumeration: &Enum) -> bool {
+        self.enumeration.id == enumeration.id
+    }
+}
+
+impl PartialEq for EnumVariant {
+    fn eq(&self, other: &Self) -> bool {
+        self.enumeration.id == other.enumeration.id && self.index == other.index
+    }
+}
+
+impl Eq for EnumVariant {}
+
+impl Hash for EnumVariant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.enumeration.id.hash(state);
+        self.index.hash(state);
+    }
+}
+
+impl Display for EnumVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.enumeration.name, self.name())
+    }
+}
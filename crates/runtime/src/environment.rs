@@ -1,16 +1,22 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use super::value::Value;
 
 #[derive(Debug, Clone)]
 pub struct Environment {
     state: HashMap<String, ValueCell>,
+    constants: HashSet<String>,
 }
 
 impl Environment {
     pub fn new() -> Self {
         Environment {
             state: HashMap::new(),
+            constants: HashSet::new(),
         }
     }
 
@@ -22,6 +28,20 @@ impl Environment {
         self.state.contains_key(name)
     }
 
+    pub fn is_constant(&self, name: &str) -> bool {
+        self.constants.contains(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ValueCell> {
+        self.constants.remove(name);
+        self.state.remove(name)
+    }
+
+    pub fn set_constant(&mut self, name: String, value: ValueCell) {
+        self.constants.insert(name.clone());
+        self.set(name, value);
+    }
+
     pub fn set(&mut self, name: String, value: ValueCell) {
         match self.state.get_mut(&name) {
             Some(val) => match val {
@@ -1,8 +1,12 @@
+use std::rc::Rc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use tenda_common::span::SourceSpan;
 use tenda_parser::ast;
 
 use crate::environment::Environment;
+use crate::generator::GeneratorCode;
+use crate::module::Module;
+use crate::record::RecordMethods;
 use crate::runtime::Runtime;
 
 use super::runtime_error::Result;
@@ -15,6 +19,9 @@ pub struct Function {
     pub id: usize,
     pub object: FunctionObject,
     pub metadata: Option<FunctionRuntimeMetadata>,
+    /// The module the function was declared in, whose globals it resolves
+    /// wherever it is called from. `None` for the main program and builtins.
+    pub module: Option<Rc<Module>>,
 }
 
 impl Function {
@@ -29,6 +36,30 @@ impl Function {
             id: unique_id,
             object: FunctionObject::new(params, Box::new(captured_env), body),
             metadata: None,
+            module: None,
+        }
+    }
+
+    pub fn new_record_constructor(
+        name: String,
+        params: Vec<FunctionParam>,
+        captured_env: Environment,
+        initializers: Vec<ast::LocalDecl>,
+        methods: RecordMethods,
+    ) -> Self {
+        let unique_id = FUNCTION_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
+
+        Function {
+            id: unique_id,
+            object: FunctionObject::RecordConstructor {
+                name,
+                params,
+                env: Box::new(captured_env),
+                initializers,
+                methods,
+            },
+            metadata: None,
+            module: None,
         }
     }
 
@@ -39,6 +70,7 @@ impl Function {
             id: unique_id,
             object: FunctionObject::new_builtin(params, Box::default(), func_ptr),
             metadata: None,
+            module: None,
         }
     }
 
@@ -46,6 +78,7 @@ impl Function {
         match &self.object {
             FunctionObject::UserDefined { params, .. } => params.clone(),
             FunctionObject::Builtin { params, .. } => params.clone(),
+            FunctionObject::RecordConstructor { params, .. } => params.clone(),
         }
     }
 
@@ -53,6 +86,7 @@ impl Function {
         match &self.object {
             FunctionObject::UserDefined { env, .. } => env,
             FunctionObject::Builtin { env, .. } => env,
+            FunctionObject::RecordConstructor { env, .. } => env,
         }
     }
 
@@ -60,12 +94,25 @@ impl Function {
         match &mut self.object {
             FunctionObject::UserDefined { env, .. } => env,
             FunctionObject::Builtin { env, .. } => env,
+            FunctionObject::RecordConstructor { env, .. } => env,
         }
     }
 
     pub fn set_metadata(&mut self, metadata: FunctionRuntimeMetadata) {
         self.metadata = Some(metadata);
     }
+
+    /// Whether a call to this function in tail position can take the place of
+    /// the caller: only user functions that are not generators run a body.
+    pub fn is_tail_callable(&self) -> bool {
+        matches!(
+            self.object,
+            FunctionObject::UserDefined {
+                generator: None,
+                ..
+            }
+        )
+    }
 }
 
 impl PartialEq for Function {
@@ -77,13 +124,28 @@ impl PartialEq for Function {
 #[derive(Debug, Clone)]
 pub struct FunctionParam {
     pub name: String,
+    pub default: Option<ast::Expr>,
+    pub is_variadic: bool,
     pub is_captured: bool,
 }
 
+impl FunctionParam {
+    pub fn new(name: String, is_captured: bool) -> Self {
+        FunctionParam {
+            name,
+            default: None,
+            is_variadic: false,
+            is_captured,
+        }
+    }
+}
+
 impl From<ast::FunctionParam> for FunctionParam {
     fn from(param: ast::FunctionParam) -> Self {
         FunctionParam {
             name: param.name,
+            default: param.default,
+            is_variadic: param.variadic,
             is_captured: param.captured,
         }
     }
@@ -101,12 +163,22 @@ pub enum FunctionObject {
         params: Vec<FunctionParam>,
         env: Box<Environment>,
         body: Box<ast::Stmt>,
+        /// The lowered body of a function containing `produza`, compiled
+        /// once when the function is defined and shared by every call.
+        generator: Option<Rc<GeneratorCode>>,
     },
     Builtin {
         params: Vec<FunctionParam>,
         env: Box<Environment>,
         func_ptr: BuiltinFunctionPointer,
     },
+    RecordConstructor {
+        name: String,
+        params: Vec<FunctionParam>,
+        env: Box<Environment>,
+        initializers: Vec<ast::LocalDecl>,
+        methods: RecordMethods,
+    },
 }
 
 impl FunctionObject {
@@ -117,6 +189,9 @@ impl FunctionObject {
     ) -> Self {
         FunctionObject::UserDefined {
             params,
+            generator: body
+                .contains_yield()
+                .then(|| Rc::new(GeneratorCode::compile(&body))),
             body,
             env: context,
         }
@@ -162,11 +237,26 @@ impl FunctionRuntimeMetadata {
 
 #[macro_export]
 macro_rules! params {
+    ($($kind:literal,)* ...$rest:literal) => {
+        {
+            use $crate::FunctionParam;
+            let mut params: Vec<FunctionParam> = params![$($kind),*];
+            params.push(FunctionParam {
+                name: $rest.to_string(),
+                default: None,
+                is_variadic: true,
+                is_captured: false,
+            });
+            params
+        }
+    };
     ($($kind:expr),*) => {
         {
             use $crate::FunctionParam;
             vec![$($kind.to_string()),*].into_iter().map(|name| FunctionParam {
                 name,
+                default: None,
+                is_variadic: false,
                 is_captured: false,
             }).collect()
         }
@@ -0,0 +1,325 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tenda_common::span::SourceSpan;
+use tenda_parser::ast;
+
+use crate::associative_array::AssociativeArrayKey;
+use crate::frame::Frame;
+use crate::function::Function;
+use crate::module::Module;
+use crate::range::RangeIter;
+use crate::stack::LoopJump;
+use crate::value::Value;
+
+static GENERATOR_ID_COUNTER: AtomicUsize = AtomicUsize::new(1);
+
+/// A suspended call to a function containing `produza`. The body is lowered
+/// into a flat list of instructions so that it can stop at a `produza` and
+/// later resume from the same point, with its frames and open loops intact.
+#[derive(Debug)]
+pub struct Generator {
+    pub id: usize,
+    pub(crate) name: Option<String>,
+    pub(crate) code: Rc<GeneratorCode>,
+    pub(crate) module: Option<Rc<Module>>,
+    pub(crate) state: GeneratorState,
+}
+
+impl Generator {
+    pub(crate) fn new(
+        name: Option<String>,
+        code: Rc<GeneratorCode>,
+        module: Option<Rc<Module>>,
+        frame: Frame,
+    ) -> Self {
+        let unique_id = GENERATOR_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
+
+        Generator {
+            id: unique_id,
+            name,
+            code,
+            module,
+            state: GeneratorState::Suspended(Suspension {
+                pc: 0,
+                frames: vec![frame],
+                iterations: vec![],
+            }),
+        }
+    }
+}
+
+impl PartialEq for Generator {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum GeneratorState {
+    Suspended(Suspension),
+    Running,
+    Done,
+}
+
+/// Everything a generator needs to resume: the next instruction, the frames
+/// of the scopes it is inside of and the loops it is iterating.
+#[derive(Debug)]
+pub(crate) struct Suspension {
+    pub(crate) pc: usize,
+    pub(crate) frames: Vec<Frame>,
+    pub(crate) iterations: Vec<Iteration>,
+}
+
+/// The remaining elements of a value consumed by `para cada`. Each element
+/// comes with a key: its position, or the key of a dictionary entry.
+#[derive(Debug)]
+pub(crate) enum Iteration {
+    Values(std::iter::Enumerate<std::vec::IntoIter<Value>>),
+    Range(std::iter::Enumerate<RangeIter>),
+    Entries(indexmap::map::IntoIter<AssociativeArrayKey, Value>),
+    Generator(Rc<RefCell<Generator>>, usize),
+    /// A record or dictionary whose `próximo` function is called for each
+    /// element, until it returns `Nada`.
+    Protocol(Function, usize),
+}
+
+impl Iteration {
+    pub(crate) const PROTOCOL_FN: &'static str = "próximo";
+
+    /// Whether a single loop variable receives the key instead of the value,
+    /// as it does when iterating a dictionary.
+    pub(crate) fn binds_keys(&self) -> bool {
+        matches!(self, Iteration::Entries(_))
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum Instruction {
+    /// Runs a statement that does not contain `produza` to completion. The
+    /// index refers to the innermost lowered loop, from which any `pare` or
+    /// `continua` raised by the statement looks for the loop it acts on.
+    Exec(Box<ast::Stmt>, Option<usize>),
+    Yield(ast::Expr),
+    Return(Option<ast::Expr>),
+    JumpIfFalse(ast::Expr, usize),
+    Jump(usize),
+    PushScope,
+    PopScope,
+    IterStart(ast::Expr, SourceSpan),
+    /// Binds the next element in a new scope, or jumps past the loop when
+    /// the iteration is over.
+    IterNext(ast::ForEachItem, usize),
+    IterEnd,
+}
+
+/// Where a lowered loop continues after a `pare` or a `continua`, and how
+/// many scopes and iterations are open at that point.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LoopExit {
+    pub(crate) break_target: usize,
+    pub(crate) continue_target: usize,
+    pub(crate) scopes: usize,
+    pub(crate) iterations: usize,
+    pub(crate) label: Option<String>,
+    /// The lowered loop enclosing this one.
+    pub(crate) parent: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct GeneratorCode {
+    pub(crate) instructions: Vec<Instruction>,
+    pub(crate) loops: Vec<LoopExit>,
+}
+
+impl GeneratorCode {
+    /// Finds the lowered loop a jump acts on, starting from the innermost
+    /// one, and returns it with the instruction to continue from.
+    pub(crate) fn resolve_loop_jump(
+        &self,
+        jump: &LoopJump,
+        innermost: Option<usize>,
+    ) -> Option<(&LoopExit, usize)> {
+        let mut current = innermost;
+
+        while let Some(index) = current {
+            let exit = &self.loops[index];
+
+            if jump.targets(exit.label.as_ref()) {
+                return match jump {
+                    LoopJump::Break(_) => Some((exit, exit.break_target)),
+                    LoopJump::Continue(_) => Some((exit, exit.continue_target)),
+                };
+            }
+
+            current = exit.parent;
+        }
+
+        None
+    }
+
+    pub(crate) fn compile(body: &ast::Stmt) -> Self {
+        let mut compiler = Compiler {
+            code: GeneratorCode::default(),
+            scopes: 1,
+            iterations: 0,
+            enclosing_loops: vec![],
+        };
+
+        compiler.compile_stmt(body);
+        compiler.code
+    }
+}
+
+struct Compiler {
+    code: GeneratorCode,
+    scopes: usize,
+    iterations: usize,
+    enclosing_loops: Vec<usize>,
+}
+
+impl Compiler {
+    fn compile_stmt(&mut self, stmt: &ast::Stmt) {
+        use ast::Stmt;
+
+        if !stmt.contains_yield() {
+            let instruction = match stmt {
+                Stmt::Return(ast::Return { value, .. }) => Instruction::Return(value.clone()),
+                stmt => {
+                    Instruction::Exec(Box::new(stmt.clone()), self.enclosing_loops.last().copied())
+                }
+            };
+
+            self.emit(instruction);
+
+            return;
+        }
+
+        match stmt {
+            Stmt::Yield(ast::Yield { value, .. }) => {
+                self.emit(Instruction::Yield(value.clone()));
+            }
+            Stmt::Block(ast::Block { inner, .. }) => {
+                self.emit(Instruction::PushScope);
+                self.scopes += 1;
+
+                for stmt in &inner.inner {
+                    self.compile_stmt(stmt);
+                }
+
+                self.scopes -= 1;
+                self.emit(Instruction::PopScope);
+            }
+            Stmt::Cond(ast::Cond {
+                cond,
+                then,
+                or_else,
+                ..
+            }) => {
+                let jump_to_else = self.emit(Instruction::JumpIfFalse(cond.clone(), 0));
+
+                self.compile_stmt(then);
+
+                match or_else {
+                    Some(or_else) => {
+                        let jump_to_end = self.emit(Instruction::Jump(0));
+
+                        self.patch(jump_to_else);
+                        self.compile_stmt(or_else);
+                        self.patch(jump_to_end);
+                    }
+                    None => self.patch(jump_to_else),
+                }
+            }
+            Stmt::While(ast::While {
+                cond, body, label, ..
+            }) => {
+                let start = self.next_index();
+                let jump_to_end = self.emit(Instruction::JumpIfFalse(cond.clone(), 0));
+
+                let loop_index = self.enter_loop(start, self.iterations, label.clone());
+
+                self.compile_stmt(body);
+                self.emit(Instruction::Jump(start));
+
+                self.exit_loop(loop_index);
+                self.patch(jump_to_end);
+            }
+            Stmt::ForEach(ast::ForEach {
+                item,
+                iterable,
+                body,
+                label,
+                span,
+            }) => {
+                self.emit(Instruction::IterStart(iterable.clone(), span.clone()));
+                self.iterations += 1;
+
+                let next = self.emit(Instruction::IterNext(item.clone(), 0));
+                let loop_index = self.enter_loop(next, self.iterations, label.clone());
+
+                self.scopes += 1;
+                self.compile_stmt(body);
+                self.scopes -= 1;
+
+                self.emit(Instruction::PopScope);
+                self.emit(Instruction::Jump(next));
+
+                self.exit_loop(loop_index);
+                self.patch(next);
+
+                self.iterations -= 1;
+                self.emit(Instruction::IterEnd);
+            }
+            _ => unreachable!("'produza' is only allowed in blocks, conditionals and loops"),
+        }
+    }
+
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.code.instructions.push(instruction);
+        self.code.instructions.len() - 1
+    }
+
+    fn next_index(&self) -> usize {
+        self.code.instructions.len()
+    }
+
+    /// Points the jump emitted at `index` to the next instruction.
+    fn patch(&mut self, index: usize) {
+        let target = self.next_index();
+
+        match &mut self.code.instructions[index] {
+            Instruction::JumpIfFalse(_, jump)
+            | Instruction::Jump(jump)
+            | Instruction::IterNext(_, jump) => *jump = target,
+            _ => unreachable!(),
+        }
+    }
+
+    fn enter_loop(
+        &mut self,
+        continue_target: usize,
+        iterations: usize,
+        label: Option<String>,
+    ) -> usize {
+        self.code.loops.push(LoopExit {
+            break_target: 0,
+            continue_target,
+            scopes: self.scopes,
+            iterations,
+            label,
+            parent: self.enclosing_loops.last().copied(),
+        });
+
+        let loop_index = self.code.loops.len() - 1;
+        self.enclosing_loops.push(loop_index);
+
+        loop_index
+    }
+
+    /// Sets the loop's `pare` target to the next instruction.
+    fn exit_loop(&mut self, loop_index: usize) {
+        self.code.loops[loop_index].break_target = self.next_index();
+        self.enclosing_loops.pop();
+    }
+}
@@ -1,21 +1,33 @@
 mod associative_array;
 mod date;
+mod enumeration;
 mod environment;
 mod frame;
 mod function;
+mod generator;
+mod module;
 mod platform;
+mod range;
+mod record;
 mod runtime;
 mod runtime_error;
+mod set;
 mod stack;
 mod value;
 
 pub use associative_array::*;
 pub use date::*;
+pub use enumeration::*;
 pub use environment::*;
 pub use frame::*;
 pub use function::*;
+pub use generator::*;
+pub use module::*;
 pub use platform::*;
+pub use range::*;
+pub use record::*;
 pub use runtime::*;
 pub use runtime_error::*;
+pub use set::*;
 pub use stack::*;
 pub use value::*;
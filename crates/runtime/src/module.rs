@@ -0,0 +1,191 @@
+use std::{
+    cell::OnceCell,
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+use tenda_common::source::IdentifiedSource;
+
+use crate::{
+    environment::{Environment, ValueCell},
+    stack::StackError,
+    value::Value,
+};
+
+#[derive(Debug, Default)]
+pub struct ModuleRegistry {
+    pub prelude: Option<Environment>,
+    pub cache: HashMap<String, Value>,
+    pub loading: Vec<String>,
+    pub sources: Vec<(IdentifiedSource, Rc<str>)>,
+    names: HashMap<String, &'static str>,
+}
+
+impl ModuleRegistry {
+    pub fn new() -> Self {
+        ModuleRegistry::default()
+    }
+
+    /// Returns the name given to the source of a module. Source names live
+    /// for the whole program, so each path is leaked at most once.
+    pub fn source_name(&mut self, path: &str) -> &'static str {
+        self.names
+            .entry(path.to_string())
+            .or_insert_with(|| Box::leak(path.to_string().into_boxed_str()))
+    }
+
+    /// Registers the source of a module, replacing the one registered by an
+    /// earlier attempt at loading the same file.
+    pub fn register_source(&mut self, source_id: IdentifiedSource, source: Rc<str>) {
+        self.sources
+            .retain(|(registered, _)| registered.name() != source_id.name());
+        self.sources.push((source_id, source));
+    }
+}
+
+/// Resolves an import path against the directory of the importing source,
+/// falling back to the working directory when the importer has no name.
+pub fn resolve_module_path(importer: Option<&str>, path: &str) -> PathBuf {
+    let path = Path::new(path);
+
+    if path.is_absolute() {
+        return normalize_path(path);
+    }
+
+    let base = importer
+        .and_then(|name| Path::new(name).parent())
+        .unwrap_or(Path::new(""));
+
+    normalize_path(&base.join(path))
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// A loaded module, bound to the name given by `importe`.
+///
+/// Functions declared in a module resolve their globals in the module's own
+/// global scope, and the namespace reads through the same cells, so both
+/// always see the current state of the module.
+pub struct Module {
+    pub path: String,
+    scope: OnceCell<ModuleScope>,
+}
+
+struct ModuleScope {
+    globals: Environment,
+    exports: Vec<String>,
+}
+
+impl Module {
+    pub fn new(path: String) -> Self {
+        Module {
+            path,
+            scope: OnceCell::new(),
+        }
+    }
+
+    /// Publishes the global scope of a module that finished running. The
+    /// bindings declared on top of the prelude are exported, each as a shared
+    /// cell, keeping the cells already captured by closures.
+    pub fn finish(&self, module_env: &Environment, prelude: &Environment) {
+        let mut globals = Environment::new();
+        let mut exports = vec![];
+
+        for (name, cell) in module_env {
+            let cell = match cell {
+                ValueCell::Owned(_) if prelude.has(name) => cell.clone(),
+                ValueCell::Owned(value) => ValueCell::new_shared(value.clone()),
+                ValueCell::Shared(value) => ValueCell::Shared(value.clone()),
+            };
+
+            match module_env.is_constant(name) {
+                true => globals.set_constant(name.clone(), cell),
+                false => globals.set(name.clone(), cell),
+            }
+
+            if !prelude.has(name) {
+                exports.push(name.clone());
+            }
+        }
+
+        exports.sort();
+
+        let _ = self.scope.set(ModuleScope { globals, exports });
+    }
+
+    /// The global scope of the module, once it has finished running. While
+    /// the module is still running, its globals live in the stack instead.
+    pub fn globals(&self) -> Option<&Environment> {
+        self.scope.get().map(|scope| &scope.globals)
+    }
+
+    pub fn exports(&self) -> &[String] {
+        self.scope
+            .get()
+            .map(|scope| scope.exports.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<Value> {
+        self.exported(name).map(ValueCell::extract)
+    }
+
+    pub fn set(&self, name: &str, value: Value) -> Result<(), StackError> {
+        match self.exported(name) {
+            Some(_) => self.assign(name, value),
+            None => Err(StackError::AssignToUndefined(name.to_string())),
+        }
+    }
+
+    /// Assigns to a global of the module, writing through its shared cell.
+    pub(crate) fn assign(&self, name: &str, value: Value) -> Result<(), StackError> {
+        let globals = self
+            .globals()
+            .ok_or_else(|| StackError::AssignToUndefined(name.to_string()))?;
+
+        if globals.is_constant(name) {
+            return Err(StackError::AssignToConstant(name.to_string()));
+        }
+
+        match globals.get(name) {
+            Some(ValueCell::Shared(cell)) => {
+                *cell.borrow_mut() = value;
+                Ok(())
+            }
+            _ => Err(StackError::AssignToUndefined(name.to_string())),
+        }
+    }
+
+    fn exported(&self, name: &str) -> Option<&ValueCell> {
+        let scope = self.scope.get()?;
+
+        match scope.exports.iter().any(|export| export == name) {
+            true => scope.globals.get(name),
+            false => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Module").field("path", &self.path).finish()
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use std::fmt::Display;
+
+/// A sequence of integers from `start` towards `end`, advancing by `step`.
+/// The end is only part of the sequence when the range is inclusive. Without
+/// an explicit step, a range counts down when `end` is below `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+    pub step: i64,
+    pub inclusive: bool,
+}
+
+impl Range {
+    pub fn new(start: i64, end: i64) -> Self {
+        Range {
+            start,
+            end,
+            step: Range::default_step(start, end),
+            inclusive: true,
+        }
+    }
+
+    pub fn exclusive(start: i64, end: i64) -> Self {
+        Range {
+            inclusive: false,
+            ..Range::new(start, end)
+        }
+    }
+
+    /// Returns the same bounds with another step, which must not be zero.
+    pub fn with_step(self, step: i64) -> Self {
+        debug_assert!(step != 0, "range step must not be zero");
+
+        Range { step, ..self }
+    }
+
+    fn default_step(start: i64, end: i64) -> i64 {
+        match end < start {
+            true => -1,
+            false => 1,
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let started = match self.step > 0 {
+            true => self.start <= value,
+            false => self.start >= value,
+        };
+
+        let offset = value as i128 - self.start as i128;
+
+        started && self.is_before_end(value) && offset % self.step as i128 == 0
+    }
+
+    fn is_before_end(&self, value: i64) -> bool {
+        match (self.step > 0, self.inclusive) {
+            (true, true) => value <= self.end,
+            (true, false) => value < self.end,
+            (false, true) => value >= self.end,
+            (false, false) => value > self.end,
+        }
+    }
+
+    pub fn iter(&self) -> RangeIter {
+        RangeIter {
+            next: Some(self.start),
+            range: *self,
+        }
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} até {}", self.start, self.end)?;
+
+        if !self.inclusive {
+            write!(f, " exclusivo")?;
+        }
+
+        if self.step != Range::default_step(self.start, self.end) {
+            write!(f, " passo {}", self.step)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Produces the elements of a range one at a time, without allocating them.
+#[derive(Debug, Clone)]
+pub struct RangeIter {
+    next: Option<i64>,
+    range: Range,
+}
+
+impl Iterator for RangeIter {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+
+        if !self.range.is_before_end(current) {
+            self.next = None;
+            return None;
+        }
+
+        self.next = current.checked_add(self.range.step);
+
+        Some(current)
+    }
+}
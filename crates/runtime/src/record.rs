@@ -0,0 +1,39 @@
+use indexmap::IndexMap;
+use std::rc::Rc;
+
+use crate::{function::Function, value::Value};
+
+pub type RecordMethods = Rc<IndexMap<String, Function>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub fields: IndexMap<String, Value>,
+    pub methods: RecordMethods,
+}
+
+impl Record {
+    pub fn new(name: String, fields: IndexMap<String, Value>, methods: RecordMethods) -> Self {
+        Record {
+            name,
+            fields,
+            methods,
+        }
+    }
+
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.fields.get(field)
+    }
+
+    pub fn get_mut(&mut self, field: &str) -> Option<&mut Value> {
+        self.fields.get_mut(field)
+    }
+
+    pub fn get_method(&self, name: &str) -> Option<&Function> {
+        self.methods.get(name)
+    }
+
+    pub fn field_names(&self) -> Vec<String> {
+        self.fields.keys().cloned().collect()
+    }
+}
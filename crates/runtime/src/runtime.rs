@@ -1,18 +1,36 @@
+use indexmap::IndexMap;
+use num_bigint::BigInt;
+use num_integer::Integer as _;
+use num_traits::{Signed, ToPrimitive, Zero};
+use rust_decimal::{prelude::MathematicalOps, Decimal};
 use std::{cell::RefCell, fmt::Debug, rc::Rc};
-use tenda_common::span::SourceSpan;
+use tenda_common::{
+    source::IdentifiedSource,
+    span::{SourceSpan, Span},
+};
 use tenda_parser::{self, ast};
 use tenda_reporting::Diagnostic;
+use tenda_scanner::Scanner;
 
 use crate::{
     associative_array::{AssociativeArray, AssociativeArrayKey},
     attach_span_if_missing,
+    enumeration::Enum,
     environment::{Environment, ValueCell},
     frame::Frame,
-    function::{Function, FunctionObject},
+    function::{Function, FunctionObject, FunctionParam},
+    generator::{Generator, GeneratorCode, GeneratorState, Instruction, Iteration, Suspension},
+    module::{self, Module, ModuleRegistry},
     platform::{self},
+    range,
+    record::{Record, RecordMethods},
     runtime_error::{Result, RuntimeError},
-    stack::{Stack, StackError},
-    value::{Value, ValueType},
+    set::set_values,
+    stack::{LoopJump, Stack, StackError},
+    value::{
+        decimal_from_f64, decimal_from_integer, display_value, floored_remainder, values_equal,
+        Value, ValueType,
+    },
     FunctionName, FunctionRuntimeMetadata, StackFrame,
 };
 
@@ -20,6 +38,20 @@ use crate::{
 pub struct Runtime {
     stack: Stack,
     platform: Box<dyn platform::Platform>,
+    modules: ModuleRegistry,
+    /// Whether a call in tail position may replace the running function,
+    /// which is not the case inside `tente` blocks or generators.
+    allows_tail_call: bool,
+    tail_call: Option<TailCall>,
+}
+
+/// A call in tail position, made by `invoke_function` once the frame of the
+/// function that reached it is gone.
+#[derive(Debug)]
+struct TailCall {
+    func: Function,
+    args: Vec<Option<Value>>,
+    span: SourceSpan,
 }
 
 impl Runtime {
@@ -27,13 +59,24 @@ impl Runtime {
         Runtime {
             stack: Stack::new(),
             platform: Box::new(platform),
+            modules: ModuleRegistry::new(),
+            allows_tail_call: false,
+            tail_call: None,
         }
     }
 
     pub fn eval(&mut self, ast: &ast::Ast) -> Result<Value> {
+        if self.modules.prelude.is_none() {
+            self.modules.prelude = Some(self.get_global_env().clone());
+        }
+
         self.interpret_ast(ast)
     }
 
+    pub fn get_module_sources(&self) -> &[(IdentifiedSource, Rc<str>)] {
+        &self.modules.sources
+    }
+
     pub fn get_global_env(&self) -> &Environment {
         self.stack.global().get_env()
     }
@@ -56,10 +99,7 @@ impl Runtime {
 
             last_value = value;
 
-            if self.stack.has_return_value()
-                || self.stack.has_loop_break_flag()
-                || self.stack.has_loop_continue_flag()
-            {
+            if self.stack.has_return_value() || self.stack.has_loop_jump() {
                 break;
             }
         }
@@ -80,6 +120,9 @@ impl Runtime {
             ForEach(for_each) => self.visit_for_each(for_each),
             Break(break_stmt) => self.visit_break(break_stmt),
             Continue(continue_stmt) => self.visit_continue(continue_stmt),
+            Try(try_stmt) => self.visit_try(try_stmt),
+            Throw(throw_stmt) => self.visit_throw(throw_stmt),
+            Yield(_) => unreachable!("generator bodies are run by `resume_generator`"),
         }
         .map_err(|mut err| attach_span_if_missing!(err, stmt.get_span()))
     }
@@ -92,6 +135,11 @@ impl Runtime {
         match decl {
             Local(local) => self.visit_local_decl(local)?,
             Function(function) => self.visit_function_decl(function)?,
+            Import(import) => self.visit_import_decl(import)?,
+            Record(record) => self.visit_record_decl(record)?,
+            Enum(enumeration) => self.visit_enum_decl(enumeration)?,
+            Class(class) => self.visit_class_decl(class)?,
+            Destructuring(destructuring) => self.visit_destructuring_decl(destructuring)?,
         };
 
         Ok(Value::Nil)
@@ -112,9 +160,13 @@ impl Runtime {
             Access(indexing) => self.visit_access(indexing),
             Variable(variable) => self.visit_variable(variable),
             AssociativeArray(associative_array) => self.visit_associative_array(associative_array),
+            Comprehension(comprehension) => self.visit_comprehension(comprehension),
             AnonymousFunction(anonymous_function) => {
                 self.visit_anonymous_function(anonymous_function)
             }
+            Interpolation(interpolation) => self.visit_interpolation(interpolation),
+            Match(match_expr) => self.visit_match(match_expr, false),
+            DestructuringAssign(destructuring) => self.visit_destructuring_assign(destructuring),
         }
     }
 
@@ -133,10 +185,12 @@ impl Runtime {
     fn visit_return(&mut self, return_stmt: &ast::Return) -> Result<Value> {
         let ast::Return { value, .. } = return_stmt;
 
-        if let Some(expr) = value {
-            let value = self.visit_expr(expr)?;
-            self.stack.set_return_value(ValueCell::new(value));
-        }
+        let value = match value {
+            Some(expr) => self.visit_tail_expr(expr)?,
+            None => Value::Nil,
+        };
+
+        self.stack.set_return_value(ValueCell::new(value));
 
         Ok(Value::Nil)
     }
@@ -159,16 +213,18 @@ impl Runtime {
     }
 
     fn visit_while(&mut self, while_stmt: &ast::While) -> Result<Value> {
-        let ast::While { cond, body, .. } = while_stmt;
+        let ast::While {
+            cond, body, label, ..
+        } = while_stmt;
 
-        while self.visit_expr(cond)?.to_bool() && !self.stack.has_loop_break_flag() {
+        while self.visit_expr(cond)?.to_bool() {
             self.interpret_stmt(body)?;
 
-            self.stack.set_loop_continue_flag(false);
+            if self.settle_loop_jump(label.as_ref()) || self.stack.has_return_value() {
+                break;
+            }
         }
 
-        self.stack.set_loop_break_flag(false);
-
         Ok(Value::Nil)
     }
 
@@ -177,54 +233,292 @@ impl Runtime {
             item,
             iterable,
             body,
+            label,
             span,
         } = for_each;
 
         let iterable = self.visit_expr(iterable)?;
+        let mut iteration = self.iterate(iterable, span)?;
+
+        while let Some(entry) = self.next_item(&mut iteration)? {
+            let frame = self.bind_loop_item(item, entry, iteration.binds_keys())?;
+
+            self.stack.push(frame);
+            self.interpret_stmt(body)?;
+            self.stack.pop();
+
+            if self.settle_loop_jump(label.as_ref()) || self.stack.has_return_value() {
+                break;
+            }
+        }
+
+        Ok(Value::Nil)
+    }
+
+    /// Handles a `pare` or `continua` pending after an iteration of the loop
+    /// with `label`, and returns whether the loop must stop. A jump aimed at
+    /// an outer loop stops this one and is left pending for the next.
+    fn settle_loop_jump(&mut self, label: Option<&String>) -> bool {
+        match self.stack.get_loop_jump() {
+            None => false,
+            Some(jump) if !jump.targets(label) => true,
+            Some(jump) => {
+                let is_break = matches!(jump, LoopJump::Break(_));
+                self.stack.take_loop_jump();
+                is_break
+            }
+        }
+    }
+
+    fn iterate(&mut self, iterable: Value, span: &SourceSpan) -> Result<Iteration> {
+        if let Some(next) = self.iteration_protocol(&iterable) {
+            return Ok(Iteration::Protocol(next, 0));
+        }
 
-        if !iterable.is_iterable() {
-            return Err(Box::new(RuntimeError::NotIterable {
+        match iterable {
+            Value::Generator(generator) => Ok(Iteration::Generator(generator, 0)),
+            Value::String(string) => Ok(Iteration::Values(
+                string
+                    .chars()
+                    .map(|char| Value::String(char.to_string()))
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .enumerate(),
+            )),
+            Value::AssociativeArray(associative_array) => Ok(Iteration::Entries(
+                associative_array.borrow().clone().into_iter(),
+            )),
+            Value::List(list) => Ok(Iteration::Values(
+                list.borrow().clone().into_iter().enumerate(),
+            )),
+            Value::Set(set) => Ok(Iteration::Values(
+                set_values(&set.borrow()).into_iter().enumerate(),
+            )),
+            Value::Enum(enumeration) => Ok(Iteration::Values(
+                enumeration
+                    .all_variants()
+                    .into_iter()
+                    .map(Value::EnumVariant)
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .enumerate(),
+            )),
+            Value::Range(range) => Ok(Iteration::Range(range.iter().enumerate())),
+            iterable => Err(Box::new(RuntimeError::NotIterable {
                 value: iterable.kind(),
                 span: Some(span.clone()),
                 stacktrace: vec![],
-            }));
+            })),
         }
+    }
 
-        for value in iterable {
-            let mut frame = Frame::new();
+    /// Finds the `próximo` function of a record or dictionary that implements
+    /// the iteration protocol. A method is bound to its record.
+    fn iteration_protocol(&self, iterable: &Value) -> Option<Function> {
+        match iterable {
+            Value::AssociativeArray(associative_array) => {
+                let key = AssociativeArrayKey::String(Iteration::PROTOCOL_FN.to_string());
 
-            let stored_value = if item.captured {
-                ValueCell::new_shared(value.clone())
-            } else {
-                ValueCell::new(value.clone())
-            };
+                match associative_array.borrow().get(&key) {
+                    Some(Value::Function(next)) => Some(next.clone()),
+                    _ => None,
+                }
+            }
+            Value::Record(record) => {
+                let record_ref = record.borrow();
+
+                if let Some(field) = record_ref.get(Iteration::PROTOCOL_FN) {
+                    return match field {
+                        Value::Function(next) => Some(next.clone()),
+                        _ => None,
+                    };
+                }
 
-            frame.get_env_mut().set(item.name.clone(), stored_value);
+                let mut method = record_ref.get_method(Iteration::PROTOCOL_FN)?.clone();
 
-            self.stack.push(frame);
-            self.interpret_stmt(body)?;
+                method.get_env_mut().set(
+                    ast::Variable::THIS.to_string(),
+                    ValueCell::new_shared(Value::Record(record.clone())),
+                );
 
-            if self.stack.has_loop_break_flag() {
-                break;
+                Some(method)
             }
+            _ => None,
+        }
+    }
 
-            self.stack.set_loop_continue_flag(false);
-            self.stack.pop();
+    /// Returns the next element of the iteration along with its key.
+    fn next_item(&mut self, iteration: &mut Iteration) -> Result<Option<(Value, Value)>> {
+        let (value, position) = match iteration {
+            Iteration::Values(values) => {
+                return Ok(values
+                    .next()
+                    .map(|(position, value)| (Value::Integer(BigInt::from(position)), value)));
+            }
+            Iteration::Range(range) => {
+                return Ok(range.next().map(|(position, value)| {
+                    (
+                        Value::Integer(BigInt::from(position)),
+                        Value::Integer(BigInt::from(value)),
+                    )
+                }));
+            }
+            Iteration::Entries(entries) => {
+                return Ok(entries.next().map(|(key, value)| (key.into(), value)));
+            }
+            Iteration::Generator(generator, position) => {
+                (self.resume_generator(&generator.clone())?, position)
+            }
+            Iteration::Protocol(next, position) => {
+                match self.call_function(next.clone(), vec![], None)? {
+                    Value::Nil => (None, position),
+                    value => (Some(value), position),
+                }
+            }
+        };
+
+        let key = Value::Integer(BigInt::from(*position));
+        *position += 1;
+
+        Ok(value.map(|value| (key, value)))
+    }
+
+    fn bind_loop_item(
+        &mut self,
+        item: &ast::ForEachItem,
+        (key, value): (Value, Value),
+        binds_keys: bool,
+    ) -> Result<Frame> {
+        let mut frame = Frame::new();
+        let mut bindings = vec![];
+
+        match item {
+            ast::ForEachItem::Single(pattern) if binds_keys => {
+                self.destructure(pattern, key, &mut bindings)?
+            }
+            ast::ForEachItem::Single(pattern) => self.destructure(pattern, value, &mut bindings)?,
+            ast::ForEachItem::Pair(key_pattern, value_pattern) => {
+                self.destructure(key_pattern, key, &mut bindings)?;
+                self.destructure(value_pattern, value, &mut bindings)?;
+            }
+        }
+
+        for (binding, value) in bindings {
+            frame
+                .get_env_mut()
+                .set(binding.name.clone(), binding_cell(binding, value));
         }
 
-        self.stack.set_loop_break_flag(false);
+        Ok(frame)
+    }
+
+    fn visit_break(&mut self, break_stmt: &ast::Break) -> Result<Value> {
+        self.stack
+            .set_loop_jump(LoopJump::Break(break_stmt.label.clone()));
 
         Ok(Value::Nil)
     }
 
-    fn visit_break(&mut self, _break_stmt: &ast::Break) -> Result<Value> {
-        self.stack.set_loop_break_flag(true);
+    fn visit_continue(&mut self, continue_stmt: &ast::Continue) -> Result<Value> {
+        self.stack
+            .set_loop_jump(LoopJump::Continue(continue_stmt.label.clone()));
 
         Ok(Value::Nil)
     }
 
-    fn visit_continue(&mut self, _continue_stmt: &ast::Continue) -> Result<Value> {
-        self.stack.set_loop_continue_flag(true);
+    fn visit_try(&mut self, try_stmt: &ast::Try) -> Result<Value> {
+        let ast::Try {
+            body,
+            catch,
+            finally,
+            ..
+        } = try_stmt;
+
+        let depth = self.stack.depth();
+        let allows_tail_call = std::mem::replace(&mut self.allows_tail_call, false);
+
+        let result = match (self.interpret_stmt(body), catch) {
+            (Err(err), Some(catch)) => {
+                self.stack.truncate(depth);
+                self.visit_catch(catch, &err)
+            }
+            (result, _) => result,
+        };
+
+        if result.is_err() {
+            self.stack.truncate(depth);
+        }
+
+        let result = match finally {
+            Some(finally) => {
+                let loop_jump = self.stack.take_loop_jump();
+
+                // An error raised by `finalmente` replaces both the outcome of
+                // the body and any `para`/`continua` it was carrying out.
+                match self.interpret_stmt(finally) {
+                    Ok(_) => {
+                        if let Some(loop_jump) = loop_jump {
+                            self.stack.set_loop_jump(loop_jump);
+                        }
+
+                        result
+                    }
+                    Err(err) => Err(err),
+                }
+            }
+            None => result,
+        };
+
+        self.allows_tail_call = allows_tail_call;
+
+        result.map(|_| Value::Nil)
+    }
+
+    fn visit_throw(&mut self, throw_stmt: &ast::Throw) -> Result<Value> {
+        let ast::Throw { value, span } = throw_stmt;
+
+        let value = self.visit_expr(value)?;
+
+        let message = match &value {
+            Value::String(message) => Some(message.clone()),
+            Value::AssociativeArray(associative_array) => match associative_array
+                .borrow()
+                .get(&AssociativeArrayKey::String("mensagem".to_string()))
+            {
+                Some(Value::String(message)) => Some(message.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        Err(Box::new(RuntimeError::Thrown {
+            value,
+            span: Some(span.clone()),
+            message,
+            stacktrace: vec![],
+        }))
+    }
+
+    fn visit_catch(&mut self, catch: &ast::Catch, err: &RuntimeError) -> Result<Value> {
+        let ast::Catch { param, body, .. } = catch;
+
+        let mut frame = Frame::new();
+
+        if let Some(param) = param {
+            let value = err.to_value();
+
+            let stored_value = if param.captured {
+                ValueCell::new_shared(value)
+            } else {
+                ValueCell::new(value)
+            };
+
+            frame.get_env_mut().set(param.name.clone(), stored_value);
+        }
+
+        self.stack.push(frame);
+        self.interpret_stmt(body)?;
+        self.stack.pop();
 
         Ok(Value::Nil)
     }
@@ -243,18 +537,46 @@ impl Runtime {
             false => ValueCell::new(value),
         };
 
-        match self.stack.define(name.clone(), value) {
-            Ok(_) => Ok(Value::Nil),
-            Err(err) => match err {
-                StackError::AlreadyDeclared => Err(Box::new(RuntimeError::AlreadyDeclared {
-                    var_name: name.to_string(),
-                    span: Some(span.clone()),
-                    help: Some("declare a variável com outro nome ou use `=` para atribuir um novo valor a ela".to_string()),
-                    stacktrace: vec![],
-                })),
-                _ => unreachable!(),
-            },
+        match local.constant {
+            true => self.define_constant(name, value, span)?,
+            false => self.define_variable(name, value, span)?,
+        }
+
+        Ok(Value::Nil)
+    }
+
+    fn visit_destructuring_decl(
+        &mut self,
+        destructuring: &ast::DestructuringDecl,
+    ) -> Result<Value> {
+        let ast::DestructuringDecl {
+            pattern,
+            value,
+            span,
+        } = destructuring;
+
+        let value = self.visit_expr(value)?;
+        let mut bindings = vec![];
+
+        self.destructure(pattern, value, &mut bindings)?;
+
+        for (binding, value) in bindings {
+            self.define_variable(&binding.name, binding_cell(binding, value), span)?;
         }
+
+        Ok(Value::Nil)
+    }
+
+    fn define_variable(&mut self, name: &str, value: ValueCell, span: &SourceSpan) -> Result<()> {
+        let result = self.stack.define(name.to_string(), value);
+
+        declaration_result(name, result, span)
+    }
+
+    fn define_constant(&mut self, name: &str, value: ValueCell, span: &SourceSpan) -> Result<()> {
+        let result = self.stack.define_constant(name.to_string(), value);
+
+        declaration_result(name, result, span)
     }
 
     fn visit_function_decl(&mut self, function: &ast::FunctionDecl) -> Result<Value> {
@@ -285,76 +607,397 @@ impl Runtime {
 }
 
 impl Runtime {
-    fn visit_binary(&mut self, binary: &ast::BinaryOp) -> Result<Value> {
-        let ast::BinaryOp {
-            lhs, op, rhs, span, ..
-        } = binary;
+    fn visit_record_decl(&mut self, record: &ast::RecordDecl) -> Result<Value> {
+        let ast::RecordDecl {
+            name, fields, span, ..
+        } = record;
 
-        use ast::BinaryOperator::*;
-        use Value::*;
+        let params = fields
+            .iter()
+            .map(|field| FunctionParam::new(field.clone(), false))
+            .collect();
 
-        let lhs = self.visit_expr(lhs)?;
+        let mut constructor = Function::new_record_constructor(
+            name.clone(),
+            params,
+            Environment::new(),
+            vec![],
+            RecordMethods::default(),
+        );
+        constructor.set_metadata(FunctionRuntimeMetadata::new(
+            Some(span.clone()),
+            Some(name.clone()),
+        ));
+        constructor.module = self.stack.module().cloned();
+
+        let constructor = match record.captured {
+            true => ValueCell::new_shared(Value::Function(constructor)),
+            false => ValueCell::new(Value::Function(constructor)),
+        };
 
-        match op {
-            LogicalAnd => {
-                if lhs.to_bool() {
-                    return self.visit_expr(rhs);
-                } else {
-                    return Ok(lhs);
-                }
-            }
-            LogicalOr => {
-                if lhs.to_bool() {
-                    return Ok(lhs);
-                } else {
-                    return self.visit_expr(rhs);
-                }
-            }
-            _ => {}
+        match self.stack.define(name.clone(), constructor) {
+            Ok(_) => Ok(Value::Nil),
+            Err(err) => match err {
+                StackError::AlreadyDeclared => Err(Box::new(RuntimeError::AlreadyDeclared {
+                    var_name: name.to_string(),
+                    span: Some(span.clone()),
+                    help: Some("declare a estrutura com outro nome".to_string()),
+                    stacktrace: vec![],
+                })),
+                _ => unreachable!(),
+            },
+        }
+    }
+
+    fn visit_enum_decl(&mut self, enumeration: &ast::EnumDecl) -> Result<Value> {
+        let ast::EnumDecl {
+            name,
+            variants,
+            span,
+            ..
+        } = enumeration;
+
+        let value = Value::Enum(Rc::new(Enum::new(name.clone(), variants.clone())));
+
+        let value = match enumeration.captured {
+            true => ValueCell::new_shared(value),
+            false => ValueCell::new(value),
         };
 
-        let rhs = self.visit_expr(rhs)?;
+        match self.stack.define(name.clone(), value) {
+            Ok(_) => Ok(Value::Nil),
+            Err(err) => match err {
+                StackError::AlreadyDeclared => Err(Box::new(RuntimeError::AlreadyDeclared {
+                    var_name: name.to_string(),
+                    span: Some(span.clone()),
+                    help: Some("declare a enumeração com outro nome".to_string()),
+                    stacktrace: vec![],
+                })),
+                _ => unreachable!(),
+            },
+        }
+    }
 
-        let value = match op {
-            Add => match (lhs, rhs) {
-                (Number(lhs), Number(rhs)) => Number(lhs + rhs),
-                (String(lhs), String(rhs)) => String(format!("{}{}", lhs, rhs)),
-                (String(lhs), rhs) => String(format!("{}{}", lhs, rhs)),
-                (lhs, String(rhs)) => String(format!("{}{}", lhs, rhs)),
-                (List(lhs), List(rhs)) => {
-                    let mut list = lhs.borrow().clone();
-                    list.extend_from_slice(&rhs.borrow());
+    fn visit_class_decl(&mut self, class: &ast::ClassDecl) -> Result<Value> {
+        let ast::ClassDecl {
+            name,
+            params,
+            body,
+            span,
+            ..
+        } = class;
 
-                    List(Rc::new(RefCell::new(list)))
-                }
-                (Date(rhs), Number(millis)) => Value::Date(rhs + millis as i64),
-                (Number(millis), Date(rhs)) => Value::Date(rhs + millis as i64),
-                (lhs, rhs) => {
-                    return Err(Box::new(RuntimeError::TypeMismatch {
-                        first: lhs.kind(),
-                        second: rhs.kind(),
-                        span: Some(span.clone()),
-                        message: Some(format!("não é possível somar '{}' e '{}'", lhs, rhs)),
-                        stacktrace: vec![],
-                    }));
+        let mut initializers = vec![];
+        let mut methods = IndexMap::new();
+
+        for member in &body.inner {
+            match member {
+                ast::Stmt::Decl(ast::Decl::Local(field)) => initializers.push(field.clone()),
+                ast::Stmt::Decl(ast::Decl::Function(method)) => {
+                    let metadata = FunctionRuntimeMetadata::new(
+                        Some(method.span.clone()),
+                        Some(method.name.clone()),
+                    );
+
+                    let mut method_fn =
+                        self.create_function(&method.params, method.body.clone(), Some(metadata));
+
+                    method_fn.get_env_mut().remove(ast::Variable::THIS);
+
+                    methods.insert(method.name.clone(), method_fn);
                 }
+                _ => unreachable!(),
+            }
+        }
+
+        let constructor_params = params
+            .iter()
+            .map(|param| FunctionParam {
+                is_captured: true,
+                ..param.clone().into()
+            })
+            .collect();
+
+        let mut constructor = Function::new_record_constructor(
+            name.clone(),
+            constructor_params,
+            self.capture_environment(params),
+            initializers,
+            Rc::new(methods),
+        );
+        constructor.set_metadata(FunctionRuntimeMetadata::new(
+            Some(span.clone()),
+            Some(name.clone()),
+        ));
+        constructor.module = self.stack.module().cloned();
+
+        let constructor = match class.captured {
+            true => ValueCell::new_shared(Value::Function(constructor)),
+            false => ValueCell::new(Value::Function(constructor)),
+        };
+
+        match self.stack.define(name.clone(), constructor) {
+            Ok(_) => Ok(Value::Nil),
+            Err(err) => match err {
+                StackError::AlreadyDeclared => Err(Box::new(RuntimeError::AlreadyDeclared {
+                    var_name: name.to_string(),
+                    span: Some(span.clone()),
+                    help: Some("declare a classe com outro nome".to_string()),
+                    stacktrace: vec![],
+                })),
+                _ => unreachable!(),
             },
-            Subtract => match (lhs, rhs) {
-                (Number(lhs), Number(rhs)) => Number(lhs - rhs),
-                (Date(rhs), Number(millis)) => Value::Date(rhs - millis as i64),
-                (Number(millis), Date(rhs)) => Value::Date(rhs - millis as i64),
-                (lhs, rhs) => {
-                    return Err(Box::new(RuntimeError::TypeMismatch {
-                        first: lhs.kind(),
-                        second: rhs.kind(),
-                        span: Some(span.clone()),
-                        message: Some(format!("não é possível subtrair '{}' de '{}'", rhs, lhs)),
-                        stacktrace: vec![],
-                    }));
-                }
+        }
+    }
+
+    fn visit_import_decl(&mut self, import: &ast::ImportDecl) -> Result<Value> {
+        let ast::ImportDecl {
+            path, name, span, ..
+        } = import;
+
+        let namespace = self.load_module(path, span)?;
+
+        let namespace = match import.captured {
+            true => ValueCell::new_shared(namespace),
+            false => ValueCell::new(namespace),
+        };
+
+        match self.stack.define(name.clone(), namespace) {
+            Ok(_) => Ok(Value::Nil),
+            Err(err) => match err {
+                StackError::AlreadyDeclared => Err(Box::new(RuntimeError::AlreadyDeclared {
+                    var_name: name.to_string(),
+                    span: Some(span.clone()),
+                    help: Some("importe o módulo com outro nome usando `como`".to_string()),
+                    stacktrace: vec![],
+                })),
+                _ => unreachable!(),
+            },
+        }
+    }
+
+    fn load_module(&mut self, path: &str, span: &SourceSpan) -> Result<Value> {
+        let resolved = module::resolve_module_path(Span::source(span).name(), path);
+        let resolved = resolved.to_string_lossy().to_string();
+
+        let key = self.module_key(&resolved);
+
+        if let Some(namespace) = self.modules.cache.get(&key) {
+            return Ok(namespace.clone());
+        }
+
+        if self.modules.loading.contains(&key) {
+            return Err(Box::new(RuntimeError::CircularImport {
+                path: path.to_string(),
+                span: Some(span.clone()),
+                stacktrace: vec![],
+            }));
+        }
+
+        let source = self.platform.read_file(&resolved).map_err(|_| {
+            Box::new(RuntimeError::ModuleNotFound {
+                path: path.to_string(),
+                span: Some(span.clone()),
+                help: Some(format!(
+                    "verifique se o arquivo '{}' existe e pode ser lido",
+                    resolved
+                )),
+                stacktrace: vec![],
+            })
+        })?;
+
+        let mut source_id = IdentifiedSource::new();
+        source_id.set_name(self.modules.source_name(&resolved));
+
+        // Parse errors point into the module, so its source is registered
+        // before parsing. A later attempt at the same file replaces the entry.
+        self.modules
+            .register_source(source_id, Rc::from(source.as_str()));
+
+        let invalid_module = |message: String, inner_span: SourceSpan| {
+            Box::new(RuntimeError::InvalidModule {
+                path: path.to_string(),
+                span: Some(inner_span),
+                message: Some(message),
+                import_span: Some(span.clone().with_label("importado aqui".to_string())),
+                stacktrace: vec![],
+            })
+        };
+
+        let tokens = Scanner::new(&source, source_id).scan().map_err(|errs| {
+            let err = errs.into_iter().next().unwrap();
+            invalid_module(err.to_string(), err.get_span().unwrap_or(span.clone()))
+        })?;
+
+        let ast = tenda_parser::Parser::new(&tokens, source_id)
+            .parse()
+            .map_err(|errs| {
+                let err = errs.into_iter().next().unwrap();
+                invalid_module(err.to_string(), err.get_span().unwrap_or(span.clone()))
+            })?;
+
+        let prelude = self.modules.prelude.clone().unwrap_or_default();
+
+        let module = Rc::new(Module::new(key.clone()));
+
+        let mut module_stack = Stack::new();
+        *module_stack.global_mut() = Frame::from_env(prelude.clone());
+        module_stack.replace_module(Some(module.clone()));
+
+        self.modules.loading.push(key.clone());
+        std::mem::swap(&mut self.stack, &mut module_stack);
+
+        let result = self.interpret_ast(&ast);
+
+        std::mem::swap(&mut self.stack, &mut module_stack);
+        self.modules.loading.pop();
+
+        result?;
+
+        module.finish(module_stack.global().get_env(), &prelude);
+
+        let namespace = Value::Module(module);
+
+        self.modules.cache.insert(key, namespace.clone());
+
+        Ok(namespace)
+    }
+
+    /// Identifies a module by its canonical path, as the platform reports it,
+    /// so every spelling of the same file, including through symbolic links,
+    /// shares one cache entry.
+    fn module_key(&self, resolved: &str) -> String {
+        let path = std::path::Path::new(resolved);
+
+        if let Ok(canonical) = self.platform.canonicalize(resolved) {
+            return canonical;
+        }
+
+        match path.is_absolute() {
+            true => resolved.to_string(),
+            false => match self.platform.current_dir() {
+                Ok(dir) => std::path::Path::new(&dir)
+                    .join(path)
+                    .to_string_lossy()
+                    .to_string(),
+                Err(_) => resolved.to_string(),
+            },
+        }
+    }
+}
+
+impl Runtime {
+    fn visit_binary(&mut self, binary: &ast::BinaryOp) -> Result<Value> {
+        let ast::BinaryOp {
+            lhs, op, rhs, span, ..
+        } = binary;
+
+        use ast::BinaryOperator::*;
+
+        let lhs = self.visit_expr(lhs)?;
+
+        match op {
+            LogicalAnd => {
+                if lhs.to_bool() {
+                    return self.visit_expr(rhs);
+                } else {
+                    return Ok(lhs);
+                }
+            }
+            LogicalOr => {
+                if lhs.to_bool() {
+                    return Ok(lhs);
+                } else {
+                    return self.visit_expr(rhs);
+                }
+            }
+            NilCoalescing => {
+                if matches!(lhs, Value::Nil) {
+                    return self.visit_expr(rhs);
+                } else {
+                    return Ok(lhs);
+                }
+            }
+            _ => {}
+        };
+
+        let rhs = self.visit_expr(rhs)?;
+
+        self.apply_binary_op(op, lhs, rhs, span)
+    }
+
+    fn apply_binary_op(
+        &mut self,
+        op: &ast::BinaryOperator,
+        lhs: Value,
+        rhs: Value,
+        span: &SourceSpan,
+    ) -> Result<Value> {
+        use ast::BinaryOperator::*;
+        use Value::*;
+
+        // Equality compares numbers of different types exactly in
+        // `values_equal`, since promoting to a float would round large integers.
+        let (lhs, rhs) = match op {
+            Equality | Inequality => (lhs, rhs),
+            _ => promote_numbers(lhs, rhs),
+        };
+
+        let value = match op {
+            Add => match (lhs, rhs) {
+                (Number(lhs), Number(rhs)) => Number(lhs + rhs),
+                (Integer(lhs), Integer(rhs)) => Integer(lhs + rhs),
+                (Decimal(lhs), Decimal(rhs)) => checked_decimal(lhs.checked_add(rhs), span)?,
+                (String(lhs), String(rhs)) => String(format!("{}{}", lhs, rhs)),
+                (String(lhs), rhs) => String(format!("{}{}", lhs, rhs)),
+                (lhs, String(rhs)) => String(format!("{}{}", lhs, rhs)),
+                (List(lhs), List(rhs)) => {
+                    let mut list = lhs.borrow().clone();
+                    list.extend_from_slice(&rhs.borrow());
+
+                    List(Rc::new(RefCell::new(list)))
+                }
+                (Date(rhs), Number(millis)) => Value::Date(rhs + millis as i64),
+                (Number(millis), Date(rhs)) => Value::Date(rhs + millis as i64),
+                (Date(rhs), Integer(millis)) => Value::Date(rhs + saturating_i64(&millis)),
+                (Integer(millis), Date(rhs)) => Value::Date(rhs + saturating_i64(&millis)),
+                (lhs, rhs) => {
+                    return Err(Box::new(RuntimeError::TypeMismatch {
+                        first: lhs.kind(),
+                        second: rhs.kind(),
+                        span: Some(span.clone()),
+                        message: Some(format!("não é possível somar '{}' e '{}'", lhs, rhs)),
+                        stacktrace: vec![],
+                    }));
+                }
+            },
+            Subtract => match (lhs, rhs) {
+                (Number(lhs), Number(rhs)) => Number(lhs - rhs),
+                (Integer(lhs), Integer(rhs)) => Integer(lhs - rhs),
+                (Decimal(lhs), Decimal(rhs)) => checked_decimal(lhs.checked_sub(rhs), span)?,
+                (Set(lhs), Set(rhs)) => {
+                    let set = lhs.borrow().difference(&rhs.borrow()).cloned().collect();
+
+                    Set(Rc::new(RefCell::new(set)))
+                }
+                (Date(rhs), Number(millis)) => Value::Date(rhs - millis as i64),
+                (Number(millis), Date(rhs)) => Value::Date(rhs - millis as i64),
+                (Date(rhs), Integer(millis)) => Value::Date(rhs - saturating_i64(&millis)),
+                (Integer(millis), Date(rhs)) => Value::Date(rhs - saturating_i64(&millis)),
+                (lhs, rhs) => {
+                    return Err(Box::new(RuntimeError::TypeMismatch {
+                        first: lhs.kind(),
+                        second: rhs.kind(),
+                        span: Some(span.clone()),
+                        message: Some(format!("não é possível subtrair '{}' de '{}'", rhs, lhs)),
+                        stacktrace: vec![],
+                    }));
+                }
             },
             Multiply => match (lhs, rhs) {
                 (Number(lhs), Number(rhs)) => Number(lhs * rhs),
+                (Integer(lhs), Integer(rhs)) => Integer(lhs * rhs),
+                (Decimal(lhs), Decimal(rhs)) => checked_decimal(lhs.checked_mul(rhs), span)?,
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -375,7 +1018,22 @@ impl Runtime {
                         stacktrace: vec![],
                     }));
                 }
+                (Integer(_), Integer(rhs)) if rhs.is_zero() => {
+                    return Err(Box::new(RuntimeError::DivisionByZero {
+                        span: Some(span.clone()),
+                        stacktrace: vec![],
+                    }));
+                }
+                (Decimal(_), Decimal(rhs)) if rhs.is_zero() => {
+                    return Err(Box::new(RuntimeError::DivisionByZero {
+                        span: Some(span.clone()),
+                        stacktrace: vec![],
+                    }));
+                }
                 (Number(lhs), Number(rhs)) => Number(lhs / rhs),
+                (Integer(lhs), Integer(rhs)) if (&lhs % &rhs).is_zero() => Integer(lhs / rhs),
+                (Integer(lhs), Integer(rhs)) => Number(integer_to_f64(&lhs) / integer_to_f64(&rhs)),
+                (Decimal(lhs), Decimal(rhs)) => checked_decimal(lhs.checked_div(rhs), span)?,
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -386,8 +1044,56 @@ impl Runtime {
                     }));
                 }
             },
+            IntegerDivision => match (lhs, rhs) {
+                (Number(_), Number(0.0)) => {
+                    return Err(Box::new(RuntimeError::DivisionByZero {
+                        span: Some(span.clone()),
+                        stacktrace: vec![],
+                    }));
+                }
+                (Integer(_), Integer(rhs)) if rhs.is_zero() => {
+                    return Err(Box::new(RuntimeError::DivisionByZero {
+                        span: Some(span.clone()),
+                        stacktrace: vec![],
+                    }));
+                }
+                (Decimal(_), Decimal(rhs)) if rhs.is_zero() => {
+                    return Err(Box::new(RuntimeError::DivisionByZero {
+                        span: Some(span.clone()),
+                        stacktrace: vec![],
+                    }));
+                }
+                (Number(lhs), Number(rhs)) => Number((lhs / rhs).floor()),
+                (Integer(lhs), Integer(rhs)) => Integer(lhs.div_floor(&rhs)),
+                (Decimal(lhs), Decimal(rhs)) => {
+                    checked_decimal(lhs.checked_div(rhs).map(|value| value.floor()), span)?
+                }
+                (lhs, rhs) => {
+                    return Err(Box::new(RuntimeError::TypeMismatch {
+                        first: lhs.kind(),
+                        second: rhs.kind(),
+                        span: Some(span.clone()),
+                        message: Some(format!(
+                            "não é possível fazer a divisão inteira de '{}' por '{}'",
+                            lhs, rhs
+                        )),
+                        stacktrace: vec![],
+                    }));
+                }
+            },
             Exponentiation => match (lhs, rhs) {
                 (Number(lhs), Number(rhs)) => Number(lhs.powf(rhs)),
+                (Integer(lhs), Integer(rhs)) => match rhs.to_u32() {
+                    Some(exponent) => Integer(lhs.pow(exponent)),
+                    None => Number(integer_to_f64(&lhs).powf(integer_to_f64(&rhs))),
+                },
+                (Decimal(lhs), Decimal(rhs)) if rhs.fract().is_zero() => match rhs.to_i64() {
+                    Some(exponent) => checked_decimal(lhs.checked_powi(exponent), span)?,
+                    None => Number(decimal_to_f64(&lhs).powf(decimal_to_f64(&rhs))),
+                },
+                (Decimal(lhs), Decimal(rhs)) => {
+                    Number(decimal_to_f64(&lhs).powf(decimal_to_f64(&rhs)))
+                }
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -402,7 +1108,30 @@ impl Runtime {
                 }
             },
             Modulo => match (lhs, rhs) {
-                (Number(lhs), Number(rhs)) => Number(lhs % rhs),
+                (Number(lhs), Number(rhs)) => Number(floored_remainder(lhs, rhs)),
+                (Integer(_), Integer(rhs)) if rhs.is_zero() => {
+                    return Err(Box::new(RuntimeError::DivisionByZero {
+                        span: Some(span.clone()),
+                        stacktrace: vec![],
+                    }));
+                }
+                (Decimal(_), Decimal(rhs)) if rhs.is_zero() => {
+                    return Err(Box::new(RuntimeError::DivisionByZero {
+                        span: Some(span.clone()),
+                        stacktrace: vec![],
+                    }));
+                }
+                (Integer(lhs), Integer(rhs)) => Integer(lhs.mod_floor(&rhs)),
+                (Decimal(lhs), Decimal(rhs)) => checked_decimal(
+                    lhs.checked_rem(rhs).and_then(|rem| {
+                        if !rem.is_zero() && rem.is_sign_negative() != rhs.is_sign_negative() {
+                            rem.checked_add(rhs)
+                        } else {
+                            Some(rem)
+                        }
+                    }),
+                    span,
+                )?,
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -416,38 +1145,118 @@ impl Runtime {
                     }));
                 }
             },
+            BitwiseAnd | BitwiseOr | BitwiseXor => match (lhs, rhs) {
+                (Integer(lhs), Integer(rhs)) => Integer(match op {
+                    BitwiseAnd => lhs & rhs,
+                    BitwiseOr => lhs | rhs,
+                    _ => lhs ^ rhs,
+                }),
+                (Set(lhs), Set(rhs)) => {
+                    let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+
+                    let set = match op {
+                        BitwiseAnd => lhs.intersection(&rhs).cloned().collect(),
+                        BitwiseOr => lhs.union(&rhs).cloned().collect(),
+                        _ => lhs.symmetric_difference(&rhs).cloned().collect(),
+                    };
+
+                    Set(Rc::new(RefCell::new(set)))
+                }
+                (lhs, rhs) => {
+                    return Err(Box::new(RuntimeError::TypeMismatch {
+                        first: lhs.kind(),
+                        second: rhs.kind(),
+                        span: Some(span.clone()),
+                        message: Some(format!(
+                            "operações bit a bit só são definidas para inteiros e conjuntos: encontrado '{}' e '{}'",
+                            lhs, rhs
+                        )),
+                        stacktrace: vec![],
+                    }));
+                }
+            },
+            ShiftLeft | ShiftRight => match (lhs, rhs) {
+                (Integer(lhs), Integer(rhs)) => {
+                    let amount = match rhs.to_usize() {
+                        Some(amount) => amount,
+                        None => {
+                            return Err(Box::new(RuntimeError::InvalidShiftAmount {
+                                amount: rhs,
+                                span: Some(span.clone()),
+                                stacktrace: vec![],
+                            }));
+                        }
+                    };
+
+                    match op {
+                        ShiftLeft => Integer(lhs << amount),
+                        _ => Integer(lhs >> amount),
+                    }
+                }
+                (lhs, rhs) => {
+                    return Err(Box::new(RuntimeError::TypeMismatch {
+                        first: lhs.kind(),
+                        second: rhs.kind(),
+                        span: Some(span.clone()),
+                        message: Some(format!(
+                            "deslocamentos de bits só são definidos para inteiros: encontrado '{}' e '{}'",
+                            lhs, rhs
+                        )),
+                        stacktrace: vec![],
+                    }));
+                }
+            },
             Equality => match (lhs, rhs) {
-                (Number(lhs), Number(rhs)) => Boolean(lhs == rhs),
+                (
+                    lhs @ (Number(_) | Integer(_) | Decimal(_)),
+                    rhs @ (Number(_) | Integer(_) | Decimal(_)),
+                ) => Boolean(values_equal(&lhs, &rhs)),
                 (Boolean(lhs), Boolean(rhs)) => Boolean(lhs == rhs),
                 (String(lhs), String(rhs)) => Boolean(lhs == rhs),
-                (List(lhs), List(rhs)) => Boolean(lhs == rhs),
-                (Value::Range(lhs_start, lhs_end), Value::Range(rhs_start, rhs_end)) => {
-                    Boolean(lhs_start == rhs_start && lhs_end == rhs_end)
+                (lhs @ List(_), rhs @ List(_)) => Boolean(values_equal(&lhs, &rhs)),
+                (Value::Range(lhs), Value::Range(rhs)) => Boolean(lhs == rhs),
+                (lhs @ AssociativeArray(_), rhs @ AssociativeArray(_)) => {
+                    Boolean(values_equal(&lhs, &rhs))
                 }
-                (AssociativeArray(lhs), AssociativeArray(rhs)) => Boolean(lhs == rhs),
+                (Set(lhs), Set(rhs)) => Boolean(lhs == rhs),
+                (Enum(lhs), Enum(rhs)) => Boolean(lhs == rhs),
+                (EnumVariant(lhs), EnumVariant(rhs)) => Boolean(lhs == rhs),
                 (Nil, Nil) => Boolean(true),
                 (Function(lhs), Function(rhs)) => Boolean(lhs == rhs),
                 (Date(lhs), Date(rhs)) => Boolean(lhs == rhs),
+                (lhs @ Record(_), rhs @ Record(_)) => Boolean(values_equal(&lhs, &rhs)),
                 _ => Boolean(false),
             },
             Inequality => match (lhs, rhs) {
-                (Number(lhs), Number(rhs)) => Boolean(lhs != rhs),
+                (
+                    lhs @ (Number(_) | Integer(_) | Decimal(_)),
+                    rhs @ (Number(_) | Integer(_) | Decimal(_)),
+                ) => Boolean(!values_equal(&lhs, &rhs)),
                 (Boolean(lhs), Boolean(rhs)) => Boolean(lhs != rhs),
                 (String(lhs), String(rhs)) => Boolean(lhs != rhs),
-                (List(lhs), List(rhs)) => Boolean(lhs != rhs),
-                (Value::Range(lhs_start1, lhs_end1), Value::Range(rhs_start2, rhs_end2)) => {
-                    Boolean(lhs_start1 != rhs_start2 || lhs_end1 != rhs_end2)
+                (lhs @ List(_), rhs @ List(_)) => Boolean(!values_equal(&lhs, &rhs)),
+                (Value::Range(lhs), Value::Range(rhs)) => Boolean(lhs != rhs),
+                (lhs @ AssociativeArray(_), rhs @ AssociativeArray(_)) => {
+                    Boolean(!values_equal(&lhs, &rhs))
                 }
-                (AssociativeArray(lhs), AssociativeArray(rhs)) => Boolean(lhs != rhs),
+                (Set(lhs), Set(rhs)) => Boolean(lhs != rhs),
+                (Enum(lhs), Enum(rhs)) => Boolean(lhs != rhs),
+                (EnumVariant(lhs), EnumVariant(rhs)) => Boolean(lhs != rhs),
                 (Nil, Nil) => Boolean(false),
                 (Function(lhs), Function(rhs)) => Boolean(lhs != rhs),
                 (Date(lhs), Date(rhs)) => Boolean(lhs != rhs),
+                (lhs @ Record(_), rhs @ Record(_)) => Boolean(!values_equal(&lhs, &rhs)),
                 _ => Boolean(true),
             },
             Greater => match (lhs, rhs) {
                 (Number(lhs), Number(rhs)) => Boolean(lhs > rhs),
+                (Integer(lhs), Integer(rhs)) => Boolean(lhs > rhs),
+                (Decimal(lhs), Decimal(rhs)) => Boolean(lhs > rhs),
                 (String(lhs), String(rhs)) => Boolean(lhs > rhs),
                 (Date(lhs), Date(rhs)) => Boolean(lhs > rhs),
+                (EnumVariant(lhs), EnumVariant(rhs)) if lhs.belongs_to(&rhs.enumeration) => {
+                    Boolean(lhs.index > rhs.index)
+                }
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -463,8 +1272,13 @@ impl Runtime {
             },
             GreaterOrEqual => match (lhs, rhs) {
                 (Number(lhs), Number(rhs)) => Boolean(lhs >= rhs),
+                (Integer(lhs), Integer(rhs)) => Boolean(lhs >= rhs),
+                (Decimal(lhs), Decimal(rhs)) => Boolean(lhs >= rhs),
                 (String(lhs), String(rhs)) => Boolean(lhs >= rhs),
                 (Date(lhs), Date(rhs)) => Boolean(lhs >= rhs),
+                (EnumVariant(lhs), EnumVariant(rhs)) if lhs.belongs_to(&rhs.enumeration) => {
+                    Boolean(lhs.index >= rhs.index)
+                }
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -480,8 +1294,13 @@ impl Runtime {
             },
             Less => match (lhs, rhs) {
                 (Number(lhs), Number(rhs)) => Boolean(lhs < rhs),
+                (Integer(lhs), Integer(rhs)) => Boolean(lhs < rhs),
+                (Decimal(lhs), Decimal(rhs)) => Boolean(lhs < rhs),
                 (String(lhs), String(rhs)) => Boolean(lhs < rhs),
                 (Date(lhs), Date(rhs)) => Boolean(lhs < rhs),
+                (EnumVariant(lhs), EnumVariant(rhs)) if lhs.belongs_to(&rhs.enumeration) => {
+                    Boolean(lhs.index < rhs.index)
+                }
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -497,8 +1316,13 @@ impl Runtime {
             },
             LessOrEqual => match (lhs, rhs) {
                 (Number(lhs), Number(rhs)) => Boolean(lhs <= rhs),
+                (Integer(lhs), Integer(rhs)) => Boolean(lhs <= rhs),
+                (Decimal(lhs), Decimal(rhs)) => Boolean(lhs <= rhs),
                 (String(lhs), String(rhs)) => Boolean(lhs <= rhs),
                 (Date(lhs), Date(rhs)) => Boolean(lhs <= rhs),
+                (EnumVariant(lhs), EnumVariant(rhs)) if lhs.belongs_to(&rhs.enumeration) => {
+                    Boolean(lhs.index <= rhs.index)
+                }
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -513,37 +1337,63 @@ impl Runtime {
                     }));
                 }
             },
-            ast::BinaryOperator::Range => match (lhs, rhs) {
-                (Number(lhs), Number(_)) if lhs != lhs.trunc() || !lhs.is_finite() => {
-                    return Err(Box::new(RuntimeError::InvalidRangeBounds {
-                        bound: lhs,
-                        span: Some(span.clone()),
-                        stacktrace: vec![],
-                    }));
+            ast::BinaryOperator::Range | ExclusiveRange => match (lhs, rhs) {
+                (
+                    lhs @ (Number(_) | Integer(_) | Decimal(_)),
+                    rhs @ (Number(_) | Integer(_) | Decimal(_)),
+                ) => {
+                    let start = range_bound(&lhs, span)?;
+                    let end = range_bound(&rhs, span)?;
+
+                    match op {
+                        ExclusiveRange => Value::Range(range::Range::exclusive(start, end)),
+                        _ => Value::Range(range::Range::new(start, end)),
+                    }
                 }
-                (Number(_), Number(rhs)) if rhs != rhs.trunc() || !rhs.is_finite() => {
-                    return Err(Box::new(RuntimeError::InvalidRangeBounds {
-                        bound: rhs,
+                (lhs, rhs) => {
+                    return Err(Box::new(RuntimeError::TypeMismatch {
+                        first: lhs.kind(),
+                        second: rhs.kind(),
                         span: Some(span.clone()),
+                        message: Some(format!(
+                            "não é possível criar um intervalo entre '{}' e '{}'",
+                            lhs, rhs
+                        )),
                         stacktrace: vec![],
                     }));
                 }
-                (Number(lhs), Number(rhs)) => Value::Range(lhs as usize, rhs as usize),
+            },
+            RangeStep => match (lhs, rhs) {
+                (Value::Range(range), step) => match range_integer(&step) {
+                    Some(step) if step != 0 => Value::Range(range.with_step(step)),
+                    _ => {
+                        return Err(Box::new(RuntimeError::InvalidRangeStep {
+                            step,
+                            span: Some(span.clone()),
+                            stacktrace: vec![],
+                        }));
+                    }
+                },
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
                         second: rhs.kind(),
                         span: Some(span.clone()),
                         message: Some(format!(
-                            "não é possível criar um intervalo entre '{}' e '{}'",
-                            lhs, rhs
+                            "não é possível aplicar o passo '{}' a '{}'",
+                            rhs, lhs
                         )),
                         stacktrace: vec![],
                     }));
                 }
             },
             Has => match (lhs, rhs) {
-                (List(list), value) => Boolean(list.borrow().contains(&value)),
+                (List(list), value) => {
+                    Boolean(list.borrow().iter().any(|item| values_equal(item, &value)))
+                }
+                (Value::Range(range), value) => {
+                    Boolean(range_integer(&value).is_some_and(|value| range.contains(value)))
+                }
                 (AssociativeArray(associative_array), key) => {
                     let key = self.resolve_associative_array_key(key).map_err(|mut src| {
                         src.set_span(span);
@@ -552,6 +1402,10 @@ impl Runtime {
 
                     Boolean(associative_array.borrow().contains_key(&key))
                 }
+                (Set(set), value) => Boolean(
+                    AssociativeArrayKey::try_from(value)
+                        .is_ok_and(|key| set.borrow().contains(&key)),
+                ),
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -566,7 +1420,12 @@ impl Runtime {
                 }
             },
             Lacks => match (lhs, rhs) {
-                (List(list), value) => Boolean(!list.borrow().contains(&value)),
+                (List(list), value) => {
+                    Boolean(!list.borrow().iter().any(|item| values_equal(item, &value)))
+                }
+                (Value::Range(range), value) => {
+                    Boolean(!range_integer(&value).is_some_and(|value| range.contains(value)))
+                }
                 (AssociativeArray(associative_array), key) => {
                     let key = self.resolve_associative_array_key(key).map_err(|mut src| {
                         src.set_span(span);
@@ -575,6 +1434,10 @@ impl Runtime {
 
                     Boolean(!associative_array.borrow().contains_key(&key))
                 }
+                (Set(set), value) => Boolean(
+                    !AssociativeArrayKey::try_from(value)
+                        .is_ok_and(|key| set.borrow().contains(&key)),
+                ),
                 (lhs, rhs) => {
                     return Err(Box::new(RuntimeError::TypeMismatch {
                         first: lhs.kind(),
@@ -590,6 +1453,7 @@ impl Runtime {
             },
             LogicalAnd => unreachable!(),
             LogicalOr => unreachable!(),
+            NilCoalescing => unreachable!(),
         };
 
         Ok(value)
@@ -606,6 +1470,8 @@ impl Runtime {
         let expr = match op {
             Negative => match rhs {
                 Number(rhs) => Number(-rhs),
+                Integer(rhs) => Integer(-rhs),
+                Decimal(rhs) => Decimal(-rhs),
                 _ => {
                     return Err(Box::new(RuntimeError::UnexpectedTypeError {
                         expected: ValueType::Number,
@@ -621,11 +1487,27 @@ impl Runtime {
                 }
             },
             LogicalNot => Value::Boolean(!rhs.to_bool()),
-        };
-
-        Ok(expr)
-    }
-
+            BitwiseNot => match rhs {
+                Integer(rhs) => Integer(!rhs),
+                _ => {
+                    return Err(Box::new(RuntimeError::UnexpectedTypeError {
+                        expected: ValueType::Integer,
+                        found: rhs.kind(),
+                        span: Some(span.clone()),
+                        message: Some(format!(
+                            "não é possível inverter os bits de valor de tipo '{}'; esperado '{}'",
+                            rhs.kind(),
+                            ValueType::Integer
+                        )),
+                        stacktrace: vec![],
+                    }));
+                }
+            },
+        };
+
+        Ok(expr)
+    }
+
     fn visit_ternary(&mut self, ternary: &ast::TernaryOp) -> Result<Value> {
         let ast::TernaryOp {
             cond,
@@ -645,7 +1527,65 @@ impl Runtime {
     }
 
     fn visit_call(&mut self, call: &ast::Call) -> Result<Value> {
-        let ast::Call { callee, args, span } = call;
+        let span = &call.span;
+        let (func, args) = self.resolve_call(call)?;
+
+        self.invoke_function(func, args, Some(span.clone()))
+            .map_err(|mut err| attach_span_if_missing!(err, span))
+    }
+
+    /// Evaluates an expression in tail position. A call to a user function is
+    /// not made here but left in `tail_call`, so that `invoke_function` runs it
+    /// in place of the current function instead of nesting on the Rust stack.
+    fn visit_tail_expr(&mut self, expr: &ast::Expr) -> Result<Value> {
+        if !self.allows_tail_call {
+            return self.visit_expr(expr);
+        }
+
+        match expr {
+            ast::Expr::Call(call) => {
+                let (func, args) = self.resolve_call(call)?;
+
+                if !func.is_tail_callable() {
+                    return self
+                        .invoke_function(func, args, Some(call.span.clone()))
+                        .map_err(|mut err| attach_span_if_missing!(err, &call.span));
+                }
+
+                self.tail_call = Some(TailCall {
+                    func,
+                    args,
+                    span: call.span.clone(),
+                });
+
+                Ok(Value::Nil)
+            }
+            ast::Expr::Grouping(ast::Grouping { expr, .. }) => self.visit_tail_expr(expr),
+            ast::Expr::Ternary(ast::TernaryOp {
+                cond,
+                then,
+                or_else,
+                span,
+            }) => if self.visit_expr(cond)?.to_bool() {
+                self.visit_tail_expr(then)
+            } else {
+                self.visit_tail_expr(or_else)
+            }
+            .map_err(|mut err| attach_span_if_missing!(err, span)),
+            ast::Expr::Match(match_expr) => self.visit_match(match_expr, true),
+            expr => self.visit_expr(expr),
+        }
+    }
+
+    /// Evaluates the callee and the arguments of a call, matching the
+    /// arguments to the callee's parameters.
+    fn resolve_call(&mut self, call: &ast::Call) -> Result<(Function, Vec<Option<Value>>)> {
+        let ast::Call {
+            callee,
+            args,
+            named_args,
+            span,
+        } = call;
 
         let callee = self.visit_expr(callee)?;
 
@@ -654,18 +1594,17 @@ impl Runtime {
             .map(|arg| self.visit_expr(arg))
             .collect::<Result<Vec<_>>>()?;
 
+        let named_args = named_args
+            .iter()
+            .map(|arg| Ok((arg, self.visit_expr(&arg.value)?)))
+            .collect::<Result<Vec<_>>>()?;
+
         match callee {
-            Value::Function(func) if args.len() != func.get_params().len() => {
-                Err(Box::new(RuntimeError::WrongNumberOfArguments {
-                    expected: func.get_params().len(),
-                    found: args.len(),
-                    span: Some(span.clone()),
-                    stacktrace: vec![],
-                }))
+            Value::Function(func) => {
+                let args = resolve_arguments(&func, args, named_args, span)?;
+
+                Ok((func, args))
             }
-            Value::Function(func) => self
-                .call_function(func, args, Some(span.clone()))
-                .map_err(|mut err| attach_span_if_missing!(err, span)),
             _ => Err(Box::new(RuntimeError::UnexpectedTypeError {
                 expected: ValueType::Function,
                 found: callee.kind(),
@@ -683,17 +1622,56 @@ impl Runtime {
         let ast::Access {
             subscripted,
             index,
+            optional,
             span,
         } = index;
 
         let subscripted = self.visit_expr(subscripted)?;
 
+        if *optional {
+            match &subscripted {
+                Value::Nil => return Ok(Value::Nil),
+                Value::List(list) => {
+                    let index = self.resolve_index(index)?;
+
+                    return Ok(list.borrow().get(index).cloned().unwrap_or(Value::Nil));
+                }
+                Value::String(string) => {
+                    let index = self.resolve_index(index)?;
+
+                    return Ok(string
+                        .chars()
+                        .nth(index)
+                        .map(|char| Value::String(char.to_string()))
+                        .unwrap_or(Value::Nil));
+                }
+                Value::AssociativeArray(associative_array) => {
+                    let key = self.resolve_associative_array_index(index)?;
+
+                    return Ok(associative_array
+                        .borrow()
+                        .get(&key)
+                        .cloned()
+                        .unwrap_or(Value::Nil));
+                }
+                Value::Module(module) => {
+                    let name = self.resolve_module_member(module, index)?;
+
+                    return Ok(module.get(&name).unwrap_or(Value::Nil));
+                }
+                _ => {}
+            }
+        }
+
         match subscripted {
             Value::List(list) => self.visit_list_access(&list.borrow(), index),
             Value::String(string) => self.visit_string_access(&string, index),
             Value::AssociativeArray(associative_array) => {
                 self.visit_associative_array_access(associative_array.borrow().clone(), index)
             }
+            Value::Record(record) => self.visit_record_access(record, index),
+            Value::Enum(enumeration) => self.visit_enum_access(&enumeration, index),
+            Value::Module(module) => self.visit_module_access(&module, index),
             value => Err(Box::new(RuntimeError::WrongIndexType {
                 value: value.kind(),
                 span: Some(span.clone()),
@@ -719,6 +1697,375 @@ impl Runtime {
         self.visit_expr(expr)
     }
 
+    fn visit_interpolation(&mut self, interpolation: &ast::Interpolation) -> Result<Value> {
+        let ast::Interpolation { parts, .. } = interpolation;
+
+        let mut text = String::new();
+
+        for part in parts {
+            let value = self.visit_expr(part)?;
+
+            text.push_str(&display_value(&value));
+        }
+
+        Ok(Value::String(text))
+    }
+
+    /// Evaluates an `escolha`. In tail position, the value of the chosen arm is
+    /// itself in tail position.
+    fn visit_match(&mut self, match_expr: &ast::Match, is_tail: bool) -> Result<Value> {
+        let ast::Match {
+            value,
+            arms,
+            or_else,
+            span,
+        } = match_expr;
+
+        let value = self.visit_expr(value)?;
+
+        for arm in arms {
+            let mut bindings = vec![];
+
+            if !self.match_pattern(&arm.pattern, &value, &mut bindings)? {
+                continue;
+            }
+
+            let mut frame = Frame::new();
+
+            for (binding, bound_value) in bindings {
+                frame
+                    .get_env_mut()
+                    .set(binding.name.clone(), binding_cell(binding, bound_value));
+            }
+
+            self.stack.push(frame);
+
+            if let Some(guard) = &arm.guard {
+                if !self.visit_expr(guard)?.to_bool() {
+                    self.stack.pop();
+                    continue;
+                }
+            }
+
+            let result = self.interpret_match_body(&arm.body, is_tail)?;
+
+            self.stack.pop();
+
+            return Ok(result);
+        }
+
+        match or_else {
+            Some(or_else) => {
+                self.stack.push(Frame::new());
+
+                let result = self.interpret_match_body(or_else, is_tail)?;
+
+                self.stack.pop();
+
+                Ok(result)
+            }
+            None => Err(Box::new(RuntimeError::NoMatchingCase {
+                value: value.to_string(),
+                span: Some(span.clone()),
+                help: Some(
+                    "adicione um `senão` ao `escolha` para tratar os demais valores".to_string(),
+                ),
+                stacktrace: vec![],
+            })),
+        }
+    }
+
+    fn interpret_match_body(&mut self, body: &ast::Stmt, is_tail: bool) -> Result<Value> {
+        match body {
+            ast::Stmt::Expr(expr) if is_tail => self.visit_tail_expr(expr),
+            ast::Stmt::Block(ast::Block { inner, .. }) if is_tail => match inner.inner.split_last()
+            {
+                Some((ast::Stmt::Expr(last), init)) => {
+                    for stmt in init {
+                        self.interpret_stmt(stmt)?;
+
+                        if self.stack.has_return_value() || self.stack.has_loop_jump() {
+                            return Ok(Value::Nil);
+                        }
+                    }
+
+                    self.visit_tail_expr(last)
+                }
+                _ => self.interpret_ast(inner),
+            },
+            ast::Stmt::Block(ast::Block { inner, .. }) => self.interpret_ast(inner),
+            stmt => self.interpret_stmt(stmt),
+        }
+    }
+
+    fn match_pattern<'p>(
+        &mut self,
+        pattern: &'p ast::Pattern,
+        value: &Value,
+        bindings: &mut Vec<(&'p ast::PatternBinding, Value)>,
+    ) -> Result<bool> {
+        use ast::Pattern;
+
+        let is_match = match (pattern, value) {
+            (Pattern::Wildcard(_), _) => true,
+            (Pattern::Binding(binding), value) => {
+                bindings.push((binding, value.clone()));
+                true
+            }
+            (Pattern::Literal(literal), value) => {
+                values_equal(value, &literal.value.clone().into())
+            }
+            (Pattern::Range(range), Value::Number(number)) => {
+                range.start <= *number && *number <= range.end
+            }
+            (Pattern::Range(range), Value::Integer(number)) => {
+                let number = integer_to_f64(number);
+
+                range.start <= number && number <= range.end
+            }
+            (Pattern::Range(range), Value::Decimal(number)) => {
+                let number = decimal_to_f64(number);
+
+                range.start <= number && number <= range.end
+            }
+            (Pattern::List(list), Value::List(values)) => {
+                let values = values.borrow();
+
+                let is_length_match = match list.rest {
+                    Some(_) => values.len() >= list.elements.len(),
+                    None => values.len() == list.elements.len(),
+                };
+
+                if !is_length_match {
+                    return Ok(false);
+                }
+
+                for (element, value) in list.elements.iter().zip(values.iter()) {
+                    if !self.match_pattern(element, value, bindings)? {
+                        return Ok(false);
+                    }
+                }
+
+                if let Some(rest) = &list.rest {
+                    let rest_values = values[list.elements.len()..].to_vec();
+                    let rest_values = Value::List(Rc::new(RefCell::new(rest_values)));
+
+                    self.match_pattern(rest, &rest_values, bindings)?;
+                }
+
+                true
+            }
+            (Pattern::AssociativeArray(pattern), Value::Record(record)) => {
+                let record = record.borrow();
+
+                for (key, element) in &pattern.elements {
+                    let field = match &key.value {
+                        tenda_scanner::Literal::String(field) => field,
+                        _ => return Ok(false),
+                    };
+
+                    match record.get(field) {
+                        Some(value) if self.match_pattern(element, value, bindings)? => {}
+                        _ => return Ok(false),
+                    }
+                }
+
+                true
+            }
+            (Pattern::AssociativeArray(pattern), Value::AssociativeArray(associative_array)) => {
+                let associative_array = associative_array.borrow();
+
+                for (key, element) in &pattern.elements {
+                    let key = self
+                        .resolve_associative_array_key(key.value.clone().into())
+                        .map_err(|mut err| attach_span_if_missing!(err, &key.span))?;
+
+                    match associative_array.get(&key) {
+                        Some(value) if self.match_pattern(element, value, bindings)? => {}
+                        _ => return Ok(false),
+                    }
+                }
+
+                true
+            }
+            (Pattern::Record(pattern), Value::Record(record)) => {
+                let record = record.borrow();
+
+                if record.name != pattern.name {
+                    return Ok(false);
+                }
+
+                for (field, element) in &pattern.fields {
+                    let value = match record.get(field) {
+                        Some(value) => value,
+                        None => {
+                            return Err(unknown_record_field(&record, field.clone(), &pattern.span))
+                        }
+                    };
+
+                    if !self.match_pattern(element, value, bindings)? {
+                        return Ok(false);
+                    }
+                }
+
+                true
+            }
+            (Pattern::EnumVariant(pattern), Value::EnumVariant(variant)) => {
+                let enumeration = &variant.enumeration;
+
+                if enumeration.name != pattern.enum_name {
+                    return Ok(false);
+                }
+
+                if !enumeration.variants.contains(&pattern.variant) {
+                    return Err(unknown_enum_variant(
+                        enumeration,
+                        pattern.variant.clone(),
+                        &pattern.span,
+                    ));
+                }
+
+                variant.name() == pattern.variant
+            }
+            _ => false,
+        };
+
+        Ok(is_match)
+    }
+
+    fn destructure<'p>(
+        &mut self,
+        pattern: &'p ast::Pattern,
+        value: Value,
+        bindings: &mut Vec<(&'p ast::PatternBinding, Value)>,
+    ) -> Result<()> {
+        use ast::Pattern;
+
+        match (pattern, value) {
+            (Pattern::Wildcard(_), _) => Ok(()),
+            (Pattern::Binding(binding), value) => {
+                bindings.push((binding, value));
+                Ok(())
+            }
+            (Pattern::List(list), Value::List(values)) => {
+                let values = values.borrow().clone();
+
+                let (expected, is_length_match) = match list.rest {
+                    Some(_) => (
+                        format!("pelo menos {}", list.elements.len()),
+                        values.len() >= list.elements.len(),
+                    ),
+                    None => (
+                        list.elements.len().to_string(),
+                        values.len() == list.elements.len(),
+                    ),
+                };
+
+                if !is_length_match {
+                    let help = match values.len() > list.elements.len() {
+                        true => Some(
+                            "use `...resto` no fim do padrão para guardar os demais elementos"
+                                .to_string(),
+                        ),
+                        false => None,
+                    };
+
+                    return Err(Box::new(RuntimeError::DestructuringLengthMismatch {
+                        expected,
+                        found: values.len(),
+                        span: Some(list.span.clone()),
+                        help,
+                        stacktrace: vec![],
+                    }));
+                }
+
+                let (values, rest_values) = values.split_at(list.elements.len());
+
+                for (element, value) in list.elements.iter().zip(values.iter()) {
+                    self.destructure(element, value.clone(), bindings)?;
+                }
+
+                if let Some(rest) = &list.rest {
+                    let rest_values = Value::List(Rc::new(RefCell::new(rest_values.to_vec())));
+
+                    self.destructure(rest, rest_values, bindings)?;
+                }
+
+                Ok(())
+            }
+            (Pattern::AssociativeArray(pattern), Value::AssociativeArray(associative_array)) => {
+                let associative_array = associative_array.borrow().clone();
+
+                for (key, element) in &pattern.elements {
+                    let key_value = self
+                        .resolve_associative_array_key(key.value.clone().into())
+                        .map_err(|mut err| attach_span_if_missing!(err, &key.span))?;
+
+                    match associative_array.get(&key_value) {
+                        Some(value) => self.destructure(element, value.clone(), bindings)?,
+                        None => {
+                            return Err(Box::new(RuntimeError::AssociativeArrayKeyNotFound {
+                                key: key_value,
+                                span: Some(key.span.clone()),
+                                stacktrace: vec![],
+                            }))
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            (Pattern::AssociativeArray(pattern), Value::Record(record)) => {
+                let record = record.borrow().clone();
+
+                for (key, element) in &pattern.elements {
+                    let field = match &key.value {
+                        tenda_scanner::Literal::String(field) => field.clone(),
+                        _ => Value::from(key.value.clone()).to_string(),
+                    };
+
+                    match record.get(&field) {
+                        Some(value) => self.destructure(element, value.clone(), bindings)?,
+                        None => return Err(unknown_record_field(&record, field, &key.span)),
+                    }
+                }
+
+                Ok(())
+            }
+            (Pattern::List(list), value) => Err(Box::new(RuntimeError::UnexpectedTypeError {
+                expected: ValueType::List,
+                found: value.kind(),
+                span: Some(list.span.clone()),
+                message: Some(format!(
+                    "não é possível desestruturar um valor do tipo '{}' como lista",
+                    value.kind()
+                )),
+                stacktrace: vec![],
+            })),
+            (Pattern::AssociativeArray(pattern), value) => {
+                Err(Box::new(RuntimeError::UnexpectedTypeError {
+                    expected: ValueType::AssociativeArray,
+                    found: value.kind(),
+                    span: Some(pattern.span.clone()),
+                    message: Some(format!(
+                        "não é possível desestruturar um valor do tipo '{}' como dicionário",
+                        value.kind()
+                    )),
+                    stacktrace: vec![],
+                }))
+            }
+            (
+                Pattern::Literal(_)
+                | Pattern::Range(_)
+                | Pattern::Record(_)
+                | Pattern::EnumVariant(_),
+                _,
+            ) => {
+                unreachable!("refutable patterns are rejected by the parser")
+            }
+        }
+    }
+
     fn visit_literal(&mut self, literal: &ast::Literal) -> Result<Value> {
         let ast::Literal { value, .. } = literal;
 
@@ -744,50 +2091,41 @@ impl Runtime {
     fn visit_assign(&mut self, assign: &ast::Assign) -> Result<Value> {
         let ast::Assign {
             name: variable,
+            op,
             value,
             span,
         } = assign;
 
         match &**variable {
             ast::Expr::Variable(ast::Variable { name, .. }) => {
-                let value = self.visit_expr(value)?;
+                let value = match op {
+                    Some(op) => {
+                        let current = self.visit_expr(variable)?;
 
-                let result = self
-                    .stack
-                    .assign(name.clone(), ValueCell::new(value.clone()));
+                        self.visit_compound_value(op, current, value, span)?
+                    }
+                    None => self.visit_expr(value)?,
+                };
 
-                match result {
-                    Ok(_) => Ok(value),
-                    Err(err) => match err {
-                        StackError::AssignToUndefined(name) => {
-                            Err(Box::new(RuntimeError::UndefinedReference {
-                                var_name: name.clone(),
-                                span: Some(span.clone()),
-                                help: Some(format!(
-                                    "talvez você queria definir a variável '{}': `seja {} = ...`",
-                                    name, name
-                                )),
-                                stacktrace: vec![],
-                            }))
-                        }
-                        _ => unreachable!(),
-                    },
-                }
+                self.assign_variable(name, value.clone(), span)?;
+
+                Ok(value)
             }
             ast::Expr::Access(ast::Access {
                 index,
                 subscripted,
                 span: lvalue_span,
+                ..
             }) => {
                 let subscripted = self.visit_expr(subscripted)?;
 
                 match subscripted {
-                    Value::List(list) => {
-                        self.visit_list_assign(list, index, value)
-                    }
+                    Value::List(list) => self.visit_list_assign(list, index, assign),
                     Value::AssociativeArray(associative_array) => {
-                        self.visit_associative_array_assign(associative_array, index, value)
+                        self.visit_associative_array_assign(associative_array, index, assign)
                     }
+                    Value::Record(record) => self.visit_record_assign(record, index, assign),
+                    Value::Module(module) => self.visit_module_assign(&module, index, assign),
                     Value::String(_) => Err(Box::new(RuntimeError::ImmutableString {
                         span: Some(span.clone()),
                         help: Some(
@@ -812,6 +2150,59 @@ impl Runtime {
         }
     }
 
+    fn visit_destructuring_assign(
+        &mut self,
+        destructuring: &ast::DestructuringAssign,
+    ) -> Result<Value> {
+        let ast::DestructuringAssign {
+            pattern,
+            value,
+            span,
+        } = destructuring;
+
+        let value = self.visit_expr(value)?;
+        let mut bindings = vec![];
+
+        self.destructure(pattern, value.clone(), &mut bindings)?;
+
+        for (binding, bound_value) in bindings {
+            self.assign_variable(&binding.name, bound_value, span)?;
+        }
+
+        Ok(value)
+    }
+
+    fn assign_variable(&mut self, name: &str, value: Value, span: &SourceSpan) -> Result<()> {
+        match self.stack.assign(name.to_string(), ValueCell::new(value)) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                StackError::AssignToUndefined(name) => {
+                    Err(Box::new(RuntimeError::UndefinedReference {
+                        var_name: name.clone(),
+                        span: Some(span.clone()),
+                        help: Some(format!(
+                            "talvez você queria definir a variável '{}': `seja {} = ...`",
+                            name, name
+                        )),
+                        stacktrace: vec![],
+                    }))
+                }
+                StackError::AssignToConstant(name) => {
+                    Err(Box::new(RuntimeError::ConstantReassignment {
+                        var_name: name.clone(),
+                        span: Some(span.clone()),
+                        help: Some(
+                            "declare uma nova variável com outro nome para guardar o novo valor"
+                                .to_string(),
+                        ),
+                        stacktrace: vec![],
+                    }))
+                }
+                _ => unreachable!(),
+            },
+        }
+    }
+
     fn visit_associative_array(
         &mut self,
         associative_array: &ast::AssociativeArray,
@@ -837,37 +2228,93 @@ impl Runtime {
         Ok(Value::AssociativeArray(Rc::new(RefCell::new(map))))
     }
 
-    fn visit_anonymous_function(
-        &mut self,
-        anonymous_function: &ast::AnonymousFunction,
-    ) -> Result<Value> {
-        let ast::AnonymousFunction {
-            params, body, span, ..
-        } = anonymous_function;
+    fn visit_comprehension(&mut self, comprehension: &ast::Comprehension) -> Result<Value> {
+        let ast::Comprehension {
+            element,
+            item,
+            iterable,
+            span,
+            ..
+        } = comprehension;
 
-        let metadata = FunctionRuntimeMetadata::new(Some(span.clone()), None);
-        let func = self.create_function(params, body.clone(), Some(metadata));
+        let result = match element {
+            ast::ComprehensionElement::List(_) => Value::List(Rc::default()),
+            ast::ComprehensionElement::AssociativeArray(_, _) => {
+                Value::AssociativeArray(Rc::default())
+            }
+        };
 
-        Ok(Value::Function(func))
-    }
-}
+        let iterable = self.visit_expr(iterable)?;
+        let mut iteration = self.iterate(iterable, span)?;
 
-impl Runtime {
-    fn visit_list_access(&mut self, list: &[Value], index: &ast::Expr) -> Result<Value> {
-        let span = index.get_span();
-        let index = self.resolve_index(index)?;
+        while let Some(entry) = self.next_item(&mut iteration)? {
+            let frame = self.bind_loop_item(item, entry, iteration.binds_keys())?;
 
-        if index >= list.len() {
-            return Err(Box::new(RuntimeError::IndexOutOfBounds {
-                index,
-                len: list.len(),
-                span: Some(span.clone()),
-                help: vec!["verifique se o índice está dentro dos limites da lista antes de tentar acessá-lo".to_string()],
-                stacktrace: vec![],
-            }));
+            self.stack.push(frame);
+            let collected = self.collect_comprehension_element(comprehension, &result);
+            self.stack.pop();
+
+            collected?;
+        }
+
+        Ok(result)
+    }
+
+    /// Evaluates the element of a comprehension for the current loop variables
+    /// and adds it to `result`, unless the condition rejects it.
+    fn collect_comprehension_element(
+        &mut self,
+        comprehension: &ast::Comprehension,
+        result: &Value,
+    ) -> Result<()> {
+        if let Some(condition) = &comprehension.condition {
+            if !self.visit_expr(condition)?.to_bool() {
+                return Ok(());
+            }
+        }
+
+        match (&comprehension.element, result) {
+            (ast::ComprehensionElement::List(value), Value::List(list)) => {
+                let value = self.visit_expr(value)?;
+
+                list.borrow_mut().push(value);
+            }
+            (
+                ast::ComprehensionElement::AssociativeArray(key, value),
+                Value::AssociativeArray(associative_array),
+            ) => {
+                let key = self.resolve_associative_array_index(key)?;
+                let value = self.visit_expr(value)?;
+
+                associative_array.borrow_mut().insert(key, value);
+            }
+            _ => unreachable!(),
         }
 
-        Ok(list[index].clone())
+        Ok(())
+    }
+
+    fn visit_anonymous_function(
+        &mut self,
+        anonymous_function: &ast::AnonymousFunction,
+    ) -> Result<Value> {
+        let ast::AnonymousFunction {
+            params, body, span, ..
+        } = anonymous_function;
+
+        let metadata = FunctionRuntimeMetadata::new(Some(span.clone()), None);
+        let func = self.create_function(params, body.clone(), Some(metadata));
+
+        Ok(Value::Function(func))
+    }
+}
+
+impl Runtime {
+    fn visit_list_access(&mut self, list: &[Value], index: &ast::Expr) -> Result<Value> {
+        let span = index.get_span();
+        let index = self.resolve_index(index)?;
+
+        list_element(list, index, span)
     }
 
     fn visit_string_access(&mut self, string: &str, index: &ast::Expr) -> Result<Value> {
@@ -895,20 +2342,96 @@ impl Runtime {
         associative_array: AssociativeArray,
         index: &ast::Expr,
     ) -> Result<Value> {
+        let span = index.get_span();
+        let index = self.resolve_associative_array_index(index)?;
+
+        associative_array_entry(&associative_array, &index, span)
+    }
+
+    fn resolve_associative_array_index(
+        &mut self,
+        index: &ast::Expr,
+    ) -> Result<AssociativeArrayKey> {
         let span = index.get_span();
         let index = self.visit_expr(index)?;
-        let index = self
-            .resolve_associative_array_key(index)
+
+        self.resolve_associative_array_key(index)
             .map_err(|mut source| {
                 source.set_span(span);
                 source
-            })?;
+            })
+    }
 
-        match associative_array.get(&index) {
-            Some(value) => Ok(value.clone()),
-            None => Err(Box::new(RuntimeError::AssociativeArrayKeyNotFound {
-                key: index,
-                span: Some(span.clone()),
+    fn visit_record_access(
+        &mut self,
+        record: Rc<RefCell<Record>>,
+        index: &ast::Expr,
+    ) -> Result<Value> {
+        let member = self.resolve_record_field(&record, index, true)?;
+
+        let record_ref = record.borrow();
+
+        if let Some(value) = record_ref.get(&member) {
+            return Ok(value.clone());
+        }
+
+        let mut method = record_ref.get_method(&member).unwrap().clone();
+
+        drop(record_ref);
+
+        method.get_env_mut().set(
+            ast::Variable::THIS.to_string(),
+            ValueCell::new_shared(Value::Record(record)),
+        );
+
+        Ok(Value::Function(method))
+    }
+
+    fn visit_enum_access(&mut self, enumeration: &Rc<Enum>, index: &ast::Expr) -> Result<Value> {
+        let span = index.get_span();
+
+        let variant = match self.visit_expr(index)? {
+            Value::String(variant) => variant,
+            value => {
+                return Err(Box::new(RuntimeError::UnexpectedTypeError {
+                    expected: ValueType::String,
+                    found: value.kind(),
+                    message: Some(format!(
+                        "variantes da enumeração '{}' são acessadas pelo nome",
+                        enumeration.name
+                    )),
+                    span: Some(span.clone()),
+                    stacktrace: vec![],
+                }))
+            }
+        };
+
+        match enumeration.variant(&variant) {
+            Some(variant) => Ok(Value::EnumVariant(variant)),
+            None => Err(unknown_enum_variant(enumeration, variant, span)),
+        }
+    }
+
+    fn visit_module_access(&mut self, module: &Rc<Module>, index: &ast::Expr) -> Result<Value> {
+        let name = self.resolve_module_member(module, index)?;
+
+        module
+            .get(&name)
+            .ok_or_else(|| unknown_module_member(module, name, index.get_span()))
+    }
+
+    /// Evaluates the name of a member of a module namespace.
+    fn resolve_module_member(&mut self, module: &Module, index: &ast::Expr) -> Result<String> {
+        match self.visit_expr(index)? {
+            Value::String(name) => Ok(name),
+            value => Err(Box::new(RuntimeError::UnexpectedTypeError {
+                expected: ValueType::String,
+                found: value.kind(),
+                message: Some(format!(
+                    "nomes do módulo '{}' são acessados pelo nome",
+                    module.path
+                )),
+                span: Some(index.get_span().clone()),
                 stacktrace: vec![],
             })),
         }
@@ -918,12 +2441,26 @@ impl Runtime {
         &mut self,
         list: Rc<RefCell<Vec<Value>>>,
         index: &ast::Expr,
-        value: &ast::Expr,
+        assign: &ast::Assign,
     ) -> Result<Value> {
         let index_span = index.get_span();
 
-        let value = self.visit_expr(value)?;
-        let index = self.resolve_index(index)?;
+        let (index, value) = match &assign.op {
+            Some(op) => {
+                let index = self.resolve_index(index)?;
+                let current = list_element(&list.borrow(), index, index_span)?;
+
+                (
+                    index,
+                    self.visit_compound_value(op, current, &assign.value, &assign.span)?,
+                )
+            }
+            None => {
+                let value = self.visit_expr(&assign.value)?;
+
+                (self.resolve_index(index)?, value)
+            }
+        };
 
         let mut list = list.borrow_mut();
 
@@ -945,22 +2482,96 @@ impl Runtime {
         Ok(value)
     }
 
+    fn visit_record_assign(
+        &mut self,
+        record: Rc<RefCell<Record>>,
+        index: &ast::Expr,
+        assign: &ast::Assign,
+    ) -> Result<Value> {
+        let (field, value) = match &assign.op {
+            Some(op) => {
+                let field = self.resolve_record_field(&record, index, false)?;
+                let current = record.borrow().get(&field).unwrap().clone();
+
+                (
+                    field,
+                    self.visit_compound_value(op, current, &assign.value, &assign.span)?,
+                )
+            }
+            None => {
+                let value = self.visit_expr(&assign.value)?;
+
+                (self.resolve_record_field(&record, index, false)?, value)
+            }
+        };
+
+        *record.borrow_mut().get_mut(&field).unwrap() = value.clone();
+
+        Ok(value)
+    }
+
+    /// Assigns to a global of a module through its namespace, so the module's
+    /// own functions see the new value.
+    fn visit_module_assign(
+        &mut self,
+        module: &Rc<Module>,
+        index: &ast::Expr,
+        assign: &ast::Assign,
+    ) -> Result<Value> {
+        let span = index.get_span();
+        let name = self.resolve_module_member(module, index)?;
+
+        let value = match &assign.op {
+            Some(op) => {
+                let current = module
+                    .get(&name)
+                    .ok_or_else(|| unknown_module_member(module, name.clone(), span))?;
+
+                self.visit_compound_value(op, current, &assign.value, &assign.span)?
+            }
+            None => self.visit_expr(&assign.value)?,
+        };
+
+        match module.set(&name, value.clone()) {
+            Ok(_) => Ok(value),
+            Err(StackError::AssignToConstant(var_name)) => {
+                Err(Box::new(RuntimeError::ConstantReassignment {
+                    var_name,
+                    span: Some(assign.span.clone()),
+                    help: Some(
+                        "declare uma nova variável com outro nome para guardar o novo valor"
+                            .to_string(),
+                    ),
+                    stacktrace: vec![],
+                }))
+            }
+            Err(_) => Err(unknown_module_member(module, name, span)),
+        }
+    }
+
     fn visit_associative_array_assign(
         &mut self,
         associative_array: Rc<RefCell<indexmap::IndexMap<AssociativeArrayKey, Value>>>,
         index: &ast::Expr,
-        value: &ast::Expr,
+        assign: &ast::Assign,
     ) -> Result<Value> {
-        let span = index.get_span();
+        let (index, value) = match &assign.op {
+            Some(op) => {
+                let span = index.get_span();
+                let index = self.resolve_associative_array_index(index)?;
+                let current = associative_array_entry(&associative_array.borrow(), &index, span)?;
+
+                (
+                    index,
+                    self.visit_compound_value(op, current, &assign.value, &assign.span)?,
+                )
+            }
+            None => {
+                let value = self.visit_expr(&assign.value)?;
 
-        let value = self.visit_expr(value)?;
-        let index = self.visit_expr(index)?;
-        let index = self
-            .resolve_associative_array_key(index)
-            .map_err(|mut source| {
-                source.set_span(span);
-                source
-            })?;
+                (self.resolve_associative_array_index(index)?, value)
+            }
+        };
 
         let mut associative_array = associative_array.borrow_mut();
 
@@ -968,6 +2579,20 @@ impl Runtime {
 
         Ok(value)
     }
+
+    /// Evaluates the right-hand side of a compound assignment and combines it
+    /// with the target's current value.
+    fn visit_compound_value(
+        &mut self,
+        op: &ast::BinaryOperator,
+        current: Value,
+        value: &ast::Expr,
+        span: &SourceSpan,
+    ) -> Result<Value> {
+        let value = self.visit_expr(value)?;
+
+        self.apply_binary_op(op, current, value, span)
+    }
 }
 
 impl Runtime {
@@ -977,33 +2602,124 @@ impl Runtime {
         args: Vec<Value>,
         span: Option<SourceSpan>,
     ) -> Result<Value> {
-        let args = func
+        let args = positional_slots(&func.get_params(), args);
+
+        self.invoke_function(func, args, span)
+    }
+
+    /// Calls a function with one slot per parameter, then any function it
+    /// calls in tail position, reusing this Rust frame for each of them. The
+    /// frames of the calls in between are not kept in an error's stack trace,
+    /// only the one that started the chain.
+    fn invoke_function(
+        &mut self,
+        mut func: Function,
+        mut args: Vec<Option<Value>>,
+        span: Option<SourceSpan>,
+    ) -> Result<Value> {
+        // The entry frame keeps the span of the original call site; tail calls
+        // only move `call_span`, so a failure after them still points here.
+        let entry = StackFrame::new(
+            func.metadata
+                .as_ref()
+                .and_then(|m| m.get_name().clone())
+                .map(FunctionName::Named)
+                .unwrap_or(FunctionName::TopLevel),
+            span.clone(),
+        );
+
+        let mut call_span = span;
+        let mut is_tail_call = false;
+
+        loop {
+            let result = self.run_function(func, args, call_span);
+
+            match (self.tail_call.take(), result) {
+                (Some(tail_call), Ok(_)) => {
+                    func = tail_call.func;
+                    args = tail_call.args;
+                    call_span = Some(tail_call.span);
+                    is_tail_call = true;
+                }
+                (_, Err(mut err)) if is_tail_call => {
+                    if let Some(stacktrace) = err.get_mut_stacktrace() {
+                        stacktrace.push(entry);
+                    } else {
+                        err.set_stacktrace(vec![entry]);
+                    }
+
+                    return Err(err);
+                }
+                (_, result) => return result,
+            }
+        }
+    }
+
+    /// Runs a single function call. Empty argument slots take the parameter's
+    /// default value, evaluated inside the callee's frame, or an empty list
+    /// for a variadic parameter.
+    fn run_function(
+        &mut self,
+        func: Function,
+        args: Vec<Option<Value>>,
+        span: Option<SourceSpan>,
+    ) -> Result<Value> {
+        let args: Vec<_> = func
             .get_params()
-            .iter()
-            .zip(args)
-            .map(|(a, b)| (a.clone(), b))
+            .into_iter()
+            .zip(args.into_iter().chain(std::iter::repeat(None)))
+            .map(|(param, value)| match value {
+                None if param.is_variadic => (param, Some(Value::List(Rc::default()))),
+                value => (param, value),
+            })
             .collect();
 
         let context_frame = Frame::from_env(func.get_env().clone());
 
+        let module = self.stack.replace_module(func.module.clone());
         self.stack.push(context_frame);
 
         let result = match func.object {
-            FunctionObject::Builtin { func_ptr, env, .. } => func_ptr(args, self, env),
+            FunctionObject::Builtin { func_ptr, env, .. } => {
+                let args = args
+                    .into_iter()
+                    .filter_map(|(param, value)| value.map(|value| (param, value)))
+                    .collect();
+
+                func_ptr(args, self, env)
+            }
+            FunctionObject::RecordConstructor {
+                name,
+                initializers,
+                methods,
+                ..
+            } => self.construct_record(name, args, &initializers, methods),
+            FunctionObject::UserDefined {
+                generator: Some(code),
+                ..
+            } => self.bind_parameters(args).map(|_| {
+                let name = func.metadata.as_ref().and_then(|m| m.get_name().clone());
+                let frame = self.stack.take_innermost_frame();
+                let generator = Generator::new(name, code, func.module.clone(), frame);
+
+                Value::Generator(Rc::new(RefCell::new(generator)))
+            }),
             FunctionObject::UserDefined { body, .. } => {
-                for (param, arg_value) in args.into_iter() {
-                    let stored_value = if param.is_captured {
-                        ValueCell::new_shared(arg_value)
-                    } else {
-                        ValueCell::new(arg_value)
-                    };
+                let is_expr = matches!(body.as_ref(), ast::Stmt::Expr(_));
+                let allows_tail_call = std::mem::replace(&mut self.allows_tail_call, true);
 
-                    self.stack.define(param.name.clone(), stored_value).unwrap();
-                }
+                let result = self
+                    .bind_parameters(args)
+                    .and_then(|_| match body.as_ref() {
+                        ast::Stmt::Expr(expr) => self
+                            .visit_tail_expr(expr)
+                            .map_err(|mut err| attach_span_if_missing!(err, expr.get_span())),
+                        body => self.interpret_stmt(body),
+                    });
 
-                let is_expr = matches!(body.as_ref(), ast::Stmt::Expr(_));
+                self.allows_tail_call = allows_tail_call;
 
-                match self.interpret_stmt(&body) {
+                match result {
                     Ok(value) => {
                         if is_expr {
                             Ok(value)
@@ -1040,59 +2756,310 @@ impl Runtime {
         };
 
         self.stack.pop();
+        self.stack.replace_module(module);
 
         result
     }
 
+    /// Runs a generator until its next `produza`, returning the produced value,
+    /// or `None` once the body has finished.
+    pub fn resume_generator(
+        &mut self,
+        generator: &Rc<RefCell<Generator>>,
+    ) -> Result<Option<Value>> {
+        let (code, module, mut suspension) = {
+            let mut generator = generator.borrow_mut();
+
+            match std::mem::replace(&mut generator.state, GeneratorState::Running) {
+                GeneratorState::Suspended(suspension) => {
+                    (generator.code.clone(), generator.module.clone(), suspension)
+                }
+                GeneratorState::Running => {
+                    return Err(Box::new(RuntimeError::GeneratorAlreadyRunning {
+                        span: None,
+                        stacktrace: vec![],
+                    }));
+                }
+                GeneratorState::Done => {
+                    generator.state = GeneratorState::Done;
+                    return Ok(None);
+                }
+            }
+        };
+
+        let depth = self.stack.depth();
+        let module = self.stack.replace_module(module);
+
+        for frame in std::mem::take(&mut suspension.frames) {
+            self.stack.push(frame);
+        }
+
+        let allows_tail_call = std::mem::replace(&mut self.allows_tail_call, false);
+        let result = self.run_generator(&code, &mut suspension, depth);
+        self.allows_tail_call = allows_tail_call;
+
+        suspension.frames = self.stack.split_off(depth);
+        self.stack.replace_module(module);
+
+        let mut generator = generator.borrow_mut();
+
+        match result {
+            Ok(Some(value)) => {
+                generator.state = GeneratorState::Suspended(suspension);
+                Ok(Some(value))
+            }
+            Ok(None) => {
+                generator.state = GeneratorState::Done;
+                Ok(None)
+            }
+            Err(mut err) => {
+                generator.state = GeneratorState::Done;
+
+                let trace = StackFrame::new(
+                    generator
+                        .name
+                        .clone()
+                        .map(FunctionName::Named)
+                        .unwrap_or(FunctionName::Anonymous),
+                    None,
+                );
+
+                if let Some(stacktrace) = err.get_mut_stacktrace() {
+                    stacktrace.push(trace);
+                } else {
+                    err.set_stacktrace(vec![trace]);
+                }
+
+                Err(err)
+            }
+        }
+    }
+
+    fn run_generator(
+        &mut self,
+        code: &GeneratorCode,
+        suspension: &mut Suspension,
+        depth: usize,
+    ) -> Result<Option<Value>> {
+        while let Some(instruction) = code.instructions.get(suspension.pc) {
+            suspension.pc += 1;
+
+            match instruction {
+                Instruction::Exec(stmt, enclosing_loop) => {
+                    self.interpret_stmt(stmt)?;
+
+                    if self.stack.has_return_value() {
+                        self.stack.consume_return_value();
+                        return Ok(None);
+                    }
+
+                    let target = self
+                        .stack
+                        .take_loop_jump()
+                        .and_then(|jump| code.resolve_loop_jump(&jump, *enclosing_loop));
+
+                    if let Some((exit, target)) = target {
+                        self.stack.truncate(depth + exit.scopes);
+                        suspension.iterations.truncate(exit.iterations);
+                        suspension.pc = target;
+                    }
+                }
+                Instruction::Yield(value) => return self.visit_expr(value).map(Some),
+                Instruction::Return(value) => {
+                    if let Some(value) = value {
+                        self.visit_expr(value)?;
+                    }
+
+                    return Ok(None);
+                }
+                Instruction::JumpIfFalse(cond, target) => {
+                    if !self.visit_expr(cond)?.to_bool() {
+                        suspension.pc = *target;
+                    }
+                }
+                Instruction::Jump(target) => suspension.pc = *target,
+                Instruction::PushScope => self.stack.push(Frame::new()),
+                Instruction::PopScope => self.stack.pop(),
+                Instruction::IterStart(iterable, span) => {
+                    let iterable = self.visit_expr(iterable)?;
+                    let iteration = self.iterate(iterable, span)?;
+
+                    suspension.iterations.push(iteration);
+                }
+                Instruction::IterNext(item, exit) => {
+                    let iteration = suspension.iterations.last_mut().unwrap();
+
+                    match self.next_item(iteration)? {
+                        Some(entry) => {
+                            let frame = self.bind_loop_item(item, entry, iteration.binds_keys())?;
+                            self.stack.push(frame);
+                        }
+                        None => suspension.pc = *exit,
+                    }
+                }
+                Instruction::IterEnd => {
+                    suspension.iterations.pop();
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Defines the parameters in the current frame, in order, so a default
+    /// value can refer to the parameters declared before it. Returns the names
+    /// that were bound.
+    fn bind_parameters(
+        &mut self,
+        args: Vec<(FunctionParam, Option<Value>)>,
+    ) -> Result<Vec<String>> {
+        let mut bound = Vec::with_capacity(args.len());
+
+        for (param, arg_value) in args.into_iter() {
+            let arg_value = match (arg_value, &param.default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => self.visit_expr(default)?,
+                (None, None) => continue,
+            };
+
+            let stored_value = if param.is_captured {
+                ValueCell::new_shared(arg_value)
+            } else {
+                ValueCell::new(arg_value)
+            };
+
+            self.stack.define(param.name.clone(), stored_value).unwrap();
+            bound.push(param.name);
+        }
+
+        Ok(bound)
+    }
+
+    fn construct_record(
+        &mut self,
+        name: String,
+        args: Vec<(FunctionParam, Option<Value>)>,
+        initializers: &[ast::LocalDecl],
+        methods: RecordMethods,
+    ) -> Result<Value> {
+        let mut field_names = self.bind_parameters(args)?;
+
+        for initializer in initializers {
+            self.visit_local_decl(initializer)?;
+            field_names.push(initializer.name.clone());
+        }
+
+        let fields = field_names
+            .into_iter()
+            .map(|field| {
+                let value = self.stack.lookup(&field).unwrap().extract();
+                (field, value)
+            })
+            .collect();
+
+        let record = Record::new(name, fields, methods);
+
+        Ok(Value::Record(Rc::new(RefCell::new(record))))
+    }
+
     fn create_function(
         &self,
         params: &[ast::FunctionParam],
         body: Box<ast::Stmt>,
         metadata: Option<FunctionRuntimeMetadata>,
     ) -> Function {
+        let context = self.capture_environment(params);
+
+        let mut func = Function::new(
+            params.iter().map(|p| p.clone().into()).collect(),
+            context,
+            body,
+        );
+
+        if let Some(metadata) = metadata {
+            func.set_metadata(metadata);
+        }
+
+        func.module = self.stack.module().cloned();
+
+        func
+    }
+
+    fn capture_environment(&self, params: &[ast::FunctionParam]) -> Environment {
         let mut context = Environment::new();
 
-        for frame in self.stack.into_iter() {
-            for (name, value) in frame.get_env() {
+        for env in self.stack.into_iter() {
+            for (name, value) in env {
                 if params.iter().any(|param| param.name == *name) {
                     continue;
                 }
 
                 if let ValueCell::Shared(value) = value {
-                    context.set(name.clone(), ValueCell::Shared(value.clone()));
+                    let value = ValueCell::Shared(value.clone());
+
+                    context.remove(name);
+
+                    match env.is_constant(name) {
+                        true => context.set_constant(name.clone(), value),
+                        false => context.set(name.clone(), value),
+                    }
                 }
             }
         }
 
-        let mut func = Function::new(
-            params.iter().map(|p| p.clone().into()).collect(),
-            context,
-            body,
-        );
+        context
+    }
 
-        if let Some(metadata) = metadata {
-            func.set_metadata(metadata);
+    fn resolve_record_field(
+        &mut self,
+        record: &Rc<RefCell<Record>>,
+        index: &ast::Expr,
+        include_methods: bool,
+    ) -> Result<String> {
+        let span = index.get_span();
+        let index = self.visit_expr(index)?;
+        let record = record.borrow();
+
+        let field = match index {
+            Value::String(field) => field,
+            value => {
+                return Err(Box::new(RuntimeError::UnexpectedTypeError {
+                    expected: ValueType::String,
+                    found: value.kind(),
+                    message: Some(format!(
+                        "campos da estrutura '{}' são acessados pelo nome",
+                        record.name
+                    )),
+                    span: Some(span.clone()),
+                    stacktrace: vec![],
+                }))
+            }
+        };
+
+        let is_method = include_methods && record.get_method(&field).is_some();
+
+        if record.get(&field).is_none() && !is_method {
+            return Err(unknown_record_field(&record, field, span));
         }
 
-        func
+        Ok(field)
     }
 
     fn resolve_associative_array_key(
         &mut self,
         key: Value,
     ) -> std::result::Result<AssociativeArrayKey, Box<RuntimeError>> {
-        match key {
-            Value::String(value) => Ok(AssociativeArrayKey::String(value)),
-            Value::Number(value) if !value.is_finite() || value.trunc() != value => {
+        match AssociativeArrayKey::try_from(key) {
+            Ok(key) => Ok(key),
+            Err(value @ (Value::Number(_) | Value::Decimal(_))) => {
                 Err(Box::new(RuntimeError::InvalidNumberAssociativeArrayKey {
-                    key: value,
+                    key: value.to_f64().unwrap_or(f64::NAN),
                     span: None,
                     stacktrace: vec![],
                 }))
             }
-            Value::Number(value) => Ok(AssociativeArrayKey::Number(value as i64)),
-            val => Err(Box::new(RuntimeError::InvalidTypeAssociativeArrayKey {
-                key: val.kind(),
+            Err(value) => Err(Box::new(RuntimeError::InvalidTypeAssociativeArrayKey {
+                key: value.kind(),
                 span: None,
                 stacktrace: vec![],
             })),
@@ -1111,6 +3078,12 @@ impl Runtime {
                 }))
             }
             Value::Number(num) => Ok(num as usize),
+            Value::Integer(num) if num.is_negative() => Err(Box::new(RuntimeError::InvalidIndex {
+                index: integer_to_f64(&num),
+                span: Some(span.clone()),
+                stacktrace: vec![],
+            })),
+            Value::Integer(num) => Ok(saturating_usize(&num)),
             val => Err(Box::new(RuntimeError::UnexpectedTypeError {
                 expected: ValueType::Number,
                 found: val.kind(),
@@ -1125,3 +3098,299 @@ impl Runtime {
         }
     }
 }
+
+fn list_element(list: &[Value], index: usize, span: &SourceSpan) -> Result<Value> {
+    match list.get(index) {
+        Some(value) => Ok(value.clone()),
+        None => Err(Box::new(RuntimeError::IndexOutOfBounds {
+            index,
+            len: list.len(),
+            span: Some(span.clone()),
+            help: vec![
+                "verifique se o índice está dentro dos limites da lista antes de tentar acessá-lo"
+                    .to_string(),
+            ],
+            stacktrace: vec![],
+        })),
+    }
+}
+
+fn associative_array_entry(
+    associative_array: &AssociativeArray,
+    key: &AssociativeArrayKey,
+    span: &SourceSpan,
+) -> Result<Value> {
+    match associative_array.get(key) {
+        Some(value) => Ok(value.clone()),
+        None => Err(Box::new(RuntimeError::AssociativeArrayKeyNotFound {
+            key: key.clone(),
+            span: Some(span.clone()),
+            stacktrace: vec![],
+        })),
+    }
+}
+
+fn unknown_record_field(record: &Record, field: String, span: &SourceSpan) -> Box<RuntimeError> {
+    Box::new(RuntimeError::UnknownRecordField {
+        record: record.name.clone(),
+        field,
+        span: Some(span.clone()),
+        help: Some(match record.fields.is_empty() {
+            true => format!("a estrutura '{}' não possui campos", record.name),
+            false => format!(
+                "os campos de '{}' são: {}",
+                record.name,
+                record.field_names().join(", ")
+            ),
+        }),
+        stacktrace: vec![],
+    })
+}
+
+fn unknown_enum_variant(
+    enumeration: &Enum,
+    variant: String,
+    span: &SourceSpan,
+) -> Box<RuntimeError> {
+    Box::new(RuntimeError::UnknownEnumVariant {
+        enumeration: enumeration.name.clone(),
+        variant,
+        span: Some(span.clone()),
+        help: Some(format!(
+            "as variantes de '{}' são: {}",
+            enumeration.name,
+            enumeration.variants.join(", ")
+        )),
+        stacktrace: vec![],
+    })
+}
+
+fn unknown_module_member(module: &Module, name: String, span: &SourceSpan) -> Box<RuntimeError> {
+    Box::new(RuntimeError::UnknownModuleMember {
+        module: module.path.clone(),
+        name,
+        span: Some(span.clone()),
+        help: Some(match module.exports().is_empty() {
+            true => format!("o módulo '{}' não declara nomes", module.path),
+            false => format!(
+                "os nomes declarados em '{}' são: {}",
+                module.path,
+                module.exports().join(", ")
+            ),
+        }),
+        stacktrace: vec![],
+    })
+}
+
+/// Promotes an integer operand to a float when the other operand is a float,
+/// so mixed arithmetic follows the float rules. Operands mixed with a decimal
+/// become decimals, falling back to floats when they do not fit in one.
+fn promote_numbers(lhs: Value, rhs: Value) -> (Value, Value) {
+    match (lhs, rhs) {
+        (Value::Integer(lhs), Value::Number(rhs)) => {
+            (Value::Number(integer_to_f64(&lhs)), Value::Number(rhs))
+        }
+        (Value::Number(lhs), Value::Integer(rhs)) => {
+            (Value::Number(lhs), Value::Number(integer_to_f64(&rhs)))
+        }
+        (Value::Decimal(lhs), Value::Integer(rhs)) => match decimal_from_integer(&rhs) {
+            Some(rhs) => (Value::Decimal(lhs), Value::Decimal(rhs)),
+            None => (
+                Value::Number(decimal_to_f64(&lhs)),
+                Value::Number(integer_to_f64(&rhs)),
+            ),
+        },
+        (Value::Integer(lhs), Value::Decimal(rhs)) => match decimal_from_integer(&lhs) {
+            Some(lhs) => (Value::Decimal(lhs), Value::Decimal(rhs)),
+            None => (
+                Value::Number(integer_to_f64(&lhs)),
+                Value::Number(decimal_to_f64(&rhs)),
+            ),
+        },
+        (Value::Decimal(lhs), Value::Number(rhs)) => match decimal_from_f64(rhs) {
+            Some(rhs) => (Value::Decimal(lhs), Value::Decimal(rhs)),
+            None => (Value::Number(decimal_to_f64(&lhs)), Value::Number(rhs)),
+        },
+        (Value::Number(lhs), Value::Decimal(rhs)) => match decimal_from_f64(lhs) {
+            Some(lhs) => (Value::Decimal(lhs), Value::Decimal(rhs)),
+            None => (Value::Number(lhs), Value::Number(decimal_to_f64(&rhs))),
+        },
+        pair => pair,
+    }
+}
+
+fn checked_decimal(value: Option<Decimal>, span: &SourceSpan) -> Result<Value> {
+    match value {
+        Some(value) => Ok(Value::Decimal(value)),
+        None => Err(Box::new(RuntimeError::DecimalOverflow {
+            span: Some(span.clone()),
+            stacktrace: vec![],
+        })),
+    }
+}
+
+fn decimal_to_f64(value: &Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+fn integer_to_f64(value: &BigInt) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Returns the value as a range element when it is an integral number that
+/// fits in the bounds of a range.
+fn range_integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(value) if value.trunc() == *value => value.to_i64(),
+        Value::Integer(value) => value.to_i64(),
+        Value::Decimal(value) if value.fract().is_zero() => value.to_i64(),
+        _ => None,
+    }
+}
+
+fn range_bound(value: &Value, span: &SourceSpan) -> Result<i64> {
+    range_integer(value).ok_or_else(|| {
+        Box::new(RuntimeError::InvalidRangeBounds {
+            bound: value.to_f64().unwrap_or(f64::NAN),
+            span: Some(span.clone()),
+            stacktrace: vec![],
+        })
+    })
+}
+
+fn saturating_usize(value: &BigInt) -> usize {
+    match value.is_negative() {
+        true => 0,
+        false => value.to_usize().unwrap_or(usize::MAX),
+    }
+}
+
+fn saturating_i64(value: &BigInt) -> i64 {
+    match value.is_negative() {
+        true => value.to_i64().unwrap_or(i64::MIN),
+        false => value.to_i64().unwrap_or(i64::MAX),
+    }
+}
+
+fn declaration_result(
+    name: &str,
+    result: std::result::Result<(), StackError>,
+    span: &SourceSpan,
+) -> Result<()> {
+    match result {
+        Ok(_) => Ok(()),
+        Err(err) => match err {
+            StackError::AlreadyDeclared => Err(Box::new(RuntimeError::AlreadyDeclared {
+                var_name: name.to_string(),
+                span: Some(span.clone()),
+                help: Some("declare a variável com outro nome ou use `=` para atribuir um novo valor a ela".to_string()),
+                stacktrace: vec![],
+            })),
+            _ => unreachable!(),
+        },
+    }
+}
+
+fn binding_cell(binding: &ast::PatternBinding, value: Value) -> ValueCell {
+    match binding.captured {
+        true => ValueCell::new_shared(value),
+        false => ValueCell::new(value),
+    }
+}
+
+/// Matches the arguments of a call against the parameters of `func`, giving
+/// one slot per parameter. Slots left empty are filled by default values when
+/// the function is invoked.
+fn resolve_arguments(
+    func: &Function,
+    args: Vec<Value>,
+    named_args: Vec<(&ast::NamedArg, Value)>,
+    span: &SourceSpan,
+) -> Result<Vec<Option<Value>>> {
+    let params = func.get_params();
+    let is_variadic = params.last().is_some_and(|param| param.is_variadic);
+
+    if !is_variadic && args.len() > params.len() {
+        return Err(Box::new(RuntimeError::WrongNumberOfArguments {
+            expected: params.len(),
+            found: args.len() + named_args.len(),
+            span: Some(span.clone()),
+            stacktrace: vec![],
+        }));
+    }
+
+    let mut slots = positional_slots(&params, args);
+
+    for (arg, value) in named_args {
+        let position = match params.iter().position(|param| param.name == arg.name) {
+            Some(position) => position,
+            None => {
+                return Err(Box::new(RuntimeError::UnknownArgument {
+                    name: arg.name.clone(),
+                    span: Some(arg.span.clone()),
+                    help: Some(match params.is_empty() {
+                        true => "a função não recebe parâmetros".to_string(),
+                        false => format!(
+                            "os parâmetros da função são: {}",
+                            params
+                                .iter()
+                                .map(|param| param.name.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    }),
+                    stacktrace: vec![],
+                }))
+            }
+        };
+
+        if slots[position].is_some() {
+            return Err(Box::new(RuntimeError::DuplicateArgument {
+                name: arg.name.clone(),
+                span: Some(arg.span.clone()),
+                stacktrace: vec![],
+            }));
+        }
+
+        slots[position] = Some(value);
+    }
+
+    let missing: Vec<String> = params
+        .iter()
+        .zip(&slots)
+        .filter(|(param, slot)| slot.is_none() && param.default.is_none() && !param.is_variadic)
+        .map(|(param, _)| param.name.clone())
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(Box::new(RuntimeError::MissingArguments {
+            params: missing,
+            span: Some(span.clone()),
+            stacktrace: vec![],
+        }));
+    }
+
+    Ok(slots)
+}
+
+/// Places positional arguments in one slot per parameter, collecting the
+/// arguments past the fixed parameters into a list for a variadic parameter.
+fn positional_slots(params: &[FunctionParam], mut args: Vec<Value>) -> Vec<Option<Value>> {
+    let is_variadic = params.last().is_some_and(|param| param.is_variadic);
+
+    let rest = match is_variadic {
+        true => Some(args.split_off((params.len() - 1).min(args.len()))),
+        false => None,
+    };
+
+    let mut slots: Vec<Option<Value>> = args.into_iter().map(Some).collect();
+
+    if let Some(rest) = rest {
+        slots.resize(params.len() - 1, None);
+        slots.push((!rest.is_empty()).then(|| Value::List(Rc::new(RefCell::new(rest)))));
+    } else if slots.len() < params.len() {
+        slots.resize(params.len(), None);
+    }
+
+    slots
+}
@@ -1,10 +1,12 @@
-use tenda_common::span::SourceSpan;
+use num_bigint::BigInt;
+use std::{cell::RefCell, fmt, fmt::Display, rc::Rc};
+use tenda_common::span::{SourceSpan, Span};
 use tenda_reporting::{Diagnostic, DiagnosticConfig, HasDiagnosticHooks};
 use tenda_reporting_derive::Diagnostic;
 use thiserror::Error;
 
 use crate::{
-    associative_array::AssociativeArrayKey,
+    associative_array::{AssociativeArray, AssociativeArrayKey},
     value::{Value, ValueType},
 };
 
@@ -32,15 +34,19 @@ impl StackFrame {
     }
 }
 
+impl Display for FunctionName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FunctionName::Anonymous => write!(f, "<anônimo>"),
+            FunctionName::TopLevel => write!(f, "<raiz>"),
+            FunctionName::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 impl From<StackFrame> for tenda_reporting::StackFrame<SourceSpan> {
     fn from(val: StackFrame) -> Self {
-        let function_name = match val.function_name {
-            FunctionName::Anonymous => "<anônimo>".to_string(),
-            FunctionName::TopLevel => "<raiz>".to_string(),
-            FunctionName::Named(name) => name,
-        };
-
-        tenda_reporting::StackFrame::new(function_name, val.location)
+        tenda_reporting::StackFrame::new(val.function_name.to_string(), val.location)
     }
 }
 
@@ -115,6 +121,20 @@ pub enum RuntimeError {
         stacktrace: Vec<StackFrame>,
     },
 
+    #[error("não é possível atribuir um novo valor à constante '{}'", .var_name)]
+    ConstantReassignment {
+        var_name: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[help]
+        help: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
     #[error("número de argumentos incorreto: esperado {}, encontrado {}", .expected, .found)]
     WrongNumberOfArguments {
         expected: usize,
@@ -127,6 +147,42 @@ pub enum RuntimeError {
         stacktrace: Vec<StackFrame>,
     },
 
+    #[error("argumentos obrigatórios ausentes: {}", format_param_names(.params))]
+    MissingArguments {
+        params: Vec<String>,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("a função não possui parâmetro chamado '{}'", .name)]
+    UnknownArgument {
+        name: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[help]
+        help: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("argumento '{}' fornecido mais de uma vez", .name)]
+    DuplicateArgument {
+        name: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
     #[error("índice fora dos limites: índice {}, tamanho {}", .index, .len)]
     IndexOutOfBounds {
         index: usize,
@@ -164,6 +220,46 @@ pub enum RuntimeError {
         stacktrace: Vec<StackFrame>,
     },
 
+    #[error("passo de intervalo precisa ser um inteiro diferente de zero: encontrado '{}'", .step)]
+    InvalidRangeStep {
+        step: Value,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("deslocamento de bits precisa ser um inteiro não negativo: encontrado '{}'", .amount)]
+    InvalidShiftAmount {
+        amount: BigInt,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("o gerador já está em execução")]
+    GeneratorAlreadyRunning {
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("resultado fora dos limites do tipo decimal")]
+    DecimalOverflow {
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
     #[error("índice de lista precisa ser um número inteiro positivo e finito: encontrado '{}'", .index)]
     InvalidIndex {
         index: f64,
@@ -175,7 +271,7 @@ pub enum RuntimeError {
         stacktrace: Vec<StackFrame>,
     },
 
-    #[error("chave de dicionário precisa ser número inteiro ou texto: encontrado '{}'", .key)]
+    #[error("chave de dicionário precisa ser número inteiro, texto, lógico ou variante de enumeração: encontrado '{}'", .key)]
     InvalidNumberAssociativeArrayKey {
         key: f64,
 
@@ -186,7 +282,7 @@ pub enum RuntimeError {
         stacktrace: Vec<StackFrame>,
     },
 
-    #[error("chave de dicionário precisa ser número inteiro ou texto: encontrado '{}'", .key)]
+    #[error("chave de dicionário precisa ser número inteiro, texto, lógico ou variante de enumeração: encontrado '{}'", .key)]
     InvalidTypeAssociativeArrayKey {
         key: ValueType,
 
@@ -197,6 +293,17 @@ pub enum RuntimeError {
         stacktrace: Vec<StackFrame>,
     },
 
+    #[error("elemento de conjunto precisa ser número inteiro, texto, lógico ou variante de enumeração: encontrado '{}'", .value)]
+    InvalidSetElement {
+        value: ValueType,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
     #[error("chave de dicionário não encontrada: '{}'", .key.to_string())]
     AssociativeArrayKeyNotFound {
         key: AssociativeArrayKey,
@@ -285,6 +392,288 @@ pub enum RuntimeError {
         #[metadata]
         stacktrace: Vec<StackFrame>,
     },
+
+    #[error("a estrutura '{record}' não possui o campo '{field}'")]
+    UnknownRecordField {
+        record: String,
+        field: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[help]
+        help: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("a enumeração '{enumeration}' não possui a variante '{variant}'")]
+    UnknownEnumVariant {
+        enumeration: String,
+        variant: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[help]
+        help: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("o módulo '{module}' não declara '{name}'")]
+    UnknownModuleMember {
+        module: String,
+        name: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[help]
+        help: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("não é possível desestruturar: esperado {expected} elementos, encontrado {found}")]
+    DestructuringLengthMismatch {
+        expected: String,
+        found: usize,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[help]
+        help: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("nenhum caso corresponde ao valor {value}")]
+    NoMatchingCase {
+        value: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[help]
+        help: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("módulo não encontrado: '{path}'")]
+    ModuleNotFound {
+        path: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[help]
+        help: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("importação circular do módulo '{path}'")]
+    CircularImport {
+        path: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("módulo inválido: '{path}'")]
+    InvalidModule {
+        path: String,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[message]
+        message: Option<String>,
+
+        #[label]
+        import_span: Option<SourceSpan>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+
+    #[error("erro lançado: {}", .value)]
+    Thrown {
+        value: Value,
+
+        #[span]
+        span: Option<SourceSpan>,
+
+        #[message]
+        message: Option<String>,
+
+        #[metadata]
+        stacktrace: Vec<StackFrame>,
+    },
+}
+
+impl RuntimeError {
+    /// Stable identifier for each kind of error, exposed to programs as the
+    /// `tipo` field of the value bound by a `capture` clause.
+    pub fn kind_name(&self) -> &'static str {
+        use RuntimeError::*;
+
+        match self {
+            DivisionByZero { .. } => "DIVISÃO_POR_ZERO",
+            TypeMismatch { .. } => "TIPOS_INCOMPATÍVEIS",
+            UnexpectedTypeError { .. } => "TIPO_INESPERADO",
+            UndefinedReference { .. } => "REFERÊNCIA_INDEFINIDA",
+            AlreadyDeclared { .. } => "JÁ_DECLARADA",
+            ConstantReassignment { .. } => "ATRIBUIÇÃO_A_CONSTANTE",
+            WrongNumberOfArguments { .. } => "NÚMERO_DE_ARGUMENTOS_INCORRETO",
+            MissingArguments { .. } => "ARGUMENTOS_AUSENTES",
+            UnknownArgument { .. } => "ARGUMENTO_DESCONHECIDO",
+            DuplicateArgument { .. } => "ARGUMENTO_DUPLICADO",
+            IndexOutOfBounds { .. } => "ÍNDICE_FORA_DOS_LIMITES",
+            WrongIndexType { .. } => "TIPO_NÃO_INDEXÁVEL",
+            InvalidRangeBounds { .. } => "LIMITES_DE_INTERVALO_INVÁLIDOS",
+            InvalidRangeStep { .. } => "PASSO_DE_INTERVALO_INVÁLIDO",
+            InvalidShiftAmount { .. } => "DESLOCAMENTO_INVÁLIDO",
+            DecimalOverflow { .. } => "ESTOURO_DECIMAL",
+            GeneratorAlreadyRunning { .. } => "GERADOR_EM_EXECUÇÃO",
+            InvalidIndex { .. } => "ÍNDICE_INVÁLIDO",
+            InvalidNumberAssociativeArrayKey { .. } => "CHAVE_INVÁLIDA",
+            InvalidTypeAssociativeArrayKey { .. } => "CHAVE_INVÁLIDA",
+            AssociativeArrayKeyNotFound { .. } => "CHAVE_NÃO_ENCONTRADA",
+            InvalidSetElement { .. } => "ELEMENTO_INVÁLIDO",
+            NotIterable { .. } => "NÃO_ITERÁVEL",
+            InvalidArgument { .. } => "ARGUMENTO_INVÁLIDO",
+            ImmutableString { .. } => "TEXTO_IMUTÁVEL",
+            InvalidTimestamp { .. } => "TIMESTAMP_INVÁLIDO",
+            DateIsoParseError { .. } => "ISO_INVÁLIDA",
+            InvalidTimeZoneString { .. } => "FUSO_HORÁRIO_INVÁLIDO",
+            InvalidValueForConversion { .. } => "CONVERSÃO_INVÁLIDA",
+            UnknownRecordField { .. } => "CAMPO_INEXISTENTE",
+            UnknownEnumVariant { .. } => "VARIANTE_INEXISTENTE",
+            UnknownModuleMember { .. } => "NOME_INEXISTENTE",
+            NoMatchingCase { .. } => "NENHUM_CASO_CORRESPONDENTE",
+            DestructuringLengthMismatch { .. } => "NÚMERO_DE_ELEMENTOS_INCORRETO",
+            ModuleNotFound { .. } => "MÓDULO_NÃO_ENCONTRADO",
+            CircularImport { .. } => "IMPORTAÇÃO_CIRCULAR",
+            InvalidModule { .. } => "MÓDULO_INVÁLIDO",
+            Thrown { .. } => "ERRO_LANÇADO",
+        }
+    }
+
+    /// Converts the error into the dictionary bound by a `capture` clause:
+    /// `{ "tipo": ..., "mensagem": ..., "local": ..., "pilha": [...] }`.
+    ///
+    /// Errors raised with `lance` also carry the thrown value under `valor`,
+    /// and take `tipo` from it when the value is a dictionary with that key.
+    pub fn to_value(&self) -> Value {
+        let message = self.get_message().unwrap_or_else(|| self.to_string());
+
+        let kind = match self {
+            RuntimeError::Thrown {
+                value: Value::AssociativeArray(value),
+                ..
+            } => match value
+                .borrow()
+                .get(&AssociativeArrayKey::String("tipo".to_string()))
+            {
+                Some(Value::String(kind)) => kind.clone(),
+                _ => self.kind_name().to_string(),
+            },
+            _ => self.kind_name().to_string(),
+        };
+
+        let stacktrace = build_stack_frames(self)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|frame| {
+                let mut map = AssociativeArray::new();
+
+                map.insert(
+                    AssociativeArrayKey::String("função".to_string()),
+                    Value::String(frame.function_name.to_string()),
+                );
+                map.insert(
+                    AssociativeArrayKey::String("local".to_string()),
+                    span_to_value(frame.location.as_ref()),
+                );
+
+                Value::AssociativeArray(Rc::new(RefCell::new(map)))
+            })
+            .collect();
+
+        let mut map = AssociativeArray::new();
+
+        map.insert(
+            AssociativeArrayKey::String("tipo".to_string()),
+            Value::String(kind),
+        );
+        map.insert(
+            AssociativeArrayKey::String("mensagem".to_string()),
+            Value::String(message),
+        );
+
+        if let RuntimeError::Thrown { value, .. } = self {
+            map.insert(
+                AssociativeArrayKey::String("valor".to_string()),
+                value.clone(),
+            );
+        }
+
+        map.insert(
+            AssociativeArrayKey::String("local".to_string()),
+            span_to_value(self.get_span().as_ref()),
+        );
+        map.insert(
+            AssociativeArrayKey::String("pilha".to_string()),
+            Value::List(Rc::new(RefCell::new(stacktrace))),
+        );
+
+        Value::AssociativeArray(Rc::new(RefCell::new(map)))
+    }
+}
+
+fn format_param_names(params: &[String]) -> String {
+    params
+        .iter()
+        .map(|param| format!("'{}'", param))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn span_to_value(span: Option<&SourceSpan>) -> Value {
+    let span = match span {
+        Some(span) => span,
+        None => return Value::Nil,
+    };
+
+    let mut map = AssociativeArray::new();
+
+    map.insert(
+        AssociativeArrayKey::String("início".to_string()),
+        Value::Integer(BigInt::from(span.start())),
+    );
+    map.insert(
+        AssociativeArrayKey::String("fim".to_string()),
+        Value::Integer(BigInt::from(span.end())),
+    );
+    map.insert(
+        AssociativeArrayKey::String("fonte".to_string()),
+        Value::String(Span::source(span).to_string()),
+    );
+
+    Value::AssociativeArray(Rc::new(RefCell::new(map)))
 }
 
 impl HasDiagnosticHooks<SourceSpan> for RuntimeError {
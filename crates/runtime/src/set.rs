@@ -0,0 +1,26 @@
+use crate::associative_array::AssociativeArrayKey;
+use crate::runtime_error::{Result, RuntimeError};
+use crate::value::Value;
+
+/// Distinct values in insertion order. Elements are stored as dictionary
+/// keys, so sets accept the same values that dictionaries accept as keys.
+pub type Set = indexmap::IndexSet<AssociativeArrayKey>;
+
+pub fn set_element(value: Value) -> Result<AssociativeArrayKey> {
+    AssociativeArrayKey::try_from(value).map_err(|value| {
+        Box::new(RuntimeError::InvalidSetElement {
+            value: value.kind(),
+            span: None,
+            stacktrace: vec![],
+        })
+    })
+}
+
+pub fn set_from_values(values: impl IntoIterator<Item = Value>) -> Result<Set> {
+    values.into_iter().map(set_element).collect()
+}
+
+/// Lists the elements of a set as values, in insertion order.
+pub fn set_values(set: &Set) -> Vec<Value> {
+    set.iter().cloned().map(Value::from).collect()
+}
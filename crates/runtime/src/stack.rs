@@ -1,6 +1,11 @@
+use std::rc::Rc;
 use thiserror::Error;
 
-use crate::{environment::ValueCell, frame::Frame};
+use crate::{
+    environment::{Environment, ValueCell},
+    frame::Frame,
+    module::Module,
+};
 
 type Result<T> = std::result::Result<T, StackError>;
 
@@ -8,8 +13,10 @@ type Result<T> = std::result::Result<T, StackError>;
 pub struct Stack {
     global: Frame,
     frame: Vec<Frame>,
-    has_break: bool,
-    has_continue: bool,
+    loop_jump: Option<LoopJump>,
+    /// The module of the running function, whose globals are resolved in the
+    /// module's own scope instead of the global frame.
+    module: Option<Rc<Module>>,
 }
 
 impl Stack {
@@ -17,8 +24,8 @@ impl Stack {
         Stack {
             global: Frame::new(),
             frame: vec![],
-            has_break: false,
-            has_continue: false,
+            loop_jump: None,
+            module: None,
         }
     }
 
@@ -38,15 +45,36 @@ impl Stack {
         Ok(())
     }
 
+    pub fn define_constant(&mut self, name: String, value: ValueCell) -> Result<()> {
+        let scope = self.get_innermost_scope_mut();
+
+        if scope.get_env().has(&name) {
+            return Err(StackError::AlreadyDeclared);
+        }
+
+        scope.get_env_mut().set_constant(name, value);
+
+        Ok(())
+    }
+
     pub fn assign(&mut self, name: String, value: ValueCell) -> Result<()> {
         let frame = self
             .frame
             .iter_mut()
             .rev()
-            .find(|frame| frame.get_env().has(&name))
-            .unwrap_or(&mut self.global);
+            .find(|frame| frame.get_env().has(&name));
 
-        if frame.get_env().has(&name) {
+        let frame = match (frame, self.module.as_ref()) {
+            (Some(frame), _) => frame,
+            (None, Some(module)) if module.globals().is_some() => {
+                return module.assign(&name, value.extract());
+            }
+            (None, _) => &mut self.global,
+        };
+
+        if frame.get_env().is_constant(&name) {
+            Err(StackError::AssignToConstant(name))
+        } else if frame.get_env().has(&name) {
             frame.get_env_mut().set(name, value);
             Ok(())
         } else {
@@ -61,7 +89,7 @@ impl Stack {
             }
         }
 
-        self.global.get_env().get(name)
+        self.globals().get(name)
     }
 
     pub fn push(&mut self, frame: Frame) {
@@ -76,6 +104,25 @@ impl Stack {
         self.frame.pop();
     }
 
+    pub fn depth(&self) -> usize {
+        self.frame.len()
+    }
+
+    pub fn truncate(&mut self, depth: usize) {
+        self.frame.truncate(depth);
+    }
+
+    /// Removes and returns the frames above `depth`, innermost last.
+    pub fn split_off(&mut self, depth: usize) -> Vec<Frame> {
+        self.frame.split_off(depth)
+    }
+
+    /// Moves the innermost frame out of the stack, leaving an empty frame in
+    /// its place.
+    pub fn take_innermost_frame(&mut self) -> Frame {
+        std::mem::take(self.get_innermost_scope_mut())
+    }
+
     pub fn set_return_value(&mut self, value: ValueCell) {
         self.get_innermost_scope_mut().set_return_value(value);
     }
@@ -92,20 +139,20 @@ impl Stack {
         value
     }
 
-    pub fn set_loop_break_flag(&mut self, value: bool) {
-        self.has_break = value;
+    pub fn set_loop_jump(&mut self, jump: LoopJump) {
+        self.loop_jump = Some(jump);
     }
 
-    pub fn has_loop_break_flag(&self) -> bool {
-        self.has_break
+    pub fn get_loop_jump(&self) -> Option<&LoopJump> {
+        self.loop_jump.as_ref()
     }
 
-    pub fn set_loop_continue_flag(&mut self, value: bool) {
-        self.has_continue = value;
+    pub fn has_loop_jump(&self) -> bool {
+        self.loop_jump.is_some()
     }
 
-    pub fn has_loop_continue_flag(&self) -> bool {
-        self.has_continue
+    pub fn take_loop_jump(&mut self) -> Option<LoopJump> {
+        self.loop_jump.take()
     }
 
     pub fn global(&self) -> &Frame {
@@ -115,9 +162,26 @@ impl Stack {
     pub fn global_mut(&mut self) -> &mut Frame {
         &mut self.global
     }
+
+    pub fn module(&self) -> Option<&Rc<Module>> {
+        self.module.as_ref()
+    }
+
+    /// Sets the module whose globals are in scope, returning the previous one
+    /// so the caller can restore it.
+    pub fn replace_module(&mut self, module: Option<Rc<Module>>) -> Option<Rc<Module>> {
+        std::mem::replace(&mut self.module, module)
+    }
 }
 
 impl Stack {
+    fn globals(&self) -> &Environment {
+        match self.module.as_ref().and_then(|module| module.globals()) {
+            Some(globals) => globals,
+            None => self.global.get_env(),
+        }
+    }
+
     fn get_innermost_frame(&self) -> &Frame {
         self.frame.last().unwrap_or(&self.global)
     }
@@ -146,15 +210,35 @@ impl Stack {
     }
 }
 
+/// A `pare` or `continua` on its way to the loop it acts on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoopJump {
+    Break(Option<String>),
+    Continue(Option<String>),
+}
+
+impl LoopJump {
+    /// Whether the jump acts on a loop with `label`. An unlabeled jump acts
+    /// on the innermost loop it reaches.
+    pub fn targets(&self, label: Option<&String>) -> bool {
+        match self {
+            LoopJump::Break(None) | LoopJump::Continue(None) => true,
+            LoopJump::Break(target) | LoopJump::Continue(target) => target.as_ref() == label,
+        }
+    }
+}
+
+/// Iterates the environments in scope, from the globals to the innermost
+/// frame.
 impl<'a> IntoIterator for &'a Stack {
-    type Item = &'a Frame;
-    type IntoIter = std::vec::IntoIter<&'a Frame>;
+    type Item = &'a Environment;
+    type IntoIter = std::vec::IntoIter<&'a Environment>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let mut frames: Vec<&Frame> = Vec::with_capacity(self.frame.len() + 1);
-        frames.push(&self.global);
-        frames.extend(self.frame.iter());
-        frames.into_iter()
+        let mut envs: Vec<&Environment> = Vec::with_capacity(self.frame.len() + 1);
+        envs.push(self.globals());
+        envs.extend(self.frame.iter().map(Frame::get_env));
+        envs.into_iter()
     }
 }
 
@@ -171,4 +255,7 @@ pub enum StackError {
 
     #[error("assignment to undefined variable")]
     AssignToUndefined(String),
+
+    #[error("assignment to constant")]
+    AssignToConstant(String),
 }
@@ -1,23 +1,41 @@
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
+use rust_decimal::Decimal;
 use std::cell::RefCell;
 use std::fmt;
 use std::fmt::Display;
 use std::rc::Rc;
+use std::str::FromStr;
 use tenda_scanner::Literal;
 
 use crate::associative_array::{AssociativeArray, AssociativeArrayKey};
 use crate::date::Date;
+use crate::enumeration::{Enum, EnumVariant};
 use crate::function::Function;
+use crate::generator::Generator;
+use crate::module::Module;
+use crate::range::Range;
+use crate::record::Record;
+use crate::set::Set;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Value {
     Number(f64),
+    Integer(BigInt),
+    Decimal(Decimal),
     Boolean(bool),
     String(String),
     Function(Function),
+    Generator(Rc<RefCell<Generator>>),
     List(Rc<RefCell<Vec<Value>>>),
-    Range(usize, usize),
+    Range(Range),
     AssociativeArray(Rc<RefCell<AssociativeArray>>),
+    Set(Rc<RefCell<Set>>),
     Date(Date),
+    Record(Rc<RefCell<Record>>),
+    Enum(Rc<Enum>),
+    EnumVariant(EnumVariant),
+    Module(Rc<Module>),
     Nil,
 }
 
@@ -27,33 +45,95 @@ impl Value {
 
         match self {
             Number(_) => ValueType::Number,
+            Integer(_) => ValueType::Integer,
+            Decimal(_) => ValueType::Decimal,
             Boolean(_) => ValueType::Boolean,
             String(_) => ValueType::String,
             Function(_) => ValueType::Function,
+            Generator(_) => ValueType::Generator,
             List(_) => ValueType::List,
-            Range(_, _) => ValueType::Range,
+            Range(_) => ValueType::Range,
             Nil => ValueType::Nil,
             AssociativeArray(_) => ValueType::AssociativeArray,
+            Set(_) => ValueType::Set,
             Date(_) => ValueType::Date,
+            Record(_) => ValueType::Record,
+            Enum(_) => ValueType::Enum,
+            EnumVariant(_) => ValueType::EnumVariant,
+            Module(_) => ValueType::Module,
         }
     }
 
     pub fn to_bool(&self) -> bool {
         match self {
             Value::Number(value) => *value != 0.0,
+            Value::Integer(value) => !value.is_zero(),
+            Value::Decimal(value) => !value.is_zero(),
             Value::Boolean(value) => *value,
             Value::String(_) => true,
             Value::Function(_) => true,
+            Value::Generator(_) => true,
             Value::List(_) => true,
-            Value::Range(_, _) => true,
+            Value::Range(_) => true,
             Value::Nil => false,
             Value::AssociativeArray(_) => true,
+            Value::Set(_) => true,
             Value::Date(_) => true,
+            Value::Record(_) => true,
+            Value::Enum(_) => true,
+            Value::EnumVariant(_) => true,
+            Value::Module(_) => true,
         }
     }
 
     pub fn is_iterable(&self) -> bool {
-        matches!(self, Value::List(_) | Value::Range(_, _))
+        matches!(
+            self,
+            Value::List(_)
+                | Value::Range(_)
+                | Value::String(_)
+                | Value::AssociativeArray(_)
+                | Value::Set(_)
+                | Value::Enum(_)
+                | Value::Generator(_)
+        )
+    }
+
+    /// Returns the value as a float when it is a number, promoting integers.
+    pub fn to_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(value) => Some(*value),
+            Value::Integer(value) => value.to_f64(),
+            Value::Decimal(value) => value.to_f64(),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        use Value::*;
+
+        match (self, other) {
+            (Number(lhs), Number(rhs)) => lhs == rhs,
+            (Integer(lhs), Integer(rhs)) => lhs == rhs,
+            (Decimal(lhs), Decimal(rhs)) => lhs == rhs,
+            (Boolean(lhs), Boolean(rhs)) => lhs == rhs,
+            (String(lhs), String(rhs)) => lhs == rhs,
+            (Function(lhs), Function(rhs)) => lhs == rhs,
+            (Generator(lhs), Generator(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (List(lhs), List(rhs)) => lhs == rhs,
+            (Range(lhs), Range(rhs)) => lhs == rhs,
+            (AssociativeArray(lhs), AssociativeArray(rhs)) => lhs == rhs,
+            (Set(lhs), Set(rhs)) => lhs == rhs,
+            (Date(lhs), Date(rhs)) => lhs == rhs,
+            (Record(lhs), Record(rhs)) => lhs == rhs,
+            (Enum(lhs), Enum(rhs)) => lhs == rhs,
+            (EnumVariant(lhs), EnumVariant(rhs)) => lhs == rhs,
+            (Module(lhs), Module(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (Nil, Nil) => true,
+            _ => false,
+        }
     }
 }
 
@@ -76,12 +156,15 @@ impl Display for Value {
                     v if v.is_nan() => Literal::NAN_LITERAL.to_string(),
                     _ => value.to_string(),
                 },
+                Integer(value) => value.to_string(),
+                Decimal(value) => value.to_string(),
                 Boolean(value) => match *value {
                     true => Literal::TRUE_LITERAL.to_string(),
                     false => Literal::FALSE_LITERAL.to_string(),
                 },
                 String(value) => format!("\"{}\"", value),
                 Function(value) => format!("<função {:#x}>", value.id),
+                Generator(value) => format!("<gerador {:#x}>", value.borrow().id),
                 List(value) => format!(
                     "[{}]",
                     value
@@ -94,7 +177,7 @@ impl Display for Value {
                         .collect::<Vec<_>>()
                         .join(", ")
                 ),
-                Range(start, end) => format!("{} até {}", start, end),
+                Range(range) => range.to_string(),
                 Nil => Literal::NIL_LITERAL.to_string(),
                 AssociativeArray(value) => format!(
                     "{{ {} }}",
@@ -105,14 +188,38 @@ impl Display for Value {
                             Value::String(s) => (k, format!("\"{}\"", escape_special_chars(s))),
                             _ => (k, v.to_string()),
                         })
-                        .map(|(k, v)| match k {
-                            AssociativeArrayKey::String(key) => format!("\"{}\": {}", key, v),
-                            AssociativeArrayKey::Number(key) => format!("{}: {}", key, v),
-                        })
+                        .map(|(k, v)| format!("{}: {}", escape_key(k), v))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Set(value) if value.borrow().is_empty() => "Conjunto {}".to_string(),
+                Set(value) => format!(
+                    "Conjunto {{ {} }}",
+                    value
+                        .borrow()
+                        .iter()
+                        .map(escape_key)
                         .collect::<Vec<_>>()
                         .join(", ")
                 ),
                 Date(value) => value.to_iso_string(),
+                Record(value) => {
+                    let record = value.borrow();
+
+                    format!(
+                        "{}({})",
+                        record.name,
+                        record
+                            .fields
+                            .iter()
+                            .map(|(k, v)| format!("{}: {}", k, escape_value(v)))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                }
+                Enum(value) => format!("<enumeração {}>", value.name),
+                EnumVariant(value) => value.to_string(),
+                Module(value) => format!("<módulo {}>", value.path),
             }
         )
     }
@@ -124,29 +231,12 @@ impl From<Literal> for Value {
 
         match literal {
             Number(value) => Value::Number(value),
+            Integer(value) => Value::Integer(value),
+            Decimal(value) => Value::Decimal(value),
             String(value) => Value::String(value),
             Boolean(value) => Value::Boolean(value),
             Nil => Value::Nil,
-        }
-    }
-}
-
-impl IntoIterator for Value {
-    type Item = Value;
-    type IntoIter = std::vec::IntoIter<Value>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        if !self.is_iterable() {
-            panic!("value is not iterable");
-        }
-
-        match self {
-            Value::List(list) => list.borrow_mut().clone().into_iter(),
-            Value::Range(start, end) => (start..=end)
-                .map(|i| Value::Number(i as f64))
-                .collect::<Vec<_>>()
-                .into_iter(),
-            _ => unreachable!(),
+            Interpolation(_) => unreachable!("interpolated strings are parsed as expressions"),
         }
     }
 }
@@ -154,14 +244,22 @@ impl IntoIterator for Value {
 #[derive(Debug, PartialEq, Clone)]
 pub enum ValueType {
     Number,
+    Integer,
+    Decimal,
     Boolean,
     String,
     Function,
+    Generator,
     List,
     Range,
     Nil,
     AssociativeArray,
+    Set,
     Date,
+    Record,
+    Enum,
+    EnumVariant,
+    Module,
 }
 
 impl From<Value> for ValueType {
@@ -176,13 +274,21 @@ impl Display for ValueType {
 
         let str = match self {
             Number => "número".to_string(),
+            Integer => "inteiro".to_string(),
+            Decimal => "decimal".to_string(),
             Boolean => "lógico".to_string(),
             String => "texto".to_string(),
             Function => "função".to_string(),
+            Generator => "gerador".to_string(),
             List => "lista".to_string(),
             Range => "intervalo".to_string(),
             AssociativeArray => "dicionário".to_string(),
+            Set => "conjunto".to_string(),
             Date => "data".to_string(),
+            Record => "estrutura".to_string(),
+            Enum => "enumeração".to_string(),
+            EnumVariant => "variante".to_string(),
+            Module => "módulo".to_string(),
             Nil => "Nada".to_string(),
         };
 
@@ -190,6 +296,79 @@ impl Display for ValueType {
     }
 }
 
+/// Compares two values the way `é` does. Unlike `==`, numbers of different
+/// types are equal when they hold the same quantity, also inside lists,
+/// dictionaries and records.
+pub fn values_equal(lhs: &Value, rhs: &Value) -> bool {
+    use Value::*;
+
+    match (lhs, rhs) {
+        (Integer(lhs), Number(rhs)) | (Number(rhs), Integer(lhs)) => {
+            rhs.fract() == 0.0 && BigInt::from_f64(*rhs).as_ref() == Some(lhs)
+        }
+        (Decimal(lhs), Integer(rhs)) | (Integer(rhs), Decimal(lhs)) => {
+            decimal_from_integer(rhs) == Some(*lhs)
+        }
+        (Decimal(lhs), Number(rhs)) | (Number(rhs), Decimal(lhs)) => {
+            decimal_from_f64(*rhs) == Some(*lhs)
+        }
+        (List(lhs), List(rhs)) => {
+            let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+
+            lhs.len() == rhs.len() && lhs.iter().zip(rhs.iter()).all(|(l, r)| values_equal(l, r))
+        }
+        (AssociativeArray(lhs), AssociativeArray(rhs)) => {
+            let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+
+            lhs.len() == rhs.len()
+                && lhs
+                    .iter()
+                    .all(|(key, l)| rhs.get(key).is_some_and(|r| values_equal(l, r)))
+        }
+        (Record(lhs), Record(rhs)) => {
+            let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+
+            lhs.name == rhs.name
+                && lhs.methods == rhs.methods
+                && lhs.fields.len() == rhs.fields.len()
+                && lhs
+                    .fields
+                    .iter()
+                    .all(|(field, l)| rhs.get(field).is_some_and(|r| values_equal(l, r)))
+        }
+        (lhs, rhs) => lhs == rhs,
+    }
+}
+
+/// Remainder of a floored division, used by `%` and `Matemática.resto`. It
+/// takes the sign of the divisor, so `-10 % 3` is `2` and agrees with `\`,
+/// which rounds the quotient down.
+pub fn floored_remainder(lhs: f64, rhs: f64) -> f64 {
+    let rem = lhs % rhs;
+
+    if rem != 0.0 && (rem < 0.0) != (rhs < 0.0) {
+        rem + rhs
+    } else {
+        rem
+    }
+}
+
+/// Converts an integer to a decimal, failing when it does not fit in the
+/// decimal's 96-bit mantissa.
+pub fn decimal_from_integer(value: &BigInt) -> Option<Decimal> {
+    Decimal::from_str(&value.to_string()).ok()
+}
+
+/// Converts a float to the decimal with the same shortest textual
+/// representation, so `0.1` becomes exactly `0.1`.
+pub fn decimal_from_f64(value: f64) -> Option<Decimal> {
+    if !value.is_finite() {
+        return None;
+    }
+
+    Decimal::from_str(&value.to_string()).ok()
+}
+
 pub fn escape_special_chars(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
 
@@ -220,6 +399,20 @@ pub fn escape_special_chars(s: &str) -> String {
     result
 }
 
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_string(),
+        _ => value.to_string(),
+    }
+}
+
+fn escape_key(key: &AssociativeArrayKey) -> String {
+    match key {
+        AssociativeArrayKey::String(key) => format!("\"{}\"", key),
+        key => key.to_string(),
+    }
+}
+
 pub fn escape_value(value: &Value) -> String {
     match value {
         Value::String(s) => format!("\"{}\"", escape_special_chars(s)),
@@ -1,8 +1,14 @@
-use tenda_common::source::IdentifiedSource;
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+use tenda_common::{
+    source::IdentifiedSource,
+    span::{SourceSpan, Span},
+};
+use tenda_reporting::Diagnostic;
 
 use crate::scanner_error::LexicalError;
 use crate::source_iter::SourceIter;
-use crate::token::{Literal, Token, TokenKind};
+use crate::token::{Literal, StringPart, Token, TokenKind};
 use std::char;
 
 pub struct Scanner<'a> {
@@ -78,23 +84,124 @@ impl<'a> Scanner<'a> {
             '{' => self.source.consume_token(TokenKind::LeftBrace, "{").into(),
             '}' => self.source.consume_token(TokenKind::RightBrace, "}").into(),
             ':' => self.source.consume_token(TokenKind::Colon, ":").into(),
-            '+' => self.source.consume_token(TokenKind::Plus, "+").into(),
-            '-' => {
-                if let Some('>') = self.source.peek() {
+            '+' => match self.source.peek() {
+                Some('=') => {
+                    self.source.next();
+                    self.source.consume_token(TokenKind::PlusEqual, "+=").into()
+                }
+                _ => self.source.consume_token(TokenKind::Plus, "+").into(),
+            },
+            '-' => match self.source.peek() {
+                Some('>') => {
                     self.source.next();
                     self.source.consume_token(TokenKind::Arrow, "->").into()
-                } else {
-                    self.source.consume_token(TokenKind::Minus, "-").into()
                 }
-            }
-            '*' => self.source.consume_token(TokenKind::Star, "*").into(),
-            '^' => self.source.consume_token(TokenKind::Caret, "^").into(),
-            '%' => self.source.consume_token(TokenKind::Percent, "%").into(),
+                Some('=') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::MinusEqual, "-=")
+                        .into()
+                }
+                _ => self.source.consume_token(TokenKind::Minus, "-").into(),
+            },
+            '*' => match self.source.peek() {
+                Some('=') => {
+                    self.source.next();
+                    self.source.consume_token(TokenKind::StarEqual, "*=").into()
+                }
+                _ => self.source.consume_token(TokenKind::Star, "*").into(),
+            },
+            '^' => match self.source.peek() {
+                Some('=') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::CaretEqual, "^=")
+                        .into()
+                }
+                _ => self.source.consume_token(TokenKind::Caret, "^").into(),
+            },
+            '%' => match self.source.peek() {
+                Some('=') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::PercentEqual, "%=")
+                        .into()
+                }
+                _ => self.source.consume_token(TokenKind::Percent, "%").into(),
+            },
+            '\\' => match self.source.peek() {
+                Some('=') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::BackslashEqual, "\\=")
+                        .into()
+                }
+                _ => self.source.consume_token(TokenKind::Backslash, "\\").into(),
+            },
+            '&' => self.source.consume_token(TokenKind::Ampersand, "&").into(),
+            '|' => match self.source.peek() {
+                Some('>') => {
+                    self.source.next();
+                    self.source.consume_token(TokenKind::Pipeline, "|>").into()
+                }
+                _ => self.source.consume_token(TokenKind::Pipe, "|").into(),
+            },
+            '~' => self.source.consume_token(TokenKind::Tilde, "~").into(),
+            '?' => match self.source.peek() {
+                Some('?') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::DoubleQuestion, "??")
+                        .into()
+                }
+                Some('.') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::QuestionDot, "?.")
+                        .into()
+                }
+                Some('[') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::QuestionBracket, "?[")
+                        .into()
+                }
+                _ => Err(LexicalError::UnexpectedChar {
+                    character: '?',
+                    span: self.source.consume_span(),
+                }),
+            },
             '=' => self.source.consume_token(TokenKind::EqualSign, "=").into(),
-            '"' => self.consume_string(char).map(Some),
+            '"' => self.consume_string(false).map(Some),
+            'r' if self.source.peek() == Some(&'"') => {
+                self.source.next();
+                self.consume_string(true).map(Some)
+            }
             ',' => self.source.consume_token(TokenKind::Comma, ",").into(),
-            '.' => self.source.consume_token(TokenKind::Dot, ".").into(),
+            '.' => match self.source.peek() {
+                Some('.') => {
+                    self.source.next();
+
+                    match self.source.peek() {
+                        Some('.') => {
+                            self.source.next();
+                            self.source.consume_token(TokenKind::Ellipsis, "...").into()
+                        }
+                        _ => Err(LexicalError::UnexpectedChar {
+                            character: '.',
+                            span: self.source.consume_span(),
+                        }),
+                    }
+                }
+                _ => self.source.consume_token(TokenKind::Dot, ".").into(),
+            },
             '>' => match self.source.peek() {
+                Some('>') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::ShiftRight, ">>")
+                        .into()
+                }
                 Some('=') => {
                     self.source.next();
                     self.source
@@ -104,6 +211,10 @@ impl<'a> Scanner<'a> {
                 _ => self.source.consume_token(TokenKind::Greater, ">").into(),
             },
             '<' => match self.source.peek() {
+                Some('<') => {
+                    self.source.next();
+                    self.source.consume_token(TokenKind::ShiftLeft, "<<").into()
+                }
                 Some('=') => {
                     self.source.next();
                     self.source
@@ -123,6 +234,12 @@ impl<'a> Scanner<'a> {
                     self.consume_multiline_comment();
                     Ok(None)
                 }
+                Some('=') => {
+                    self.source.next();
+                    self.source
+                        .consume_token(TokenKind::SlashEqual, "/=")
+                        .into()
+                }
                 _ => self.source.consume_token(TokenKind::Slash, "/").into(),
             },
             _ => Err(LexicalError::UnexpectedChar {
@@ -132,83 +249,84 @@ impl<'a> Scanner<'a> {
         }
     }
 
-    fn consume_string(&mut self, first_quote: char) -> Result<Token, LexicalError> {
+    /// Reads a text literal after its opening quote. Raw literals (`r"..."`)
+    /// keep backslashes and braces as written. Literals delimited by `"""`
+    /// may span several lines: a blank first and last line are dropped, and
+    /// the indentation shared by all lines is removed.
+    fn consume_string(&mut self, raw: bool) -> Result<Token, LexicalError> {
+        if self.source.lookahead().take(2).eq(['"', '"']) {
+            self.source.next();
+            self.source.next();
+
+            let opening_end = self.source.position();
+            let on_own_line = self.skip_blank_line();
+            let indent = self.multiline_indent(raw, on_own_line);
+
+            if on_own_line {
+                self.skip_indentation(indent);
+            }
+
+            return self.consume_string_contents(raw, Some((opening_end, indent)));
+        }
+
+        self.consume_string_contents(raw, None)
+    }
+
+    fn consume_string_contents(
+        &mut self,
+        raw: bool,
+        multiline: Option<(usize, usize)>,
+    ) -> Result<Token, LexicalError> {
         let mut buf = String::new();
+        let mut parts: Vec<StringPart> = vec![];
+        let mut lexeme = String::new();
         let mut closed = false;
 
-        buf.push(first_quote);
-
         while let Some(&ch) = self.source.peek() {
             match ch {
-                '"' => {
+                '"' if multiline.is_none() => {
                     self.source.next();
                     closed = true;
                     break;
                 }
-                '\n' => {
-                    return Err(LexicalError::UnexpectedStringEol {
-                        span: self.source.consume_span(),
-                    });
+                '"' if self.is_multiline_delimiter() => {
+                    self.consume_multiline_delimiter();
+                    closed = true;
+                    break;
                 }
-                '\\' => {
+                '{' if !raw => {
                     self.source.next();
 
-                    let esc = self
-                        .source
-                        .next()
-                        .ok_or(LexicalError::UnexpectedStringEol {
-                            span: self.source.consume_span(),
-                        })?;
-
-                    let resolved = match esc {
-                        '0' => Some('\0'),
-                        'a' => Some('\x07'),
-                        'b' => Some('\x08'),
-                        'e' => Some('\x1B'),
-                        'f' => Some('\x0C'),
-                        'n' => Some('\n'),
-                        'r' => Some('\r'),
-                        't' => Some('\t'),
-                        'v' => Some('\x0B'),
-                        '\\' => Some('\\'),
-                        '\'' => Some('\''),
-                        '"' => Some('"'),
-                        'x' => {
-                            let hi = self.read_hex_digit()?;
-                            let lo = self.read_hex_digit()?;
-                            Some(char::from(
-                                u8::from_str_radix(&format!("{hi}{lo}"), 16).unwrap(),
-                            ))
-                        }
-                        'u' => {
-                            let code = self.read_n_hex(4)?;
-                            char::from_u32(code)
-                        }
-                        'U' => {
-                            let code = self.read_n_hex(8)?;
-                            char::from_u32(code)
-                        }
-                        d @ '1'..='7' => {
-                            let d2 = self.read_octal_digit()?;
-                            let d3 = self.read_octal_digit()?;
-                            let val = u8::from_str_radix(&format!("{d}{d2}{d3}"), 8).unwrap();
-                            Some(char::from(val))
-                        }
-                        _ => {
-                            return Err(LexicalError::UnknownEscape {
-                                span: self.source.consume_span(),
-                                found: esc,
-                            })
-                        }
-                    };
+                    lexeme.push_str(&buf);
+                    parts.push(StringPart::Text(std::mem::take(&mut buf)));
+
+                    let (tokens, raw) = self.consume_interpolation()?;
 
-                    if let Some(c) = resolved {
-                        buf.push(c);
-                    } else {
-                        return Err(LexicalError::InvalidUnicodeEscape {
+                    lexeme.push_str(&format!("{{{}}}", raw));
+                    parts.push(StringPart::Expr(tokens));
+                }
+                '\n' => {
+                    let Some((_, indent)) = multiline else {
+                        return Err(LexicalError::UnexpectedStringEol {
                             span: self.source.consume_span(),
                         });
+                    };
+
+                    self.source.next();
+
+                    if self.is_closing_line() {
+                        self.skip_indentation(usize::MAX);
+                        self.consume_multiline_delimiter();
+                        closed = true;
+                        break;
                     }
+
+                    buf.push('\n');
+                    self.skip_indentation(indent);
+                }
+                '\\' if !raw => {
+                    self.source.next();
+                    buf.push(self.consume_escape()?);
                 }
                 _ => {
                     buf.push(ch);
@@ -218,20 +336,244 @@ impl<'a> Scanner<'a> {
         }
 
         if !closed {
-            return Err(LexicalError::UnexpectedStringEol {
-                span: self.source.consume_span(),
+            let span = self.source.consume_span();
+
+            return Err(match multiline {
+                Some((opening_end, _)) => LexicalError::UnclosedMultilineString {
+                    span: SourceSpan::new(span.start(), opening_end, span.source()),
+                },
+                None => LexicalError::UnexpectedStringEol { span },
             });
         }
 
-        let literal = buf[1..].to_owned();
+        if !parts.is_empty() {
+            lexeme.push_str(&buf);
+            parts.push(StringPart::Text(buf));
+
+            return Ok(self.source.consume_token_with_literal(
+                TokenKind::InterpolatedString,
+                lexeme,
+                Literal::Interpolation(parts),
+            ));
+        }
 
         Ok(self.source.consume_token_with_literal(
             TokenKind::String,
-            literal.clone(),
-            Literal::String(literal),
+            buf.clone(),
+            Literal::String(buf),
         ))
     }
 
+    fn consume_escape(&mut self) -> Result<char, LexicalError> {
+        let esc = self
+            .source
+            .next()
+            .ok_or(LexicalError::UnexpectedStringEol {
+                span: self.source.consume_span(),
+            })?;
+
+        let resolved = match esc {
+            '0' => Some('\0'),
+            'a' => Some('\x07'),
+            'b' => Some('\x08'),
+            'e' => Some('\x1B'),
+            'f' => Some('\x0C'),
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            'v' => Some('\x0B'),
+            '\\' => Some('\\'),
+            '\'' => Some('\''),
+            '"' => Some('"'),
+            '{' => Some('{'),
+            '}' => Some('}'),
+            'x' => {
+                let hi = self.read_hex_digit()?;
+                let lo = self.read_hex_digit()?;
+                Some(char::from(
+                    u8::from_str_radix(&format!("{hi}{lo}"), 16).unwrap(),
+                ))
+            }
+            'u' => {
+                let code = self.read_n_hex(4)?;
+                char::from_u32(code)
+            }
+            'U' => {
+                let code = self.read_n_hex(8)?;
+                char::from_u32(code)
+            }
+            d @ '1'..='7' => {
+                let d2 = self.read_octal_digit()?;
+                let d3 = self.read_octal_digit()?;
+                let val = u8::from_str_radix(&format!("{d}{d2}{d3}"), 8).unwrap();
+                Some(char::from(val))
+            }
+            _ => {
+                return Err(LexicalError::UnknownEscape {
+                    span: self.source.consume_span(),
+                    found: esc,
+                })
+            }
+        };
+
+        resolved.ok_or(LexicalError::InvalidUnicodeEscape {
+            span: self.source.consume_span(),
+        })
+    }
+
+    fn is_multiline_delimiter(&self) -> bool {
+        self.source.lookahead().take(3).eq(['"', '"', '"'])
+    }
+
+    fn consume_multiline_delimiter(&mut self) {
+        for _ in 0..3 {
+            self.source.next();
+        }
+    }
+
+    /// Whether the rest of the line is only indentation followed by `"""`.
+    fn is_closing_line(&self) -> bool {
+        let mut chars = self.source.lookahead();
+
+        while chars.next_if(|c| matches!(c, ' ' | '\t')).is_some() {}
+
+        chars.take(3).eq(['"', '"', '"'])
+    }
+
+    /// Consumes the rest of the line, including the newline, if it is blank.
+    fn skip_blank_line(&mut self) -> bool {
+        let mut chars = self.source.lookahead();
+
+        while chars.next_if(|c| matches!(c, ' ' | '\t')).is_some() {}
+
+        if chars.next() != Some('\n') {
+            return false;
+        }
+
+        while self.source.next() != Some('\n') {}
+
+        true
+    }
+
+    fn skip_indentation(&mut self, indent: usize) {
+        for _ in 0..indent {
+            match self.source.peek() {
+                Some(' ' | '\t') => self.source.next(),
+                _ => break,
+            };
+        }
+    }
+
+    /// Finds the smallest indentation among the non-blank lines of a
+    /// multi-line literal, without consuming it. The line holding the
+    /// opening quotes only counts if the text starts on a line of its own.
+    fn multiline_indent(&self, raw: bool, at_line_start: bool) -> usize {
+        let mut chars = self.source.lookahead();
+        let mut indent: Option<usize> = None;
+        let mut line_indent = 0;
+        let mut blank = at_line_start;
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '"' if chars.clone().take(2).eq(['"', '"']) => break,
+                '\n' => {
+                    line_indent = 0;
+                    blank = true;
+                }
+                ' ' | '\t' if blank => line_indent += 1,
+                _ => {
+                    if blank {
+                        indent = Some(indent.map_or(line_indent, |i| i.min(line_indent)));
+                        blank = false;
+                    }
+
+                    if ch == '\\' && !raw {
+                        chars.next();
+                    }
+                }
+            }
+        }
+
+        indent.unwrap_or(0)
+    }
+
+    fn consume_interpolation(&mut self) -> Result<(Vec<Token>, String), LexicalError> {
+        let offset = self.source.position();
+        let source_id = self.source.source_id();
+
+        let mut raw = String::new();
+        let mut depth = 1;
+        let mut in_string = false;
+
+        loop {
+            let ch = match self.source.next() {
+                Some('\n') | None => {
+                    return Err(LexicalError::UnexpectedStringEol {
+                        span: self.source.consume_span(),
+                    })
+                }
+                Some(ch) => ch,
+            };
+
+            match ch {
+                '\\' if in_string => {
+                    raw.push(ch);
+
+                    if let Some(escaped) = self.source.next() {
+                        raw.push(escaped);
+                    }
+
+                    continue;
+                }
+                '"' => in_string = !in_string,
+                '{' if !in_string => depth += 1,
+                '}' if !in_string => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+
+            raw.push(ch);
+        }
+
+        if raw.trim().is_empty() {
+            return Err(LexicalError::EmptyInterpolation {
+                span: SourceSpan::new(offset - 1, self.source.position(), source_id),
+                help: Some(
+                    "para escrever chaves literais em um texto, use `\\{` e `\\}`".to_string(),
+                ),
+            });
+        }
+
+        let shift = |span: &SourceSpan| {
+            SourceSpan::new(span.start() + offset, span.end() + offset, source_id)
+        };
+
+        let tokens = Scanner::new(&raw, source_id).scan().map_err(|errs| {
+            let mut err = errs.into_iter().next().unwrap();
+
+            if let Some(span) = err.get_span() {
+                err.set_span(&shift(&span));
+            }
+
+            err
+        })?;
+
+        let tokens = tokens
+            .into_iter()
+            .map(|mut token| {
+                token.span = shift(&token.span);
+                token
+            })
+            .collect();
+
+        Ok((tokens, raw))
+    }
+
     fn consume_number(&mut self, first: char) -> Result<Token, LexicalError> {
         let mut raw = String::new();
         raw.push(first);
@@ -273,12 +615,12 @@ impl<'a> Scanner<'a> {
                             });
                         }
 
-                        let value = u64::from_str_radix(&digits, radix).unwrap() as f64;
+                        let value = BigInt::parse_bytes(digits.as_bytes(), radix).unwrap();
 
                         return Ok(self.source.consume_token_with_literal(
                             TokenKind::Number,
                             raw,
-                            Literal::Number(value),
+                            Literal::Integer(value),
                         ));
                     }
                     _ => (),
@@ -288,6 +630,7 @@ impl<'a> Scanner<'a> {
 
         let mut matched_dot = first == '.';
         let mut matched_exp = false;
+        let mut matched_decimal_suffix = false;
 
         while let Some(&ch) = self.source.peek() {
             match ch {
@@ -314,6 +657,21 @@ impl<'a> Scanner<'a> {
                         self.source.next();
                     }
                 }
+                'd' => {
+                    matched_decimal_suffix = true;
+                    raw.push(ch);
+                    self.source.next();
+
+                    match self.source.peek() {
+                        Some(&c) if c.is_alphanumeric() || c == '_' => {
+                            return Err(LexicalError::UnexpectedChar {
+                                character: c,
+                                span: self.source.consume_span(),
+                            });
+                        }
+                        _ => break,
+                    }
+                }
                 c if c.is_alphabetic() => {
                     return Err(LexicalError::UnexpectedChar {
                         character: c,
@@ -325,12 +683,32 @@ impl<'a> Scanner<'a> {
             }
         }
 
-        let cleaned: String = raw.chars().filter(|c| *c != '_').collect();
-        let value: f64 = cleaned.parse().unwrap();
+        let cleaned: String = raw.chars().filter(|c| *c != '_' && *c != 'd').collect();
+
+        let literal = match (matched_decimal_suffix, matched_dot || matched_exp) {
+            (true, _) => {
+                let value = match matched_exp {
+                    true => Decimal::from_scientific(&cleaned),
+                    false => Decimal::from_str_exact(&cleaned),
+                };
+
+                match value {
+                    Ok(value) => Literal::Decimal(value),
+                    Err(_) => {
+                        return Err(LexicalError::DecimalLiteralOutOfRange {
+                            literal: raw,
+                            span: self.source.consume_span(),
+                        })
+                    }
+                }
+            }
+            (false, true) => Literal::Number(cleaned.parse().unwrap()),
+            (false, false) => Literal::Integer(cleaned.parse().unwrap()),
+        };
 
         Ok(self
             .source
-            .consume_token_with_literal(TokenKind::Number, raw, Literal::Number(value)))
+            .consume_token_with_literal(TokenKind::Number, raw, literal))
     }
 
     fn consume_identifier(&mut self, char: char) -> Result<Token, LexicalError> {
@@ -367,6 +745,7 @@ impl<'a> Scanner<'a> {
             "não" => self.source.consume_token(TokenKind::Not, "não"),
             "é" => self.source.consume_token(TokenKind::Equals, "é"),
             "seja" => self.source.consume_token(TokenKind::Let, "seja"),
+            "constante" => self.source.consume_token(TokenKind::Const, "constante"),
             "se" => self.source.consume_token(TokenKind::If, "se"),
             "então" => self.source.consume_token(TokenKind::Then, "então"),
             "retorna" => self.source.consume_token(TokenKind::Return, "retorna"),
@@ -375,6 +754,8 @@ impl<'a> Scanner<'a> {
             "ou" => self.source.consume_token(TokenKind::Or, "ou"),
             "e" => self.source.consume_token(TokenKind::And, "e"),
             "até" => self.source.consume_token(TokenKind::Until, "até"),
+            "exclusivo" => self.source.consume_token(TokenKind::Exclusive, "exclusivo"),
+            "passo" => self.source.consume_token(TokenKind::Step, "passo"),
             "para" => self.source.consume_token(TokenKind::ForOrBreak, "para"),
             "cada" => self.source.consume_token(TokenKind::Each, "cada"),
             "em" => self.source.consume_token(TokenKind::In, "em"),
@@ -382,6 +763,19 @@ impl<'a> Scanner<'a> {
             "enquanto" => self.source.consume_token(TokenKind::While, "enquanto"),
             "faça" => self.source.consume_token(TokenKind::Do, "faça"),
             "continua" => self.source.consume_token(TokenKind::Continue, "continua"),
+            "tente" => self.source.consume_token(TokenKind::Try, "tente"),
+            "capture" => self.source.consume_token(TokenKind::Catch, "capture"),
+            "finalmente" => self.source.consume_token(TokenKind::Finally, "finalmente"),
+            "lance" => self.source.consume_token(TokenKind::Throw, "lance"),
+            "produza" => self.source.consume_token(TokenKind::Yield, "produza"),
+            "importe" => self.source.consume_token(TokenKind::Import, "importe"),
+            "como" => self.source.consume_token(TokenKind::As, "como"),
+            "estrutura" => self.source.consume_token(TokenKind::Record, "estrutura"),
+            "enumeração" => self.source.consume_token(TokenKind::Enum, "enumeração"),
+            "classe" => self.source.consume_token(TokenKind::Class, "classe"),
+            "este" => self.source.consume_token(TokenKind::This, "este"),
+            "escolha" => self.source.consume_token(TokenKind::Match, "escolha"),
+            "caso" => self.source.consume_token(TokenKind::Case, "caso"),
             identifier => self.source.consume_token_with_literal(
                 TokenKind::Identifier,
                 identifier.to_string(),
@@ -11,12 +11,26 @@ pub enum LexicalError {
         span: SourceSpan,
     },
 
+    #[error("literal decimal fora dos limites: {}", .literal)]
+    DecimalLiteralOutOfRange {
+        literal: String,
+
+        #[span]
+        span: SourceSpan,
+    },
+
     #[error("fim de linha inesperado em texto")]
     UnexpectedStringEol {
         #[span]
         span: SourceSpan,
     },
 
+    #[error("texto de múltiplas linhas não foi fechado com '\"\"\"'")]
+    UnclosedMultilineString {
+        #[span]
+        span: SourceSpan,
+    },
+
     #[error("caractere inesperado: {}", .character)]
     UnexpectedChar {
         character: char,
@@ -48,6 +62,15 @@ pub enum LexicalError {
         span: SourceSpan,
     },
 
+    #[error("interpolação vazia em texto")]
+    EmptyInterpolation {
+        #[span]
+        span: SourceSpan,
+
+        #[help]
+        help: Option<String>,
+    },
+
     #[error("escape não reconhecido: {}", .found)]
     UnknownEscape {
         #[span]
@@ -50,9 +50,23 @@ impl<'a> SourceIter<'a> {
         self.start_position = self.end_position;
     }
 
+    pub fn position(&self) -> usize {
+        self.end_position
+    }
+
+    pub fn source_id(&self) -> IdentifiedSource {
+        self.source_id
+    }
+
     pub fn peek(&mut self) -> Option<&char> {
         self.iter.peek()
     }
+
+    /// The characters that are yet to be consumed, for lookahead beyond the
+    /// next one.
+    pub fn lookahead(&self) -> Peekable<Chars<'a>> {
+        self.iter.clone()
+    }
 }
 
 impl Iterator for SourceIter<'_> {
@@ -1,3 +1,5 @@
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
 use tenda_common::span::SourceSpan;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -54,6 +56,7 @@ pub enum TokenKind {
     Less,
     LessOrEqual,
     Let,
+    Const,
     If,
     Function,
     Then,
@@ -63,9 +66,25 @@ pub enum TokenKind {
     While,
     Do,
     Continue,
+    Try,
+    Catch,
+    Finally,
+    Throw,
+    Yield,
+    Import,
+    As,
+    Record,
+    Enum,
+    Class,
+    This,
+    Match,
+    Case,
+    InterpolatedString,
     Identifier,
     EqualSign,
     Until,
+    Exclusive,
+    Step,
     ForOrBreak,
     Each,
     In,
@@ -77,6 +96,20 @@ pub enum TokenKind {
     Slash,
     Percent,
     Caret,
+    Backslash,
+    Ampersand,
+    Pipe,
+    Pipeline,
+    Tilde,
+    ShiftLeft,
+    ShiftRight,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PercentEqual,
+    CaretEqual,
+    BackslashEqual,
     LeftParen,
     RightParen,
     LeftBracket,
@@ -85,7 +118,11 @@ pub enum TokenKind {
     RightBrace,
     Comma,
     Dot,
+    Ellipsis,
     Arrow,
+    DoubleQuestion,
+    QuestionDot,
+    QuestionBracket,
     Newline,
     Eof,
 }
@@ -93,11 +130,20 @@ pub enum TokenKind {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
     Number(f64),
+    Integer(BigInt),
+    Decimal(Decimal),
     String(String),
     Boolean(bool),
+    Interpolation(Vec<StringPart>),
     Nil,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringPart {
+    Text(String),
+    Expr(Vec<Token>),
+}
+
 impl Literal {
     pub const TRUE_LITERAL: &'static str = "verdadeiro";
     pub const FALSE_LITERAL: &'static str = "falso";
@@ -39,6 +39,7 @@ impl tenda_runtime::Platform for Platform {
             fn remove_dir(&self, path: &str) -> Result<(), tenda_runtime::FileErrorKind>;
             fn list_dirs(&self, path: &str) -> Result<Vec<String>, tenda_runtime::FileErrorKind>;
             fn current_dir(&self) -> Result<String, tenda_runtime::FileErrorKind>;
+            fn canonicalize(&self, path: &str) -> Result<String, tenda_runtime::FileErrorKind>;
             fn file_append(&self, path: &str, content: &str) -> Result<(), tenda_runtime::FileErrorKind>;
             fn args(&self) -> Vec<String>;
             fn exit(&self, code: i32);
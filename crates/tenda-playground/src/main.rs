@@ -138,10 +138,10 @@ fn main() -> io::Result<()> {
                 ));
             }
             Err(err) => {
-                send_diagnostic(vec![(
-                    *err,
-                    tenda_core::reporting::sources(source_history.clone()),
-                )]);
+                let mut sources = source_history.clone();
+                sources.extend(runtime.get_module_sources().iter().cloned());
+
+                send_diagnostic(vec![(*err, tenda_core::reporting::sources(sources))]);
             }
         }
     }
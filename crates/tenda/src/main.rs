@@ -187,7 +187,10 @@ fn start_repl() {
                 match runtime.eval(&ast) {
                     Ok(result) => println!("{}", escape_value(&result)),
                     Err(err) => {
-                        let caches = tenda_core::reporting::sources(source_history.clone());
+                        let mut sources = source_history.clone();
+                        sources.extend(runtime.get_module_sources().iter().cloned());
+
+                        let caches = tenda_core::reporting::sources(sources);
                         err.to_report().eprint(caches).unwrap();
                     }
                 }
@@ -262,7 +265,11 @@ fn run_source(source: &str, name: &'static str) {
     setup_runtime_prelude(runtime.get_global_env_mut());
 
     if let Err(err) = runtime.eval(&ast) {
-        err.to_report().eprint(cache.clone()).unwrap();
+        let mut sources: Vec<(IdentifiedSource, Rc<str>)> = vec![(source_id, Rc::from(source))];
+        sources.extend(runtime.get_module_sources().iter().cloned());
+
+        let caches = tenda_core::reporting::sources(sources);
+        err.to_report().eprint(caches).unwrap();
 
         println!(
             "\n{} programa encerrado devido a um erro durante a execução",
@@ -1,10 +1,14 @@
+use std::rc::Rc;
+
 use rstest::rstest;
 use tenda_core::{
     platform::OSPlatform,
-    runtime::{Platform, Value},
+    runtime::{Function, FunctionObject, Platform, Value},
 };
 
-use crate::{interpret_expr, interpret_stmt, interpret_stmt_and_get};
+use crate::{
+    interpret_expr, interpret_expr_with_prelude, interpret_stmt, interpret_stmt_and_get, list,
+};
 
 #[rstest]
 #[case(OSPlatform)]
@@ -21,7 +25,7 @@ fn if_statement(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(1.0)
+        Value::Integer(1.into())
     );
 }
 
@@ -39,7 +43,7 @@ fn for_loop_with_list(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(15.0)
+        Value::Integer(15.into())
     );
 }
 
@@ -56,7 +60,7 @@ fn for_loop_with_range(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(15.0)
+        Value::Integer(15.into())
     );
 }
 
@@ -75,7 +79,7 @@ fn while_loop(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(15.0)
+        Value::Integer(15.into())
     );
 }
 
@@ -92,7 +96,7 @@ fn function_definition_and_call(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(5.0)
+        Value::Integer(5.into())
     );
 }
 
@@ -114,7 +118,7 @@ fn closures(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(5.0)
+        Value::Integer(5.into())
     );
 }
 
@@ -140,7 +144,7 @@ fn nested_closures(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(24.0)
+        Value::Integer(24.into())
     );
 }
 
@@ -153,7 +157,7 @@ fn anonymous_function(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(5.0)
+        Value::Integer(5.into())
     );
 }
 
@@ -167,7 +171,7 @@ fn list_access(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(2.0)
+        Value::Integer(2.into())
     );
 }
 
@@ -184,7 +188,7 @@ fn list_mutation(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(10.0)
+        Value::Integer(10.into())
     );
 }
 
@@ -198,7 +202,7 @@ fn associative_array_access(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(3.0)
+        Value::Integer(3.into())
     );
 }
 
@@ -216,7 +220,7 @@ fn associative_array_mutation(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(30.0)
+        Value::Integer(30.into())
     );
 }
 
@@ -237,7 +241,7 @@ fn for_loop_break(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(10.0)
+        Value::Integer(10.into())
     );
 }
 
@@ -258,7 +262,7 @@ fn for_loop_continue(#[case] platform: impl Platform + 'static) {
 
     assert_eq!(
         interpret_stmt_and_get(platform, source, "resultado"),
-        Value::Number(50.0)
+        Value::Integer(50.into())
     );
 }
 
@@ -402,7 +406,7 @@ fn short_circuit_and(#[case] platform: impl Platform + 'static) {
     let runtime = interpret_stmt(platform, source);
     let value = runtime.get_global_env().get("x").unwrap().extract();
 
-    assert_eq!(value, Value::Number(0.0));
+    assert_eq!(value, Value::Integer(0.into()));
 }
 
 #[rstest]
@@ -422,7 +426,7 @@ fn short_circuit_or(#[case] platform: impl Platform + 'static) {
     let runtime = interpret_stmt(platform, source);
     let value = runtime.get_global_env().get("x").unwrap().extract();
 
-    assert_eq!(value, Value::Number(0.0));
+    assert_eq!(value, Value::Integer(0.into()));
 }
 
 #[rstest]
@@ -450,7 +454,7 @@ fn nested_loops_break(#[case] platform: impl Platform + 'static) {
         .unwrap()
         .extract();
 
-    assert_eq!(value, Value::Number(2.0));
+    assert_eq!(value, Value::Integer(2.into()));
 }
 
 #[rstest]
@@ -481,7 +485,7 @@ fn nested_loops_break_outer(#[case] platform: impl Platform + 'static) {
         .unwrap()
         .extract();
 
-    assert_eq!(value, Value::Number(6.0));
+    assert_eq!(value, Value::Integer(6.into()));
 }
 
 #[rstest]
@@ -500,7 +504,7 @@ fn overshadow_local_in_nested_block(#[case] platform: impl Platform + 'static) {
     let runtime = interpret_stmt(platform, source);
     let value = runtime.get_global_env().get("x").unwrap().extract();
 
-    assert_eq!(value, Value::Number(1.0));
+    assert_eq!(value, Value::Integer(1.into()));
 }
 
 #[rstest]
@@ -518,7 +522,7 @@ fn overshadow_function_param(#[case] platform: impl Platform + 'static) {
     let runtime = interpret_stmt(platform, source);
     let value = runtime.get_global_env().get("resultado").unwrap().extract();
 
-    assert_eq!(value, Value::Number(6.0));
+    assert_eq!(value, Value::Integer(6.into()));
 }
 
 #[rstest]
@@ -534,7 +538,7 @@ fn empty_block_in_if(#[case] platform: impl Platform + 'static) {
     let runtime = interpret_stmt(platform, source);
     let value = runtime.get_global_env().get("x").unwrap().extract();
 
-    assert_eq!(value, Value::Number(0.0));
+    assert_eq!(value, Value::Integer(0.into()));
 }
 
 #[rstest]
@@ -566,8 +570,8 @@ fn if_return(#[case] platform: impl Platform + 'static) {
         .unwrap()
         .extract();
 
-    assert_eq!(r1, Value::Number(100.0));
-    assert_eq!(r2, Value::Number(0.0));
+    assert_eq!(r1, Value::Integer(100.into()));
+    assert_eq!(r2, Value::Integer(0.into()));
 }
 
 #[rstest]
@@ -590,8 +594,8 @@ fn else_return(#[case] platform: impl Platform + 'static) {
     let a_val = runtime.get_global_env().get("a").unwrap().extract();
     let c_val = runtime.get_global_env().get("c").unwrap().extract();
 
-    assert_eq!(a_val, Value::Number(999.0));
-    assert_eq!(c_val, Value::Number(-1.0));
+    assert_eq!(a_val, Value::Integer(999.into()));
+    assert_eq!(c_val, Value::Integer((-1).into()));
 }
 
 #[rstest]
@@ -618,7 +622,7 @@ fn closure_sees_updated_var(#[case] platform: impl Platform + 'static) {
     let runtime = interpret_stmt(platform, source);
     let value = runtime.get_global_env().get("resultado").unwrap().extract();
 
-    assert_eq!(value, Value::Number(999.0));
+    assert_eq!(value, Value::Integer(999.into()));
 }
 
 #[rstest]
@@ -674,7 +678,7 @@ fn while_break_in_nested_if(#[case] platform: impl Platform + 'static) {
     let runtime = interpret_stmt(platform, source);
     let val = runtime.get_global_env().get("total").unwrap().extract();
 
-    assert_eq!(val, Value::Number(10.0));
+    assert_eq!(val, Value::Integer(10.into()));
 }
 
 #[rstest]
@@ -691,6 +695,136 @@ fn continue_outside_loop(#[case] platform: impl Platform + 'static) {
     interpret_stmt(platform, "continua");
 }
 
+#[rstest]
+#[case(OSPlatform)]
+fn labeled_break_leaves_outer_loop(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja pares = []
+
+        externo: para cada i em 1 até 3 faça
+            seja j = 0
+
+            enquanto j < 3 faça
+                j += 1
+
+                se i * j é 4 então
+                    para externo
+                fim
+
+                pares = pares + [i * 10 + j]
+            fim
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "pares"),
+        list(
+            [11, 12, 13, 21]
+                .into_iter()
+                .map(|n| Value::Integer(n.into()))
+                .collect()
+        )
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn labeled_continue_skips_outer_iteration(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja total = 0
+        seja i = 0
+
+        linhas: enquanto i < 4 faça
+            i += 1
+
+            colunas: para cada j em 1 até 4 faça
+                se j > i então
+                    continua linhas
+                fim
+
+                se j é 2 então
+                    continua colunas
+                fim
+
+                total += j
+            fim
+
+            total += 100
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "total"),
+        Value::Integer(114.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn labeled_break_in_generator(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja diagonais() = faça
+            externo: para cada i em 1 até 5 faça
+                para cada j em 1 até 5 faça
+                    se i + j > 4 então
+                        para externo
+                    fim
+
+                    produza i * 10 + j
+                fim
+            fim
+
+            produza 0
+        fim
+
+        seja resultado = []
+
+        para cada d em diagonais() faça
+            resultado = resultado + [d]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(
+            [11, 12, 13, 0]
+                .into_iter()
+                .map(|n| Value::Integer(n.into()))
+                .collect()
+        )
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn break_with_unknown_label(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        externo: enquanto verdadeiro faça
+            para interno
+        fim
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn label_does_not_cross_functions(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        externo: enquanto verdadeiro faça
+            seja f() = faça
+                enquanto verdadeiro faça
+                    continua externo
+                fim
+            fim
+        fim
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
 #[rstest]
 #[case(OSPlatform)]
 #[should_panic]
@@ -779,8 +913,8 @@ fn overshadow_variable_in_function_block(#[case] platform: impl Platform + 'stat
     let outer_x = runtime.get_global_env().get("x").unwrap().extract();
     let result = runtime.get_global_env().get("resultado").unwrap().extract();
 
-    assert_eq!(outer_x, Value::Number(10.0));
-    assert_eq!(result, Value::Number(999.0));
+    assert_eq!(outer_x, Value::Integer(10.into()));
+    assert_eq!(result, Value::Integer(999.into()));
 }
 
 #[rstest]
@@ -798,7 +932,7 @@ fn overshadow_parameter_in_function_block(#[case] platform: impl Platform + 'sta
     let runtime = interpret_stmt(platform, source);
     let val = runtime.get_global_env().get("resultado").unwrap().extract();
 
-    assert_eq!(val, Value::Number(10.0));
+    assert_eq!(val, Value::Integer(10.into()));
 }
 
 #[rstest]
@@ -821,8 +955,8 @@ fn partial_return_in_if(#[case] platform: impl Platform + 'static) {
     let r1 = runtime.get_global_env().get("r1").unwrap().extract();
     let r2 = runtime.get_global_env().get("r2").unwrap().extract();
 
-    assert_eq!(r1, Value::Number(111.0));
-    assert_eq!(r2, Value::Number(222.0));
+    assert_eq!(r1, Value::Integer(111.into()));
+    assert_eq!(r2, Value::Integer(222.into()));
 }
 
 #[rstest]
@@ -898,7 +1032,7 @@ fn parse_error_continue_outside_loop(#[case] platform: impl Platform + 'static)
 
 #[rstest]
 #[case(OSPlatform)]
-fn zero_or_negative_range_iteration(#[case] platform: impl Platform + 'static) {
+fn descending_range_iteration(#[case] platform: impl Platform + 'static) {
     let source = r#"
         seja soma = 0
 
@@ -910,7 +1044,24 @@ fn zero_or_negative_range_iteration(#[case] platform: impl Platform + 'static) {
     let runtime = interpret_stmt(platform, source);
     let val = runtime.get_global_env().get("soma").unwrap().extract();
 
-    assert_eq!(val, Value::Number(0.0));
+    assert_eq!(val, Value::Integer(15.into()));
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn empty_exclusive_range_iteration(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja soma = 0
+
+        para cada i em 1 até 1 exclusivo faça
+            soma = soma + i
+        fim
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+    let val = runtime.get_global_env().get("soma").unwrap().extract();
+
+    assert_eq!(val, Value::Integer(0.into()));
 }
 
 #[rstest]
@@ -925,3 +1076,2870 @@ fn use_function_argument_outside_function(#[case] platform: impl Platform + 'sta
 
     interpret_stmt(platform, source);
 }
+
+#[rstest]
+#[case(OSPlatform)]
+fn try_catch_runtime_error(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = Nada
+
+        tente
+            seja x = 1 / 0
+            resultado = "não capturado"
+        capture erro
+            resultado = erro.tipo
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("DIVISÃO_POR_ZERO".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn try_catch_error_message(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = Nada
+
+        tente
+            seja lista = [1, 2, 3]
+            lista[10]
+        capture erro
+            resultado = erro.mensagem
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("índice fora dos limites: índice 10, tamanho 3".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn try_catch_error_from_nested_call(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja divide(a, b) = faça
+            se verdadeiro então faça
+                retorna a / b
+            fim
+        fim
+
+        seja calcula(x) = divide(x, 0)
+
+        seja tamanho_pilha = 0
+        seja depois = 0
+
+        tente
+            calcula(10)
+        capture erro
+            tamanho_pilha = erro.pilha
+        fim
+
+        seja y = 5
+        depois = y + 1
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+    let env = runtime.get_global_env();
+
+    match env.get("tamanho_pilha").unwrap().extract() {
+        Value::List(list) => assert_eq!(list.borrow().len(), 3),
+        value => panic!("expected list, found {:?}", value),
+    }
+
+    assert_eq!(
+        env.get("depois").unwrap().extract(),
+        Value::Integer(6.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn try_catch_without_binding(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = 0
+
+        tente
+            resultado = { "a": 1 }.b
+        capture
+            resultado = -1
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer((-1).into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn try_finally_always_runs(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja passos = []
+
+        seja f(x) = faça
+            tente
+                retorna 10 / x
+            capture
+                retorna Nada
+            finalmente
+                passos = passos + [x]
+            fim
+        fim
+
+        seja a = f(2)
+        seja b = f(0)
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+    let env = runtime.get_global_env();
+
+    assert_eq!(env.get("a").unwrap().extract(), Value::Integer(5.into()));
+    assert_eq!(env.get("b").unwrap().extract(), Value::Nil);
+
+    assert_eq!(
+        env.get("passos").unwrap().extract(),
+        list(vec![Value::Integer(2.into()), Value::Integer(0.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn try_break_inside_loop(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = 0
+        seja finalizações = 0
+
+        para cada i em 1 até 10 faça
+            tente
+                se i é 4 então faça
+                    para
+                fim
+
+                resultado = resultado + i
+            finalmente
+                finalizações = finalizações + 1
+            fim
+        fim
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+    let env = runtime.get_global_env();
+
+    assert_eq!(
+        env.get("resultado").unwrap().extract(),
+        Value::Integer(6.into())
+    );
+    assert_eq!(
+        env.get("finalizações").unwrap().extract(),
+        Value::Integer(4.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn try_catch_captured_in_closure(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja obtém_tipo = Nada
+
+        tente
+            1 + verdadeiro
+        capture erro
+            obtém_tipo = função() -> erro.tipo
+        fim
+
+        seja resultado = obtém_tipo()
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("TIPOS_INCOMPATÍVEIS".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn try_finally_propagates_error(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        tente
+            1 / 0
+        finalmente
+            seja x = 1
+        fim
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn try_finally_error_discards_pending_break(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja capturados = 0
+        seja i = 0
+
+        enquanto i < 3 faça
+            i += 1
+
+            tente
+                tente
+                    para
+                finalmente
+                    lance "falhou"
+                fim
+            capture erro
+                capturados += 1
+            fim
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "capturados"),
+        Value::Integer(3.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_try_without_catch_or_finally(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        tente
+            seja x = 1
+        fim
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn throw_and_catch_text(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja tipo = Nada
+        seja mensagem = Nada
+
+        tente
+            lance "idade inválida"
+        capture erro
+            tipo = erro.tipo
+            mensagem = erro.mensagem
+        fim
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+    let env = runtime.get_global_env();
+
+    assert_eq!(
+        env.get("tipo").unwrap().extract(),
+        Value::String("ERRO_LANÇADO".to_string())
+    );
+    assert_eq!(
+        env.get("mensagem").unwrap().extract(),
+        Value::String("idade inválida".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn throw_custom_error_kind(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja valida_idade(idade) = faça
+            se idade < 0 então faça
+                lance { "tipo": "IDADE_NEGATIVA", "mensagem": "idade não pode ser negativa", "idade": idade }
+            fim
+
+            retorna idade
+        fim
+
+        seja resultado = Nada
+
+        tente
+            valida_idade(-3)
+        capture erro
+            resultado = [erro.tipo, erro.mensagem, erro.valor.idade]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("IDADE_NEGATIVA".to_string()),
+            Value::String("idade não pode ser negativa".to_string()),
+            Value::Integer((-3).into()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn throw_rethrow_caught_error(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = Nada
+
+        tente
+            tente
+                1 / 0
+            capture erro
+                lance erro
+            fim
+        capture erro
+            resultado = erro.tipo
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("DIVISÃO_POR_ZERO".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn throw_uncaught(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "lance \"falha\"");
+}
+
+fn write_modules(dir_name: &str, files: &[(&str, &str)]) -> String {
+    let dir = std::env::temp_dir().join(format!("tenda-{}-{}", dir_name, std::process::id()));
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for (name, content) in files {
+        std::fs::write(dir.join(name), content).unwrap();
+    }
+
+    dir.to_string_lossy().to_string()
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_module_namespace(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules(
+        "import-namespace",
+        &[("matemática.tnd", "seja pi = 3\nseja dobro(x) = x * 2\n")],
+    );
+
+    let source = format!(
+        r#"
+        importe "{dir}/matemática.tnd"
+
+        seja resultado = [matemática.pi, matemática.dobro(4)]
+    "#
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        list(vec![Value::Integer(3.into()), Value::Integer(8.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_module_relative_to_importer(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules(
+        "import-relative",
+        &[
+            ("a.tnd", "importe \"./b.tnd\"\nseja valor = b.valor + 1\n"),
+            ("b.tnd", "seja valor = 41\n"),
+        ],
+    );
+
+    let source = format!(
+        r#"
+        importe "{dir}/a.tnd" como módulo
+
+        seja resultado = módulo.valor
+    "#
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        Value::Integer(42.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_module_function_uses_module_globals(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules(
+        "import-globals",
+        &[(
+            "contagem.tnd",
+            r#"
+            seja base = 10
+
+            seja conta(n) = faça
+                se n é 0 então faça
+                    retorna base
+                fim
+
+                retorna 1 + conta(n - 1)
+            fim
+            "#,
+        )],
+    );
+
+    let source = format!(
+        r#"
+        seja base = 0
+
+        importe "{dir}/contagem.tnd"
+
+        seja resultado = contagem.conta(3)
+    "#
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        Value::Integer(13.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_module_is_evaluated_once(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules("import-cache", &[("estado.tnd", "seja itens = [0]\n")]);
+
+    let source = format!(
+        r#"
+        importe "{dir}/estado.tnd" como a
+        importe "{dir}/../{}/estado.tnd" como b
+
+        a.itens[0] = 5
+
+        seja resultado = b.itens[0]
+    "#,
+        std::path::Path::new(&dir)
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        Value::Integer(5.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_module_namespace_reads_module_state(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules(
+        "import-state",
+        &[(
+            "contador.tnd",
+            "seja contador = 0\nseja incrementa() = faça\n    contador = contador + 1\nfim\n",
+        )],
+    );
+
+    let source = format!(
+        r#"
+        importe "{dir}/contador.tnd" como a
+
+        a.incrementa()
+        a.incrementa()
+
+        seja resultado = a.contador
+    "#
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        Value::Integer(2.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_module_assignment_is_seen_by_module(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules(
+        "import-assign",
+        &[("config.tnd", "seja limite = 1\nseja lê() = limite\n")],
+    );
+
+    let source = format!(
+        r#"
+        importe "{dir}/config.tnd"
+
+        config.limite = 5
+        config.limite += 1
+
+        seja resultado = config.lê()
+    "#
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        Value::Integer(6.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_reexported_function_uses_its_own_module(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules(
+        "import-reexport",
+        &[
+            ("a.tnd", "importe \"b.tnd\"\nseja base = 2\nseja f = b.f\n"),
+            ("b.tnd", "seja base = 1\nseja f() = base\n"),
+        ],
+    );
+
+    let source = format!(
+        r#"
+        importe "{dir}/a.tnd"
+
+        seja resultado = [a.f(), a.b.f()]
+    "#
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        list(vec![Value::Integer(1.into()), Value::Integer(1.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_module_unknown_member_error_kind(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules("import-unknown", &[("vazio.tnd", "seja valor = 1\n")]);
+
+    let source = format!(
+        r#"
+        importe "{dir}/vazio.tnd"
+
+        seja resultado = Nada
+
+        tente
+            vazio.outro
+        capture erro
+            resultado = [erro.tipo, vazio?.outro]
+        fim
+    "#
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        list(vec![
+            Value::String("NOME_INEXISTENTE".to_string()),
+            Value::Nil
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_missing_module_error_kind(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = Nada
+
+        tente
+            importe "/caminho/que/não/existe.tnd"
+        capture erro
+            resultado = erro.tipo
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("MÓDULO_NÃO_ENCONTRADO".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn import_circular_module(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules(
+        "import-cycle",
+        &[
+            ("a.tnd", "importe \"b.tnd\"\n"),
+            ("b.tnd", "importe \"a.tnd\"\n"),
+        ],
+    );
+
+    interpret_stmt(platform, &format!("importe \"{dir}/a.tnd\""));
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn import_invalid_module(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules("import-invalid", &[("ruim.tnd", "seja = 1\n")]);
+
+    interpret_stmt(platform, &format!("importe \"{dir}/ruim.tnd\""));
+}
+
+#[cfg(unix)]
+#[rstest]
+#[case(OSPlatform)]
+fn import_module_through_symlink_is_evaluated_once(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules("import-symlink", &[("estado.tnd", "seja itens = [0]\n")]);
+    let link = std::path::Path::new(&dir).join("atalho.tnd");
+
+    let _ = std::fs::remove_file(&link);
+    std::os::unix::fs::symlink(std::path::Path::new(&dir).join("estado.tnd"), &link).unwrap();
+
+    let source = format!(
+        r#"
+        importe "{dir}/estado.tnd" como a
+        importe "{dir}/atalho.tnd" como b
+
+        a.itens[0] = 5
+
+        seja resultado = b.itens[0]
+    "#
+    );
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, &source, "resultado"),
+        Value::Integer(5.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn import_invalid_module_registers_source_once(#[case] platform: impl Platform + 'static) {
+    let dir = write_modules("import-invalid-retry", &[("ruim.tnd", "seja = 1\n")]);
+
+    let source = format!(
+        r#"
+        tente
+            importe "{dir}/ruim.tnd"
+        capture erro
+        fim
+
+        tente
+            importe "{dir}/ruim.tnd"
+        capture erro
+        fim
+    "#
+    );
+
+    let runtime = interpret_stmt(platform, &source);
+
+    assert_eq!(runtime.get_module_sources().len(), 1);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn record_field_access(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Aluno(nome, idade) fim
+
+        seja aluno = Aluno("Ana", 20)
+        seja resultado = [aluno.nome, aluno.idade]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("Ana".to_string()),
+            Value::Integer(20.into())
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn record_field_assign(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Ponto(
+            x,
+            y
+        )
+        fim
+
+        seja p = Ponto(1, 2)
+        seja q = p
+
+        p.x = 10
+
+        seja resultado = q.x + q.y
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(12.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn record_display(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Aluno(nome, idade) fim
+
+        seja aluno = Aluno("Ana", 20)
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "aluno").to_string(),
+        "Aluno(nome: \"Ana\", idade: 20)"
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn record_equality(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Ponto(x, y) fim
+
+        seja iguais = Ponto(1, 2) é Ponto(1, 2)
+        seja diferentes = Ponto(1, 2) é Ponto(2, 1)
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+    let env = runtime.get_global_env();
+
+    assert_eq!(env.get("iguais").unwrap().extract(), Value::Boolean(true));
+    assert_eq!(
+        env.get("diferentes").unwrap().extract(),
+        Value::Boolean(false)
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn record_inequality(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Ponto(x, y) fim
+
+        seja iguais = Ponto(1, 2) não é Ponto(1, 2)
+        seja diferentes = Ponto(1, 2) não é Ponto(2, 1)
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+    let env = runtime.get_global_env();
+
+    assert_eq!(env.get("iguais").unwrap().extract(), Value::Boolean(false));
+    assert_eq!(
+        env.get("diferentes").unwrap().extract(),
+        Value::Boolean(true)
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn record_unknown_field_error_kind(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Aluno(nome, idade) fim
+
+        seja aluno = Aluno("Ana", 20)
+        seja resultado = Nada
+
+        tente
+            aluno.nota = 10
+        capture erro
+            resultado = erro.tipo
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("CAMPO_INEXISTENTE".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn record_unknown_field_read(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Aluno(nome, idade) fim
+
+        seja aluno = Aluno("Ana", 20)
+        seja nota = aluno.nota
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn record_constructor_wrong_number_of_arguments(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Aluno(nome, idade) fim
+
+        seja aluno = Aluno("Ana")
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_record_duplicate_field(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "estrutura Aluno(nome, nome) fim");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn class_methods_with_receiver(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Conta(titular)
+            seja saldo = 0
+
+            seja deposite(valor) = faça
+                este.saldo = este.saldo + valor
+                retorna este
+            fim
+
+            seja resumo() = este.titular + ": " + este.saldo
+        fim
+
+        seja conta = Conta("Ana")
+
+        conta.deposite(10)
+        conta.deposite(5).deposite(1)
+
+        seja resultado = conta.resumo()
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("Ana: 16".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn class_instances_are_independent(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Contador
+            seja total = 0
+
+            seja incremente() = faça
+                este.total = este.total + 1
+            fim
+        fim
+
+        seja a = Contador()
+        seja b = Contador()
+
+        a.incremente()
+        a.incremente()
+        b.incremente()
+
+        seja resultado = [a.total, b.total]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![Value::Integer(2.into()), Value::Integer(1.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn class_method_stored_keeps_receiver(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Pessoa(nome)
+            seja saudação() = "Olá, " + este.nome
+
+            seja saudador() = função() -> este.saudação()
+        fim
+
+        seja ana = Pessoa("Ana")
+        seja saudar = ana.saudador()
+
+        ana.nome = "Ana Maria"
+
+        seja resultado = saudar()
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("Olá, Ana Maria".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn class_field_initializer_uses_constructor_params(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Retângulo(largura, altura)
+            seja área = largura * altura
+        fim
+
+        seja resultado = Retângulo(3, 4).área
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(12.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn class_unknown_field_assign(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Conta(titular)
+            seja renomeie(nome) = faça
+                este.nome = nome
+            fim
+        fim
+
+        Conta("Ana").renomeie("Bia")
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_this_outside_class(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "seja f() = este");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_assign_to_this(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Conta
+            seja troque() = faça
+                este = Nada
+            fim
+        fim
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_class_duplicate_member(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Conta(saldo)
+            seja saldo = 0
+        fim
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn interpolation_with_variables(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja nome = "Ana"
+        seja idade = 20
+
+        seja resultado = "Olá, {nome}, você tem {idade + 1} anos"
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("Olá, Ana, você tem 21 anos".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn interpolation_captured_in_closure(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja saudação(nome) = função() -> "Olá, {nome}!"
+
+        seja resultado = saudação("Bia")()
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("Olá, Bia!".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn match_literals_and_ranges(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja classifique(nota) = escolha nota
+            caso 10 então "perfeito"
+            caso 7 até 9 então "aprovado"
+            caso -1 então "ausente"
+            senão "reprovado"
+        fim
+
+        seja resultado = [classifique(10), classifique(8), classifique(-1), classifique(3)]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("perfeito".to_string()),
+            Value::String("aprovado".to_string()),
+            Value::String("ausente".to_string()),
+            Value::String("reprovado".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn match_list_shapes_with_guard(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja descreva(valor) = escolha valor
+            caso [] então "vazia"
+            caso [a, b] se a > b então "decrescente"
+            caso [_, _] então "par"
+            caso [1, resto] então "começa com 1 e termina com {resto}"
+            senão "outra"
+        fim
+
+        seja resultado = [descreva([]), descreva([2, 1]), descreva([1, 2]), descreva([1, 2, 3])]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("vazia".to_string()),
+            Value::String("decrescente".to_string()),
+            Value::String("par".to_string()),
+            Value::String("outra".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn match_associative_array_and_record(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Ponto(x, y) fim
+
+        seja descreva(valor) = escolha valor
+            caso { "nome": nome } então "olá, {nome}"
+            caso Ponto(x: 0, y) então "eixo y em {y}"
+            caso Ponto(x, y) então "ponto {x}, {y}"
+            senão "desconhecido"
+        fim
+
+        seja resultado = [
+            descreva({ "nome": "Ana", "idade": 20 }),
+            descreva(Ponto(0, 5)),
+            descreva(Ponto(1, 2)),
+            descreva({ "idade": 20 })
+        ]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("olá, Ana".to_string()),
+            Value::String("eixo y em 5".to_string()),
+            Value::String("ponto 1, 2".to_string()),
+            Value::String("desconhecido".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn match_statement_with_blocks(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja total = 0
+
+        para cada n em [1, 2, 3, 4] faça
+            escolha n % 2
+                caso 0 então
+                    total = total + n
+                caso 1 então
+                    continua
+            fim
+        fim
+
+        seja resultado = total
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(6.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn match_return_from_arm(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja sinal(n) = faça
+            escolha n
+                caso 0 então
+                    retorna "zero"
+                caso _ então
+                    Nada
+            fim
+            retorna "não zero"
+        fim
+
+        seja resultado = [sinal(0), sinal(1)]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("zero".to_string()),
+            Value::String("não zero".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn match_binding_captured_in_closure(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja funções = []
+
+        para cada valor em [1, 2] faça
+            escolha valor
+                caso n então
+                    funções = funções + [função() -> n * 10]
+            fim
+        fim
+
+        seja resultado = funções[0]() + funções[1]()
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(30.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn match_no_arm_error_kind(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = Nada
+
+        tente
+            escolha 42
+                caso 1 então "um"
+            fim
+        capture erro
+            resultado = erro.tipo
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("NENHUM_CASO_CORRESPONDENTE".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn match_unknown_record_field(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Ponto(x, y) fim
+
+        escolha Ponto(1, 2)
+            caso Ponto(z) então "z"
+        fim
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_match_duplicate_binding(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "escolha [1, 2] caso [a, a] então a fim");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_match_without_case(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "escolha 1 senão 2 fim");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_list_declaration_with_rest(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja [primeiro, segundo, ...resto] = [1, 2, 3, 4]
+
+        seja resultado = [primeiro, segundo, resto]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(1.into()),
+            Value::Integer(2.into()),
+            list(vec![Value::Integer(3.into()), Value::Integer(4.into())]),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_associative_array_declaration(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja pessoa = { "nome": "Ana", "idade": 20, "cidade": "Recife" }
+        seja { nome, idade: anos } = pessoa
+
+        seja resultado = [nome, anos]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("Ana".to_string()),
+            Value::Integer(20.into())
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_record_fields(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        estrutura Ponto(x, y) fim
+
+        seja { x, y } = Ponto(3, 4)
+
+        seja resultado = x * y
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(12.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_nested_patterns(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja [[a, b], { "c": c }, _] = [[1, 2], { "c": 3 }, 4]
+
+        seja resultado = a + b + c
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(6.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_assignment_swap(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja a = 1
+        seja b = 2
+
+        [a, b] = [b, a]
+
+        seja resultado = [a, b]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![Value::Integer(2.into()), Value::Integer(1.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_assignment_inside_closure(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja contador() = faça
+            seja passos = 0
+            seja total = 0
+
+            seja avance = função(n) -> faça
+                [passos, total] = [passos + 1, total + n]
+            fim
+
+            avance(5)
+            avance(7)
+
+            retorna [passos, total]
+        fim
+
+        seja resultado = contador()
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![Value::Integer(2.into()), Value::Integer(12.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_for_each_head(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja funções = []
+        seja soma = 0
+
+        para cada [nome, valor] em [["a", 1], ["b", 2]] faça
+            soma = soma + valor
+            funções = funções + [função() -> nome]
+        fim
+
+        seja resultado = funções[0]() + funções[1]() + soma
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("ab3".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_too_few_elements_error_kind(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = Nada
+
+        tente
+            seja [a, b, c] = [1, 2]
+        capture erro
+            resultado = erro.tipo
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("NÚMERO_DE_ELEMENTOS_INCORRETO".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn destructuring_missing_key_error_kind(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = Nada
+
+        tente
+            seja { nome, idade } = { "nome": "Ana" }
+        capture erro
+            resultado = erro.tipo
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("CHAVE_NÃO_ENCONTRADA".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn destructuring_too_many_elements(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "seja [a, b] = [1, 2, 3]");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn destructuring_non_list_value(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "seja [a, b] = 42");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_destructuring_literal_pattern(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "seja [a, 1] = [1, 1]");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn match_list_rest_pattern(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = escolha [1, 2, 3]
+            caso [] então 0
+            caso [primeiro, ...resto] então primeiro + resto[0] + resto[1]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(6.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn default_parameter_values(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja saudação(nome, saudação = "Olá") = saudação + ", " + nome
+        seja resultado = [saudação("Ana"), saudação("Bia", "Oi")]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("Olá, Ana".to_string()),
+            Value::String("Oi, Bia".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn default_parameter_refers_to_previous_parameters(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja f(a, b = a * 2, c = a + b) = [a, b, c]
+        seja resultado = f(1)
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(1.into()),
+            Value::Integer(2.into()),
+            Value::Integer(3.into())
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn default_parameter_captures_enclosing_variable(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja fábrica() = faça
+            seja base = 10
+            retorna função(x = base * 2) -> x + 1
+        fim
+
+        seja resultado = fábrica()()
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(21.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn named_arguments(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja f(a, b = 2, c = 3) = a * 100 + b * 10 + c
+        seja resultado = [f(c: 9, a: 1), f(1, c: 5), f(a: 4, b: 0)]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(129.into()),
+            Value::Integer(125.into()),
+            Value::Integer(403.into()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn named_arguments_in_class_constructor(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Ponto(x = 0, y = 0)
+        fim
+
+        seja ponto = Ponto(y: 5)
+        seja resultado = [ponto.x, ponto.y]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![Value::Integer(0.into()), Value::Integer(5.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn missing_arguments_error(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja f(a, b, c = 1) = a + b + c
+        seja resultado = Nada
+
+        tente
+            f()
+        capture erro
+            resultado = [erro.tipo, erro.mensagem]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("ARGUMENTOS_AUSENTES".to_string()),
+            Value::String("argumentos obrigatórios ausentes: 'a', 'b'".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn unknown_named_argument_error_kind(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja f(a) = a
+        seja resultado = Nada
+
+        tente
+            f(a: 1, b: 2)
+        capture erro
+            resultado = erro.tipo
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("ARGUMENTO_DESCONHECIDO".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn argument_given_positionally_and_by_name(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "seja f(a, b) = a\nf(1, a: 2)");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_required_parameter_after_default(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "seja f(a = 1, b) = a");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_positional_argument_after_named(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "seja f(a, b) = a\nf(a: 1, 2)");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn variadic_parameter_collects_arguments(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja soma(...números) = faça
+            seja total = 0
+
+            para cada número em números faça
+                total = total + número
+            fim
+
+            retorna total
+        fim
+
+        seja resultado = [soma(), soma(1), soma(1, 2, 3)]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(0.into()),
+            Value::Integer(1.into()),
+            Value::Integer(6.into())
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn variadic_parameter_after_defaults(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja f(a, b = 10, ...resto) = [a, b, resto]
+        seja resultado = f(1, 2, 3, 4)
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(1.into()),
+            Value::Integer(2.into()),
+            list(vec![Value::Integer(3.into()), Value::Integer(4.into())]),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn variadic_builtins(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        [
+            exiba("vários", "valores", 1, 2),
+            exiba(),
+            Matemática.máximo(3, 9, 2),
+            Matemática.mínimo(3, 9, 2, -1),
+            Matemática.máximo(4)
+        ]
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        list(vec![
+            Value::Nil,
+            Value::Nil,
+            Value::Integer(9.into()),
+            Value::Integer((-1).into()),
+            Value::Integer(4.into()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn variadic_builtin_requires_fixed_arguments(#[case] platform: impl Platform + 'static) {
+    interpret_expr_with_prelude(platform, "Matemática.máximo()");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_variadic_parameter_not_last(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "seja f(...a, b) = a");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn compound_assignment_evaluates_subscript_once(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja chamadas = 0
+
+        seja índice() = faça
+            chamadas += 1
+            retorna 0
+        fim
+
+        seja lista = [1, 2]
+        lista[índice()] += 10
+
+        seja resultado = [lista[0], chamadas]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![Value::Integer(11.into()), Value::Integer(1.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn compound_assignment_in_loop(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja total = 0
+        seja contador = 0
+
+        enquanto contador < 5 faça
+            contador += 1
+            total += contador
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "total"),
+        Value::Integer(15.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn constant_declaration(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        constante LIMITE = 10
+        seja resultado = LIMITE * 2
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(20.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_constant_reassignment(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "constante x = 1\nx = 2");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_constant_compound_reassignment(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "constante x = 1\nseja f() = faça\nx += 1\nfim");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn constant_can_be_shadowed(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        constante x = 1
+
+        seja f(x) = faça
+            x = x + 1
+            retorna x
+        fim
+
+        seja resultado = f(10)
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(11.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn constant_reassignment_error_kind(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja altera() = faça
+            x = 2
+        fim
+
+        constante x = 1
+        seja resultado = Nada
+
+        tente
+            altera()
+        capture erro
+            resultado = [erro.tipo, x]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("ATRIBUIÇÃO_A_CONSTANTE".to_string()),
+            Value::Integer(1.into())
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn prelude_globals_are_constant(#[case] platform: impl Platform + 'static) {
+    interpret_expr_with_prelude(platform, "Lista = 3");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn factorial_is_exact(#[case] platform: impl Platform + 'static) {
+    assert_eq!(
+        interpret_expr_with_prelude(platform, "\"\" + Matemática.fatorial(25)"),
+        Value::String("15511210043330985984000000".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn integer_division_compound_assignment(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja x = 17
+        x \= 5
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "x"),
+        Value::Integer(3.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn math_functions_preserve_integers(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        "" + [
+            Matemática.potência(3, 40),
+            Matemática.absoluto(-12345678901234567890),
+            Matemática.máximo(1, 2.5, 99999999999999999999),
+            Matemática.arredonda(2.6)
+        ]
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        Value::String(
+            "[12157665459056928801, 12345678901234567890, 99999999999999999999, 3]".to_string()
+        )
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn decimal_from_brazilian_text(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        "" + [Decimal.de_texto("10,50"), Decimal.de_texto("1.234,56"), Decimal.de_texto("0.25")]
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        Value::String("[10.50, 1234.56, 0.25]".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn decimal_from_invalid_text(#[case] platform: impl Platform + 'static) {
+    interpret_expr_with_prelude(platform, r#"Decimal.de_texto("dez reais")"#);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn decimal_division_with_rounding(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        "" + [
+            Decimal.divida(10, 3, 2, Decimal.arredondamentos.MEIO_PARA_CIMA),
+            Decimal.divida(2.5d, 1, 0, Decimal.arredondamentos.MEIO_PAR),
+            Decimal.divida(-10d, 3, 1, Decimal.arredondamentos.PISO),
+            Decimal.arredonda(2.345d, 2, Decimal.arredondamentos.PARA_ZERO)
+        ]
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        Value::String("[3.33, 2, -3.4, 2.34]".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn decimal_division_invalid_rounding(#[case] platform: impl Platform + 'static) {
+    interpret_expr_with_prelude(platform, r#"Decimal.divida(1, 3, 2, "QUALQUER")"#);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn decimal_currency_formatting(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        "" + [
+            Decimal.formata_moeda(1234.5d),
+            Decimal.formata_moeda(-0.5d),
+            Decimal.formata_moeda(1000000),
+            Decimal.formata(1234567.891d, 2),
+            Decimal.formata(0.125d, 2)
+        ]
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        Value::String(
+            r#"["R$ 1.234,50", "-R$ 0,50", "R$ 1.000.000,00", "1.234.567,89", "0,13"]"#.to_string()
+        )
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn generator_infinite_sequence(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja fibonacci() = faça
+            seja a = 0
+            seja b = 1
+
+            enquanto verdadeiro faça
+                produza a
+                seja próximo = a + b
+                a = b
+                b = próximo
+            fim
+        fim
+
+        seja resultado = []
+
+        para cada n em fibonacci() faça
+            se n > 50 então
+                para
+            fim
+
+            resultado = resultado + [n]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(
+            [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+                .into_iter()
+                .map(|n| Value::Integer(n.into()))
+                .collect()
+        )
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn generator_is_lazy(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja passos = 0
+
+        seja contador() = faça
+            seja i = 0
+
+            enquanto verdadeiro faça
+                passos += 1
+                produza i
+                i += 1
+            fim
+        fim
+
+        seja gerador = contador()
+        seja antes = passos
+
+        para cada i em gerador faça
+            se i é 2 então
+                para
+            fim
+        fim
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+
+    assert_eq!(
+        runtime.get_global_env().get("antes").unwrap().extract(),
+        Value::Integer(0.into())
+    );
+    assert_eq!(
+        runtime.get_global_env().get("passos").unwrap().extract(),
+        Value::Integer(3.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn generator_loops_and_early_return(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja pares(lista) = faça
+            para cada x em lista faça
+                se x % 2 é 1 então
+                    continua
+                fim
+
+                se x > 6 então
+                    retorna
+                fim
+
+                produza x * 10
+            fim
+
+            produza "nunca"
+        fim
+
+        seja resultado = []
+
+        para cada p em pares([1, 2, 3, 4, 5, 6, 7, 8]) faça
+            resultado = resultado + [p]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(20.into()),
+            Value::Integer(40.into()),
+            Value::Integer(60.into())
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn generator_consuming_generator(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja naturais() = faça
+            seja n = 1
+
+            enquanto verdadeiro faça
+                produza n
+                n += 1
+            fim
+        fim
+
+        seja quadrados(limite) = faça
+            para cada n em naturais() faça
+                se n > limite então
+                    para
+                fim
+
+                produza n * n
+            fim
+        fim
+
+        seja soma = 0
+
+        para cada q em quadrados(4) faça
+            soma += q
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "soma"),
+        Value::Integer(30.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn generator_body_is_compiled_once_per_function(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja dois() = faça
+            produza 1
+            produza 2
+        fim
+
+        seja primeiro = dois()
+        seja segundo = dois()
+    "#;
+
+    let runtime = interpret_stmt(platform, source);
+    let env = runtime.get_global_env();
+
+    let code = match env.get("dois").unwrap().extract() {
+        Value::Function(Function {
+            object:
+                FunctionObject::UserDefined {
+                    generator: Some(code),
+                    ..
+                },
+            ..
+        }) => code,
+        value => panic!("expected generator function, found {:?}", value),
+    };
+
+    // This copy, the stored definition and both generators share one body.
+    assert_eq!(Rc::strong_count(&code), 4);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn generator_is_consumed_once(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja dois() = faça
+            produza 1
+            produza 2
+        fim
+
+        seja gerador = dois()
+        seja contagem = 0
+
+        para cada x em gerador faça
+            contagem += 1
+        fim
+
+        para cada x em gerador faça
+            contagem += 1
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "contagem"),
+        Value::Integer(2.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn generator_error_propagates(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja falha() = faça
+            produza 1
+            lance "falhou"
+        fim
+
+        seja mensagem = Nada
+
+        tente
+            para cada x em falha() faça
+            fim
+        capture erro
+            mensagem = erro.mensagem
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "mensagem"),
+        Value::String("falhou".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_yield_outside_function(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "produza 1");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_yield_inside_try(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja f() = faça
+            tente
+                produza 1
+            capture
+            fim
+        fim
+    "#;
+
+    interpret_stmt(platform, source);
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn return_stops_loops(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja visitados = 0
+
+        seja procura(lista) = faça
+            para cada x em lista faça
+                visitados += 1
+
+                se x é 2 então
+                    retorna
+                fim
+            fim
+        fim
+
+        procura([1, 2, 3, 4])
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "visitados"),
+        Value::Integer(2.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn for_each_over_text(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja letras = []
+
+        para cada letra em "olá" faça
+            letras = letras + [letra]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "letras"),
+        list(vec![
+            Value::String("o".to_string()),
+            Value::String("l".to_string()),
+            Value::String("á".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn for_each_over_dict_keys(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja chaves = []
+
+        para cada chave em { "a": 1, "b": 2 } faça
+            chaves = chaves + [chave]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "chaves"),
+        list(vec![
+            Value::String("a".to_string()),
+            Value::String("b".to_string())
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn for_each_over_dict_entries(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja preços = { "café": 5, "pão": 2 }
+        seja resumo = ""
+
+        para cada produto, preço em preços faça
+            resumo = resumo + produto + "=" + preço + ";"
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resumo"),
+        Value::String("café=5;pão=2;".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn for_each_with_position(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja resultado = []
+
+        para cada i, [a, b] em [[1, 2], [3, 4]] faça
+            resultado = resultado + [i, a + b]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(0.into()),
+            Value::Integer(3.into()),
+            Value::Integer(1.into()),
+            Value::Integer(7.into()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn for_each_iteration_protocol_on_dict(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja contagem_regressiva(n) = faça
+            seja atual = n + 1
+
+            retorna {
+                "próximo": função() -> faça
+                    atual = atual - 1
+                    retorna se atual > 0 então atual senão Nada
+                fim
+            }
+        fim
+
+        seja vistos = []
+
+        para cada x em contagem_regressiva(3) faça
+            vistos = vistos + [x]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "vistos"),
+        list(vec![
+            Value::Integer(3.into()),
+            Value::Integer(2.into()),
+            Value::Integer(1.into())
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn for_each_iteration_protocol_on_class(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        classe Pares(limite)
+            seja atual = 0
+
+            seja próximo() = faça
+                se este.atual >= este.limite então
+                    retorna Nada
+                fim
+
+                este.atual = este.atual + 2
+                retorna este.atual
+            fim
+        fim
+
+        seja soma = 0
+
+        para cada i, par em Pares(6) faça
+            soma = soma + i * 100 + par
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "soma"),
+        Value::Integer(312.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn descending_range_with_step(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja vistos = []
+
+        para cada i em 10 até 0 passo -3 faça
+            vistos = vistos + [i]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "vistos"),
+        list(vec![
+            Value::Integer(10.into()),
+            Value::Integer(7.into()),
+            Value::Integer(4.into()),
+            Value::Integer(1.into()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn exclusive_range_with_negative_bounds(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja vistos = []
+
+        para cada i em -2 até 2 exclusivo faça
+            vistos = vistos + [i]
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "vistos"),
+        list(vec![
+            Value::Integer((-2).into()),
+            Value::Integer((-1).into()),
+            Value::Integer(0.into()),
+            Value::Integer(1.into()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn range_iteration_is_lazy(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja último = 0
+
+        para cada i em 0 até 9223372036854775807 faça
+            último = i
+
+            se i é 3 então
+                para
+            fim
+        fim
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "último"),
+        Value::Integer(3.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn list_comprehension_with_filter(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja números = [-1, 2, 3, -4]
+        seja dobros = [x * 2 para cada x em números se x > 0]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "dobros"),
+        list(vec![Value::Integer(4.into()), Value::Integer(6.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn dict_comprehension_with_computed_keys(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja preços = { "café": 5, "pão": 2 }
+        seja dobrados = { "{produto}!": preço * 2 para cada produto, preço em preços }
+        seja resultado = [dobrados["café!"], dobrados["pão!"]]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![Value::Integer(10.into()), Value::Integer(4.into())])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn comprehension_variable_is_scoped(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja x = 100
+        seja lista = [x para cada x em [1, 2]]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "x"),
+        Value::Integer(100.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn comprehension_closures_capture_each_element(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja multiplicadores(fator) = faça
+            retorna [função() -> n * fator para cada n em 1 até 3]
+        fim
+
+        seja resultado = [f() para cada f em multiplicadores(10)]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(10.into()),
+            Value::Integer(20.into()),
+            Value::Integer(30.into()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn pipeline_error_points_at_failing_stage(#[case] platform: impl Platform + 'static) {
+    let source = r#"seja soma(a, b) = a + b
+seja início = -1
+
+tente
+    1 |> soma(1) |> soma(1, 2)
+capture erro
+    início = erro.local.início
+fim"#;
+
+    let stage_start = source.find("soma(1, 2)").unwrap();
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "início"),
+        Value::Integer(stage_start.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn multiline_string_error_points_inside_literal(#[case] platform: impl Platform + 'static) {
+    let source = r#"seja início = -1
+
+tente
+    seja texto = """
+        primeira linha
+        segunda: {1 - "a"}
+        """
+capture erro
+    início = erro.local.início
+fim"#;
+
+    let operation_start = source.find("1 - \"a\"").unwrap();
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "início"),
+        Value::Integer(operation_start.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn tail_recursive_return_runs_in_constant_stack(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja números = [x para cada x em 1 até 100000]
+
+        seja soma(i, total) = faça
+            se i é 100000 então
+                retorna total
+            fim
+
+            retorna soma(i + 1, total + números[i])
+        fim
+
+        seja resultado = soma(0, 0)
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(5_000_050_000i64.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn tail_recursive_expression_body_runs_in_constant_stack(
+    #[case] platform: impl Platform + 'static,
+) {
+    let source = r#"
+        seja conta(n, total) = se n é 0 então total senão conta(n - 1, total + 1)
+
+        seja resultado = conta(100000, 0)
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::Integer(100000.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn tail_recursive_match_arm_runs_in_constant_stack(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja conta(n, total) = escolha n
+            caso 0 então total
+            senão conta(n - 1, total + 1)
+        fim
+
+        seja soma(n, total) = faça
+            retorna escolha n
+                caso 0 então total
+                senão
+                    seja próximo = n - 1
+                    soma(próximo, total + n)
+            fim
+        fim
+
+        seja resultado = [conta(100000, 0), soma(100000, 0)]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(100000.into()),
+            Value::Integer(5000050000i64.into()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn tail_call_inside_try_is_caught(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja falha() = faça
+            lance "erro"
+        fim
+
+        seja tenta() = faça
+            tente
+                retorna falha()
+            capture erro
+                retorna "capturado"
+            fim
+        fim
+
+        seja resultado = tenta()
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        Value::String("capturado".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn tail_call_error_stacktrace_replaces_intermediate_frames(
+    #[case] platform: impl Platform + 'static,
+) {
+    let source = r#"seja falha() = faça
+    lance "erro"
+fim
+
+seja intermediária() = faça
+    retorna falha()
+fim
+
+seja entrada() = faça
+    retorna intermediária()
+fim
+
+seja pilha = []
+
+tente
+    entrada()
+capture erro
+    pilha = [[quadro["função"], quadro["local"]["início"]] para cada quadro em erro["pilha"]]
+fim"#;
+
+    let frame = |name: &str, start: usize| {
+        list(vec![
+            Value::String(name.to_string()),
+            Value::Integer(start.into()),
+        ])
+    };
+
+    let throw_site = source.find("lance").unwrap();
+    let inner_call_site = source.find("falha()\nfim").unwrap();
+    let entry_call_site = source.find("entrada()\ncapture").unwrap();
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "pilha"),
+        list(vec![
+            frame("falha", throw_site),
+            frame("entrada", inner_call_site),
+            frame("<raiz>", entry_call_site),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn set_keeps_distinct_values(#[case] platform: impl Platform + 'static) {
+    let source = r#"Conjunto.para_lista(Conjunto.novo(3, 1, 3, 1.0, "a", "a", verdadeiro))"#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        list(vec![
+            Value::Integer(3.into()),
+            Value::Integer(1.into()),
+            Value::String("a".to_string()),
+            Value::Boolean(true),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn set_membership(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja vistos = Conjunto.de_lista(["ana", "bia"])
+
+        Conjunto.insira(vistos, "caio")
+        Conjunto.remova(vistos, "ana")
+
+        [vistos tem "caio", vistos tem "ana", vistos não tem "davi", vistos tem [1]]
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        list(vec![
+            Value::Boolean(true),
+            Value::Boolean(false),
+            Value::Boolean(true),
+            Value::Boolean(false),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn set_operators(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja a = Conjunto.novo(1, 2, 3)
+        seja b = Conjunto.novo(3, 4)
+
+        [
+            a | b é Conjunto.novo(4, 3, 2, 1),
+            a & b é Conjunto.novo(3),
+            a - b é Conjunto.novo(1, 2),
+            a ~ b é Conjunto.novo(1, 2, 4),
+            Conjunto.união(a, b) é a | b,
+            Conjunto.interseção(a, b) é a & b,
+            Conjunto.diferença(b, a) é Conjunto.novo(4)
+        ]
+    "#;
+
+    match interpret_expr_with_prelude(platform, source) {
+        Value::List(list) => assert!(list.borrow().iter().all(|v| *v == Value::Boolean(true))),
+        value => panic!("expected list, found {:?}", value),
+    }
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn set_iteration(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja total = 0
+
+        para cada n em Conjunto.novo(1, 2, 2, 3) faça
+            total += n
+        fim
+
+        total
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        Value::Integer(6.into())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn set_display(#[case] platform: impl Platform + 'static) {
+    assert_eq!(
+        interpret_expr_with_prelude(platform, r#""" + Conjunto.novo(1, "a", falso)"#),
+        Value::String("Conjunto { 1, \"a\", falso }".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn set_rejects_unhashable_element(#[case] platform: impl Platform + 'static) {
+    interpret_expr_with_prelude(platform, "Conjunto.novo([1])");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn assoc_array_logical_keys(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja d = {}
+
+        d[verdadeiro] = "sim"
+        d[falso] = "não"
+
+        d[1 > 0]
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        Value::String("sim".to_string())
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn enum_declaration(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        enumeração Cor:
+            VERMELHO,
+            VERDE,
+            AZUL
+        fim
+
+        seja resultado = ["" + Cor.VERDE, "" + Cor, Cor.VERDE é Cor.VERDE, Cor.VERDE é Cor.AZUL]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("Cor.VERDE".to_string()),
+            Value::String("<enumeração Cor>".to_string()),
+            Value::Boolean(true),
+            Value::Boolean(false),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn enum_variants_are_distinct_across_enums(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        enumeração Semáforo: VERMELHO, AMARELO, VERDE fim
+        enumeração Cor: VERMELHO, VERDE fim
+
+        seja resultado = [
+            Semáforo.VERMELHO é Cor.VERMELHO,
+            Semáforo.VERMELHO é "VERMELHO",
+            Semáforo.VERMELHO < Semáforo.VERDE,
+            Semáforo.VERDE >= Semáforo.AMARELO
+        ]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Boolean(false),
+            Value::Boolean(false),
+            Value::Boolean(true),
+            Value::Boolean(true),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn enum_iteration_and_dictionary_keys(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        enumeração Naipe: COPAS, ESPADAS, OUROS, PAUS fim
+
+        seja contagem = {}
+
+        para cada naipe em Naipe faça
+            contagem[naipe] = 0
+        fim
+
+        contagem[Naipe.OUROS] += 2
+
+        seja resultado = [contagem[Naipe.OUROS], contagem[Naipe.PAUS], "" + contagem]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::Integer(2.into()),
+            Value::Integer(0.into()),
+            Value::String(
+                "{ Naipe.COPAS: 0, Naipe.ESPADAS: 0, Naipe.OUROS: 2, Naipe.PAUS: 0 }".to_string()
+            ),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn enum_pattern_matching(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        enumeração Direção: NORTE, SUL, LESTE, OESTE fim
+
+        seja descreva(direção) = escolha direção
+            caso Direção.NORTE então "cima"
+            caso Direção.SUL então "baixo"
+            senão "lado"
+        fim
+
+        seja resultado = [descreva(Direção.NORTE), descreva(Direção.SUL), descreva(Direção.OESTE)]
+    "#;
+
+    assert_eq!(
+        interpret_stmt_and_get(platform, source, "resultado"),
+        list(vec![
+            Value::String("cima".to_string()),
+            Value::String("baixo".to_string()),
+            Value::String("lado".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn enum_unknown_variant(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "enumeração Cor: VERMELHO fim\nseja c = Cor.ROXO");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+#[should_panic]
+fn parse_error_enum_duplicate_variant(#[case] platform: impl Platform + 'static) {
+    interpret_stmt(platform, "enumeração Cor: VERMELHO, VERMELHO fim");
+}
+
+#[rstest]
+#[case(OSPlatform)]
+fn prelude_error_enums(#[case] platform: impl Platform + 'static) {
+    let source = r#"
+        seja tipo = Texto.para_número("abc").erro.tipo
+
+        [
+            tipo é Texto.erros.CONVERSÃO_INVÁLIDA,
+            "" + tipo,
+            escolha tipo
+                caso Texto.erros.CONVERSÃO_INVÁLIDA então "inválido"
+                senão "outro"
+            fim
+        ]
+    "#;
+
+    assert_eq!(
+        interpret_expr_with_prelude(platform, source),
+        list(vec![
+            Value::Boolean(true),
+            Value::String("Texto.erros.CONVERSÃO_INVÁLIDA".to_string()),
+            Value::String("inválido".to_string()),
+        ])
+    );
+}
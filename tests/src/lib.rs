@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use tenda_core::common::source::IdentifiedSource;
 use tenda_core::parser::ast::Ast;
 use tenda_core::parser::Parser;
@@ -54,6 +57,10 @@ pub fn interpret_stmt_and_get<P: Platform + 'static>(
     runtime.get_global_env().get(name).unwrap().extract()
 }
 
+pub fn list(values: Vec<Value>) -> Value {
+    Value::List(Rc::new(RefCell::new(values)))
+}
+
 #[macro_export]
 macro_rules! expr_tests {
     ($($name:ident: $expr:expr => $variant:ident $parens:tt),+ $(,)?) => {
@@ -25,48 +25,124 @@ expr_tests_should_panic!(
 );
 
 expr_tests!(
-    num_expr: "1" => Number(1.0),
-    num_sum_expr: "(1 + 2) + (3 + 4) + 5" => Number(15.0),
-    num_mult_expr: "(1 * 2) * (3 * 4) * 5" => Number(120.0),
-    num_sub_expr: "(1 - 2) - (3 - 4) - 5" => Number(-5.0),
-    num_div_expr: "10 / 2" => Number(5.0),
-    num_exp_expr: "2 ^ 3" => Number(8.0),
-    num_mod_expr: "10 % 3" => Number(1.0),
+    num_expr: "1" => Integer(1.into()),
+    num_sum_expr: "(1 + 2) + (3 + 4) + 5" => Integer(15.into()),
+    num_mult_expr: "(1 * 2) * (3 * 4) * 5" => Integer(120.into()),
+    num_sub_expr: "(1 - 2) - (3 - 4) - 5" => Integer((-5).into()),
+    num_div_expr: "10 / 2" => Integer(5.into()),
+    num_exp_expr: "2 ^ 3" => Integer(8.into()),
+    num_mod_expr: "10 % 3" => Integer(1.into()),
     num_greater_expr: "5 > 3" => Boolean(true),
     num_greater_equality_expr: "5 >= 5" => Boolean(true),
     num_less_expr: "3 < 5" => Boolean(true),
     num_less_equality_expr: "3 <= 3" => Boolean(true),
     num_equality_num_expr: "3 é 3" => Boolean(true),
     num_nequality_expr: "3 não é 4" => Boolean(true),
-    num_neg_exp_expr: "-2 ^ 3" => Number(-8.0),
+    num_neg_exp_expr: "-2 ^ 3" => Integer((-8).into()),
     num_nequality_str_expr: "3 não é \"3\"" => Boolean(true),
-    num_mod_neg_expr: "-10 % 3" => Number(-1.0),
-    num_neg_zero_literal: "(-0)" => Number(0.0),
-    num_sum_with_neg_operand: "1 + -2" => Number(-1.0),
+    num_mod_neg_floors_expr: "-10 % 3" => Integer(2.into()),
+    num_mod_neg_divisor_floors_expr: "10 % -3" => Integer((-2).into()),
+    num_neg_zero_literal: "(-0)" => Integer(0.into()),
+    num_sum_with_neg_operand: "1 + -2" => Integer((-1).into()),
     num_decimal_sum_then_mult: "(1.5 + 2.5) * 2" => Number(8.0),
     num_div_by_fraction: "2 / 0.5" => Number(4.0),
     num_div_result_fraction: "10 / 4" => Number(2.5),
-    num_neg_exponentiation_square: "(-5) ^ 2" => Number(25.0),
-    num_mod_operation_positive: "5 % 2" => Number(1.0),
+    num_neg_exponentiation_square: "(-5) ^ 2" => Integer(25.into()),
+    num_mod_operation_positive: "5 % 2" => Integer(1.into()),
     num_neg_exponentiation_reciprocal: "(-2) ^ -2" => Number(0.25),
-    num_mixed_precedence_exponentiation: "(2 * 2) ^ 3" => Number(64.0),
-    num_operator_precedence_linear: "1 + 2 * 3 / 6" => Number(2.0),
+    num_mixed_precedence_exponentiation: "(2 * 2) ^ 3" => Integer(64.into()),
+    num_operator_precedence_linear: "1 + 2 * 3 / 6" => Integer(2.into()),
     num_small_number_scaling: "0.0001 * 10000" => Number(1.0),
     num_equality_ge_test_equal: "1 >= 1" => Boolean(true),
     num_inequality_test_equal_fail: "1 > 1" => Boolean(false),
     num_large_precision_sum: "1000000 + 0.000001" => Number(1000000.000001),
-    num_neg_mult: "(-2) * (-3)" => Number(6.0),
-    num_mixed_add_and_mult: "2 * 3 + 4 * 5" => Number(26.0),
+    num_neg_mult: "(-2) * (-3)" => Integer(6.into()),
+    num_mixed_add_and_mult: "2 * 3 + 4 * 5" => Integer(26.into()),
     num_decimal_imprecision_sum: "0.1 + 0.2" => Number(0.30000000000000004),
-    num_large_integers_sum: "123456789 + 987654321" => Number(1111111110.0),
-    num_sub_groupings: "(1 - 2) + (3 - 4)" => Number(-2.0),
-    num_neg_mod_with_negs: "(-10) % (-3)" => Number(-1.0),
+    num_large_integers_sum: "123456789 + 987654321" => Integer(1111111110.into()),
+    num_sub_groupings: "(1 - 2) + (3 - 4)" => Integer((-2).into()),
+    num_neg_mod_with_negs: "(-10) % (-3)" => Integer((-1).into()),
 );
 
 expr_tests_should_panic!(
     num_div_by_zero: "0 / 0",
 );
 
+expr_tests!(
+    int_large_product: "99999999999999999999 * 99999999999999999999" =>
+        Integer("9999999999999999999800000000000000000001".parse().unwrap()),
+    int_power_exact: "2 ^ 100" => Integer("1267650600228229401496703205376".parse().unwrap()),
+    int_negative_exponent_promotes: "2 ^ -2" => Number(0.25),
+    int_exact_division: "10 / 2" => Integer(5.into()),
+    int_inexact_division_promotes: "7 / 2" => Number(3.5),
+    int_mixed_with_float_promotes: "1 + 0.5" => Number(1.5),
+    int_equals_float: "2 é 2.0" => Boolean(true),
+    int_not_equals_float: "2 não é 2.0" => Boolean(false),
+    int_equals_float_exactly: "9007199254740993 é 9007199254740992.0" => Boolean(false),
+    int_equals_fractional_float: "2 é 2.5" => Boolean(false),
+    int_list_equals_float_list: "[1, 2] é [1.0, 2.0]" => Boolean(true),
+    int_dict_equals_float_dict: "{ \"a\": 1 } é { \"a\": 1.0 }" => Boolean(true),
+    int_list_contains_float: "[1, 2] tem 2.0" => Boolean(true),
+    int_list_remove_all_float: "seja lista = [1, 2, 1]\nLista.remova_todos(lista, 1.0)\nlista" =>
+        List(Rc::new(RefCell::new(vec![Integer(2.into())]))),
+    int_compared_with_float: "3 > 2.5" => Boolean(true),
+    int_integer_division: "7 \\ 2" => Integer(3.into()),
+    int_integer_division_floors: "-7 \\ 2" => Integer((-4).into()),
+    int_float_integer_division: "7.5 \\ 2" => Number(3.0),
+    int_modulo: "-10 % 3" => Integer(2.into()),
+    int_modulo_negative_divisor: "10 % -3" => Integer((-2).into()),
+    int_float_modulo_floors: "-7.5 % 2" => Number(0.5),
+    int_math_remainder_floors: "Matemática.resto(-10, 3)" => Integer(2.into()),
+    int_math_remainder_float_floors: "Matemática.resto(-7.5, 2)" => Number(0.5),
+    int_math_remainder_matches_operator: "Matemática.resto(7, -2) é 7 % -2" => Boolean(true),
+    int_bitwise_and: "12 & 10" => Integer(8.into()),
+    int_bitwise_or: "12 | 10" => Integer(14.into()),
+    int_bitwise_xor: "12 ~ 10" => Integer(6.into()),
+    int_bitwise_not: "~5" => Integer((-6).into()),
+    int_shift_left: "1 << 70" => Integer("1180591620717411303424".parse().unwrap()),
+    int_shift_right: "1024 >> 3" => Integer(128.into()),
+    int_bitwise_precedence: "1 + 1 << 2" => Integer(8.into()),
+    int_hex_literal: "0xff" => Integer(255.into()),
+    int_display_exact: "\"\" + 9007199254740993" => String("9007199254740993".to_string()),
+);
+
+expr_tests_should_panic!(
+    int_div_by_zero: "1 / 0",
+    int_integer_div_by_zero: "1 \\ 0",
+    int_mod_by_zero: "1 % 0",
+    int_bitwise_on_float: "1.5 & 1",
+    int_negative_shift: "1 << -1",
+);
+
+expr_tests!(
+    dec_exact_sum: "0.1d + 0.2d é 0.3d" => Boolean(true),
+    dec_display: "\"\" + (0.1d + 0.2d)" => String("0.3".to_string()),
+    dec_keeps_scale: "\"\" + (10.50d + 1)" => String("11.50".to_string()),
+    dec_exact_product: "\"\" + (1.1d * 1.1d)" => String("1.21".to_string()),
+    dec_subtraction: "\"\" + (1d - 0.9d)" => String("0.1".to_string()),
+    dec_equals_float: "1.5d é 1.5" => Boolean(true),
+    dec_equals_integer: "2d é 2" => Boolean(true),
+    dec_compared_with_integer: "10.50d > 10" => Boolean(true),
+    dec_compared_with_float: "0.3d < 0.31" => Boolean(true),
+    dec_integer_division: "\"\" + (7.5d \\ 2)" => String("3".to_string()),
+    dec_modulo: "\"\" + (7.5d % 2)" => String("1.5".to_string()),
+    dec_modulo_floors: "\"\" + (-7.5d % 2)" => String("0.5".to_string()),
+    dec_power: "\"\" + (1.5d ^ 2)" => String("2.25".to_string()),
+    dec_negation: "\"\" + -1.25d" => String("-1.25".to_string()),
+    dec_range_has: "(0 até 10) tem 2d" => Boolean(true),
+    dec_range_has_fraction: "(0 até 10) tem 2.5d" => Boolean(false),
+    dec_range_bounds: "1d até 3d" => Range(runtime::Range::new(1, 3)),
+    dec_range_pattern: "escolha 3.5d\ncaso 1 até 5 então verdadeiro\nsenão falso\nfim" => Boolean(true),
+    dec_range_pattern_outside: "escolha 5.5d\ncaso 1 até 5 então verdadeiro\nsenão falso\nfim" => Boolean(false),
+);
+
+expr_tests_should_panic!(
+    dec_div_by_zero: "1d / 0",
+    dec_mod_by_zero: "1d % 0d",
+    dec_overflow: "79228162514264337593543950335d * 2",
+    dec_bitwise: "1d & 1",
+);
+
 expr_tests!(
     bool_expr: "verdadeiro" => Boolean(true),
     bool_expr2: "falso" => Boolean(false),
@@ -203,9 +279,9 @@ expr_tests_should_panic!(
 
 expr_tests!(
     list_expr: "[1, 2, 3]" =>
-        List(Rc::new(RefCell::new(vec![Number(1.0), Number(2.0), Number(3.0)]))),
+        List(Rc::new(RefCell::new(vec![Integer(1.into()), Integer(2.into()), Integer(3.into())]))),
     list_concat_expr: "[1, 2] + [3, 4]" =>
-        List(Rc::new(RefCell::new(vec![Number(1.0), Number(2.0), Number(3.0), Number(4.0)]))),
+        List(Rc::new(RefCell::new(vec![Integer(1.into()), Integer(2.into()), Integer(3.into()), Integer(4.into())]))),
     list_equality_expr: "[1, 2] é [1, 2]" => Boolean(true),
     list_equality2_expr: "[1, 2] é [1, 2, 3]" => Boolean(false),
     list_has_expr: "[1, 2, 3] tem 2" => Boolean(true),
@@ -214,33 +290,33 @@ expr_tests!(
         List(Rc::new(RefCell::new(vec![]))),
     nested_list_of_numbers_expr: "[[1, 2], [3, 4]]" =>
         List(Rc::new(RefCell::new(vec![
-            List(Rc::new(RefCell::new(vec![Number(1.0), Number(2.0)]))),
-            List(Rc::new(RefCell::new(vec![Number(3.0), Number(4.0)]))),
+            List(Rc::new(RefCell::new(vec![Integer(1.into()), Integer(2.into())]))),
+            List(Rc::new(RefCell::new(vec![Integer(3.into()), Integer(4.into())]))),
         ]))),
     list_with_nested_list_mixed_expr: "[1, [2, 3], 4]" =>
         List(Rc::new(RefCell::new(vec![
-            Number(1.0),
-            List(Rc::new(RefCell::new(vec![Number(2.0), Number(3.0)]))),
-            Number(4.0),
+            Integer(1.into()),
+            List(Rc::new(RefCell::new(vec![Integer(2.into()), Integer(3.into())]))),
+            Integer(4.into()),
         ]))),
     list_of_mixed_types_expr: "[\"olá\", verdadeiro, 123, Nada]" =>
         List(Rc::new(RefCell::new(vec![
             String("olá".to_string()),
             Boolean(true),
-            Number(123.0),
+            Integer(123.into()),
             Nil,
         ]))),
     list_including_range_expr: "[(1 até 3), \"coisas\", 42]" =>
         List(Rc::new(RefCell::new(vec![
-            Range(1, 3),
+            Range(runtime::Range::new(1, 3)),
             String("coisas".to_string()),
-            Number(42.0),
+            Integer(42.into()),
         ]))),
     list_arithmetic_expressions_expr: "[1 + 2, 3 * 4]" =>
-        List(Rc::new(RefCell::new(vec![Number(3.0), Number(12.0)]))),
+        List(Rc::new(RefCell::new(vec![Integer(3.into()), Integer(12.into())]))),
     list_chained_concatenation_expr: "([1, 2] + [3, 4]) + [5]" =>
         List(Rc::new(RefCell::new(vec![
-            Number(1.0), Number(2.0), Number(3.0), Number(4.0), Number(5.0)
+            Integer(1.into()), Integer(2.into()), Integer(3.into()), Integer(4.into()), Integer(5.into())
         ]))),
     list_deeply_nested_empty_expr: "[[[]]]" =>
         List(Rc::new(RefCell::new(vec![
@@ -253,7 +329,7 @@ expr_tests!(
     list_inequality_due_to_order_expr: "[1, 2] não é [2, 1]" => Boolean(true),
     list_multiple_concatenation_expr: "[1, 2] + [3] + [4, 5]" =>
         List(Rc::new(RefCell::new(vec![
-            Number(1.0), Number(2.0), Number(3.0), Number(4.0), Number(5.0)
+            Integer(1.into()), Integer(2.into()), Integer(3.into()), Integer(4.into()), Integer(5.into())
         ]))),
     list_empty_concatenation_expr: "[] + []" =>
         List(Rc::new(RefCell::new(vec![]))),
@@ -270,8 +346,8 @@ expr_tests!(
 expr_tests!(
     assoc_array_expr: "{ 1: 2, 3: 4 }" => AssociativeArray(Rc::new(RefCell::new(
         runtime::AssociativeArray::from([
-            (AssociativeArrayKey::Number(1), Number(2.0)),
-            (AssociativeArrayKey::Number(3), Number(4.0)),
+            (AssociativeArrayKey::Number(1), Integer(2.into())),
+            (AssociativeArrayKey::Number(3), Integer(4.into())),
         ])
     ))),
     assoc_array_has: "{ 1: 2, 3: 4 } tem 1" => Boolean(true),
@@ -293,12 +369,12 @@ expr_tests!(
         AssociativeArray(Rc::new(RefCell::new(
             runtime::AssociativeArray::from([
                 (AssociativeArrayKey::String("aninhado".to_string()),
-                List(Rc::new(RefCell::new(vec![Number(1.0), Number(2.0)])))),
+                List(Rc::new(RefCell::new(vec![Integer(1.into()), Integer(2.into())])))),
                 (AssociativeArrayKey::String("dicionário".to_string()),
                 AssociativeArray(Rc::new(RefCell::new(
                     runtime::AssociativeArray::from([
                         (AssociativeArrayKey::String("interior".to_string()),
-                        Number(42.0))
+                        Integer(42.into()))
                     ])
                 ))))
             ])
@@ -307,7 +383,7 @@ expr_tests!(
         AssociativeArray(Rc::new(RefCell::new(
             runtime::AssociativeArray::from([
                 (AssociativeArrayKey::Number(1),
-                List(Rc::new(RefCell::new(vec![Number(2.0), Number(3.0)])))),
+                List(Rc::new(RefCell::new(vec![Integer(2.into()), Integer(3.into())])))),
                 (AssociativeArrayKey::Number(2),
                     String("algo".to_string()))
             ])
@@ -316,7 +392,7 @@ expr_tests!(
         AssociativeArray(Rc::new(RefCell::new(
             runtime::AssociativeArray::from([
                 (AssociativeArrayKey::String("a".to_string()), Number(1.5)),
-                (AssociativeArrayKey::String("b".to_string()), Number(-2.0))
+                (AssociativeArrayKey::String("b".to_string()), Integer((-2).into()))
             ])
         ))),
     assoc_array_equality_true_expr: "{ \"a\": \"b\" } é { \"a\": \"b\" }" => Boolean(true),
@@ -349,7 +425,7 @@ expr_tests!(
                             AssociativeArray(Rc::new(RefCell::new(
                                 runtime::AssociativeArray::from([
                                     (AssociativeArrayKey::Number(3),
-                                    Number(4.0))
+                                    Integer(4.into()))
                                 ])
                             ))))
                         ])
@@ -367,7 +443,7 @@ expr_tests!(
             runtime::AssociativeArray::from([
                 (tenda_core::runtime::AssociativeArrayKey::String("arr".to_string()),
                 List(Rc::new(RefCell::new(vec![
-                    Number(1.0), Number(2.0), Number(3.0)
+                    Integer(1.into()), Integer(2.into()), Integer(3.into())
                 ])))),
                 (tenda_core::runtime::AssociativeArrayKey::String("flag".to_string()),
                 Boolean(true))
@@ -376,29 +452,29 @@ expr_tests!(
 );
 
 expr_tests!(
-    range_expr: "1 até 5" => Range(1, 5),
+    range_expr: "1 até 5" => Range(runtime::Range::new(1, 5)),
     range_equality_expr: "1 até 5 é 1 até 5" => Boolean(true),
-    range_descending_expr: "5 até 1" => Range(5, 1),
-    range_zero_expr: "0 até 0" => Range(0, 0),
-    range_single_value_expr: "5 até 5" => Range(5, 5),
-    range_normal_increasing_expr: "1 até 10" => Range(1, 10),
-    range_small_descending_expr: "2 até 1" => Range(2, 1),
-    range_large_descending_expr: "10 até 2" => Range(10, 2),
-    range_with_arithmetic_expression_expr: "(1 + 2) até (3 + 4)" => Range(3, 7),
-    range_minimal_increasing_expr: "0 até 1" => Range(0, 1),
-    range_identical_single_expr: "3 até 3" => Range(3, 3),
+    range_descending_expr: "5 até 1" => Range(runtime::Range::new(5, 1)),
+    range_zero_expr: "0 até 0" => Range(runtime::Range::new(0, 0)),
+    range_single_value_expr: "5 até 5" => Range(runtime::Range::new(5, 5)),
+    range_normal_increasing_expr: "1 até 10" => Range(runtime::Range::new(1, 10)),
+    range_small_descending_expr: "2 até 1" => Range(runtime::Range::new(2, 1)),
+    range_large_descending_expr: "10 até 2" => Range(runtime::Range::new(10, 2)),
+    range_with_arithmetic_expression_expr: "(1 + 2) até (3 + 4)" => Range(runtime::Range::new(3, 7)),
+    range_minimal_increasing_expr: "0 até 1" => Range(runtime::Range::new(0, 1)),
+    range_identical_single_expr: "3 até 3" => Range(runtime::Range::new(3, 3)),
     range_equality_true_expr: "1 até 2 é 1 até 2" => Boolean(true),
     range_inequality_due_to_shift_expr: "1 até 2 não é 2 até 3" => Boolean(true),
     range_equality_single_value_expr: "1 até 1 é 1 até 1" => Boolean(true),
     range_inequality_false_expr: "5 até 4 não é 5 até 4" => Boolean(false),
     range_length_difference_expr: "1 até 5 não é 1 até 4" => Boolean(true),
-    range_expression_calculation_expr: "(2 + 3) até (2 * 3)" => Range(5, 6),
+    range_expression_calculation_expr: "(2 + 3) até (2 * 3)" => Range(runtime::Range::new(5, 6)),
     range_chained_equality_with_boolean_true_expr: "((1 até 2) é (1 até 2)) é verdadeiro" => Boolean(true),
     range_chained_equality_failure_expr: "((1 até 1) é (1 até 2))" => Boolean(false),
     range_comparison_with_nil_false_expr: "((1 até 2) é Nada)" => Boolean(false),
     range_comparison_with_nil_true_expr: "((1 até 2) não é Nada)" => Boolean(true),
     range_nested_equality_comparison_expr: "((1 até 2) é (1 até 2)) é ((1 até 3) é (1 até 3))" => Boolean(true),
-    range_singleton_expr: "1 até 1" => Range(1, 1),
+    range_singleton_expr: "1 até 1" => Range(runtime::Range::new(1, 1)),
     range_equality_of_same_values_expr: "(2 até 2) é (2 até 2)" => Boolean(true)
 );
 
@@ -407,6 +483,96 @@ expr_tests_should_panic!(
 );
 
 expr_tests!(
-    dot_access_on_assoc_array: "{ \"a\": 1 }.a"           => Number(1.0),
-    dot_access_chained:       "{ \"a\": { \"b\": 2 } }.a.b" => Number(2.0),
+    range_negative_bounds_expr: "-3 até -1" => Range(runtime::Range::new(-3, -1)),
+    range_exclusive_expr: "0 até 10 exclusivo" => Range(runtime::Range::exclusive(0, 10)),
+    range_step_expr: "0 até 10 passo 2" => Range(runtime::Range::new(0, 10).with_step(2)),
+    range_step_of_one_expr: "1 até 5 passo 1 é 1 até 5" => Boolean(true),
+    range_exclusive_is_not_inclusive_expr: "0 até 5 exclusivo é 0 até 5" => Boolean(false),
+    range_display_expr: "\"\" + (10 até 0 exclusivo passo -3)" => String("10 até 0 exclusivo passo -3".to_string()),
+    range_has_expr: "(1 até 5) tem 5" => Boolean(true),
+    range_has_outside_expr: "(1 até 5) tem 6" => Boolean(false),
+    range_has_step_expr: "(0 até 10 passo 2) tem 4" => Boolean(true),
+    range_has_off_step_expr: "(0 até 10 passo 2) tem 5" => Boolean(false),
+    range_has_exclusive_end_expr: "(0 até 10 exclusivo) tem 10" => Boolean(false),
+    range_has_descending_expr: "(10 até 0 passo -5) tem 0" => Boolean(true),
+    range_has_negative_expr: "(-5 até -1) tem -3" => Boolean(true),
+    range_descending_infers_step_expr: "3 até 1 é 3 até 1 passo -1" => Boolean(true),
+    range_descending_exclusive_expr: "(3 até 1 exclusivo) tem 1" => Boolean(false),
+    range_descending_display_expr: "\"\" + (3 até 1)" => String("3 até 1".to_string()),
+    range_descending_explicit_step_display_expr: "\"\" + (3 até 1 passo -2)" => String("3 até 1 passo -2".to_string()),
+    range_descending_has_expr: "(3 até 1) tem 2" => Boolean(true),
+    range_descending_iteration_expr: "[x para cada x em 3 até 1]" =>
+        List(Rc::new(RefCell::new(vec![Integer(3.into()), Integer(2.into()), Integer(1.into())]))),
+    range_has_fraction_expr: "(1 até 5) tem 2.5" => Boolean(false),
+    range_lacks_expr: "(1 até 5) não tem 6" => Boolean(true),
+);
+
+expr_tests_should_panic!(
+    range_zero_step_error: "0 até 10 passo 0",
+    range_fractional_step_error: "0 até 10 passo 1.5",
+    range_fractional_bound_error: "0 até 2.5",
+    range_chained_after_step_error: "1 até 2 passo 1 até 3",
+);
+
+expr_tests!(
+    dot_access_on_assoc_array: "{ \"a\": 1 }.a"           => Integer(1.into()),
+    dot_access_chained:       "{ \"a\": { \"b\": 2 } }.a.b" => Integer(2.into()),
+);
+
+expr_tests!(
+    nil_coalescing_nil: "Nada ?? 5" => Integer(5.into()),
+    nil_coalescing_keeps_falsy: "falso ?? 5" => Boolean(false),
+    nil_coalescing_chained: "Nada ?? Nada ?? 3" => Integer(3.into()),
+    nil_coalescing_lower_than_or: "falso ou Nada ?? 2" => Integer(2.into()),
+    nil_coalescing_short_circuits: "seja x = 0\n1 ?? (x = 1)\nx" => Integer(0.into()),
+    safe_dot_access: "{ \"a\": { \"b\": 2 } }?.a?.b" => Integer(2.into()),
+    safe_dot_access_missing_key: "({ \"a\": 1 }?.b) é Nada" => Boolean(true),
+    safe_dot_access_on_nil: "seja x = Nada\nx?.a?.b é Nada" => Boolean(true),
+    safe_index_access: "[1, 2]?[1]" => Integer(2.into()),
+    safe_index_out_of_bounds: "([1, 2]?[3]) é Nada" => Boolean(true),
+    safe_index_on_nil: "seja x = Nada\nx?[0] é Nada" => Boolean(true),
+    safe_string_index_out_of_bounds: "(\"ab\"?[5]) é Nada" => Boolean(true),
+    safe_access_with_fallback: "{ \"a\": 1 }?[\"b\"] ?? 0" => Integer(0.into()),
+);
+
+expr_tests_should_panic!(
+    safe_access_not_indexable: "1?[0]",
+    safe_access_assignment: "seja x = { \"a\": 1 }\nx?.a = 2",
+    safe_access_invalid_index: "[1]?[\"a\"]",
+);
+
+expr_tests!(
+    compound_add_assign_expr: "seja x = 1\nx += 2" => Integer(3.into()),
+    compound_sub_assign_expr: "seja x = 1\nx -= 2" => Integer((-1).into()),
+    compound_mult_assign_expr: "seja x = 3\nx *= 4" => Integer(12.into()),
+    compound_div_assign_expr: "seja x = 10\nx /= 4" => Number(2.5),
+    compound_mod_assign_expr: "seja x = 10\nx %= 4" => Integer(2.into()),
+    compound_exp_assign_expr: "seja x = 2\nx ^= 10" => Integer(1024.into()),
+    compound_str_assign_expr: "seja x = \"olá\"\nx += \", mundo\"\nx" => String("olá, mundo".to_string()),
+    compound_list_index_assign_expr: "seja x = [1, 2]\nx[1] *= 5\nx[1]" => Integer(10.into()),
+    compound_assoc_array_assign_expr: "seja x = { \"a\": 1 }\nx[\"a\"] += 1\nx[\"a\"]" => Integer(2.into()),
+    compound_record_field_assign_expr: "estrutura Ponto(x) fim\nseja p = Ponto(1)\np.x -= 3\np.x" => Integer((-2).into()),
+);
+
+expr_tests_should_panic!(
+    compound_assign_type_mismatch_expr: "seja x = 1\nx += verdadeiro",
+    compound_assign_div_by_zero_expr: "seja x = 1\nx /= 0",
+    compound_assign_undefined_expr: "x += 1",
+    compound_assign_missing_key_expr: "seja x = { \"a\": 1 }\nx[\"b\"] += 1",
+    compound_assign_out_of_bounds_expr: "seja x = [1]\nx[3] += 1",
+    compound_assign_invalid_target_expr: "1 += 1",
+);
+
+expr_tests!(
+    pipeline_into_call: "-4 |> Matemática.absoluto()" => Integer(4.into()),
+    pipeline_into_function_value: "-4 |> Matemática.absoluto" => Integer(4.into()),
+    pipeline_inserts_first_argument: "10 |> Matemática.limita(0, 5)" => Integer(5.into()),
+    pipeline_chained: "[1, 2, 3] |> Lista.fatia(0, 1) |> Lista.tamanho()" => Integer(2.into()),
+    pipeline_lower_than_nil_coalescing: "Nada ?? -4 |> Matemática.absoluto()" => Integer(4.into()),
+    pipeline_lower_than_arithmetic: "1 - 5 |> Matemática.absoluto()" => Integer(4.into()),
+);
+
+expr_tests_should_panic!(
+    pipeline_into_non_function: "1 |> 2",
+    pipeline_missing_stage: "1 |>",
 );
@@ -36,17 +36,44 @@ expr_tests_should_panic!(
 );
 
 expr_tests!(
-    number_zero: "0" => Number(0.0),
-    number_plain: "123" => Number(123.0),
-    number_underscores: "1_000_000" => Number(1_000_000.0),
-    number_leading_zero: "0123" => Number(123.0),
-    number_bin: "0b1010" => Number(10.0),
-    number_bin_underscores: "0b1010_0101" => Number(0b1010_0101 as f64),
-    number_bin_uppercase: "0B1101" => Number(13.0),
-    number_oct: "0o755" => Number(0o755 as f64),
-    number_oct_uppercase: "0O644" => Number(0o644 as f64),
-    number_hex: "0xdead_beef" => Number(0xDEAD_BEEFu32 as f64),
-    number_hex_uppercase: "0XCAFE" => Number(0xCAFE as f64),
+    multiline_string_strips_common_indentation:
+        "\"\"\"\n    primeira\n      recuada\n    última\n    \"\"\"" =>
+        String("primeira\n  recuada\núltima".to_string()),
+    multiline_string_keeps_blank_lines: "\"\"\"\n  a\n\n  b\n\"\"\"" =>
+        String("a\n\nb".to_string()),
+    multiline_string_on_single_line: "\"\"\"com \"aspas\" dentro\"\"\"" =>
+        String("com \"aspas\" dentro".to_string()),
+    multiline_string_keeps_trailing_text_line: "\"\"\"\n  a\n  b\"\"\"" =>
+        String("a\nb".to_string()),
+    multiline_string_escapes: "\"\"\"\n  a\\tb\n  \"\"\"" => String("a\tb".to_string()),
+    multiline_string_interpolation: "\"\"\"\n  {1 + 1}\n    {\"x\"}\n  \"\"\"" =>
+        String("2\n  x".to_string()),
+    raw_string_keeps_backslashes: "r\"C:\\caminho\\novo\"" =>
+        String("C:\\caminho\\novo".to_string()),
+    raw_string_keeps_braces: "r\"\\d{3}\"" => String("\\d{3}".to_string()),
+    raw_multiline_string: "r\"\"\"\n  \"\\n\"\n  \"\"\"" => String("\"\\n\"".to_string()),
+    empty_string_is_not_multiline: "\"\" + \"a\"" => String("a".to_string()),
+);
+
+expr_tests_should_panic!(
+    multiline_string_unclosed: "\"\"\"\n  abc\n",
+    multiline_string_interpolation_across_lines: "\"\"\"\n  {1 +\n 2}\n\"\"\"",
+    raw_string_newline: "r\"abc\ndef\"",
+    raw_string_unclosed: "r\"abc",
+);
+
+expr_tests!(
+    number_zero: "0" => Integer(0.into()),
+    number_plain: "123" => Integer(123.into()),
+    number_underscores: "1_000_000" => Integer(1000000.into()),
+    number_leading_zero: "0123" => Integer(123.into()),
+    number_bin: "0b1010" => Integer(10.into()),
+    number_bin_underscores: "0b1010_0101" => Integer(0b1010_0101.into()),
+    number_bin_uppercase: "0B1101" => Integer(13.into()),
+    number_oct: "0o755" => Integer(0o755.into()),
+    number_oct_uppercase: "0O644" => Integer(0o644.into()),
+    number_hex: "0xdead_beef" => Integer(0xDEAD_BEEFu32.into()),
+    number_hex_uppercase: "0XCAFE" => Integer(0xCAFE.into()),
     number_float: "0.123" => Number(0.123),
     number_float_trailing: "1." => Number(1.0),
     number_exp: "1e3" => Number(1e3),
@@ -68,3 +95,50 @@ expr_tests_should_panic!(
     number_missing_exp_digits: "1e",
     number_invalid_suffix: "123abc",
 );
+
+expr_tests!(
+    postfix_paren_on_new_line_starts_statement: "seja x = 1\n(x + 1)" => Integer(2.into()),
+    postfix_bracket_on_new_line_starts_statement: "seja x = 5\n[x][0]" => Integer(5.into()),
+    postfix_index_on_same_line: "seja xs = [10, 20]\nxs[1]" => Integer(20.into()),
+    postfix_call_arguments_across_lines: "seja f(a, b) = a + b\nf(\n1,\n2\n)" => Integer(3.into()),
+    postfix_field_access_on_new_line: "seja d = { \"a\": { \"b\": 3 } }\nd\n.a\n.b" => Integer(3.into()),
+);
+
+expr_tests!(
+    interpolation_simple: "\"1 + 1 = {1 + 1}\"" => String("1 + 1 = 2".to_string()),
+    interpolation_only_expr: "\"{10 * 2}\"" => String("20".to_string()),
+    interpolation_multiple: "\"{1}, {2} e {3}\"" => String("1, 2 e 3".to_string()),
+    interpolation_text_value_unquoted: "\"Olá, {\"mundo\"}!\"" => String("Olá, mundo!".to_string()),
+    interpolation_nested_braces: "\"{ { \"a\": 1 }.a }\"" => String("1".to_string()),
+    interpolation_list_display: "\"{[1, \"dois\"]}\"" => String("[1, \"dois\"]".to_string()),
+    interpolation_nested_string_with_braces: "\"{\"\\{x\\}\"}\"" => String("{x}".to_string()),
+    interpolation_escaped_braces: "\"\\{nome\\}\"" => String("{nome}".to_string()),
+    interpolation_closing_brace_literal: "\"a}b\"" => String("a}b".to_string()),
+);
+
+expr_tests_should_panic!(
+    interpolation_empty: "\"{}\"",
+    interpolation_unclosed: "\"{1 + 1\"",
+    interpolation_invalid_expr: "\"{1 +}\"",
+    interpolation_trailing_tokens: "\"{1 2}\"",
+);
+
+expr_tests!(
+    match_expression_literal: "escolha 2 caso 1 então \"um\" caso 2 então \"dois\" fim" => String("dois".to_string()),
+    match_expression_else: "escolha 3 caso 1 então \"um\" senão \"outro\" fim" => String("outro".to_string()),
+    match_expression_binding: "escolha 5 caso n se n > 3 então n * 2 senão 0 fim" => Integer(10.into()),
+    match_expression_wildcard: "escolha \"a\" caso _ então verdadeiro fim" => Boolean(true),
+    match_expression_nil: "escolha Nada caso Nada então 1 senão 2 fim" => Integer(1.into()),
+);
+
+expr_tests_should_panic!(
+    match_expression_no_case: "escolha 1 fim",
+    match_expression_missing_then: "escolha 1 caso 1 \"um\" fim",
+    match_expression_invalid_pattern: "escolha 1 caso 1 + 1 então 2 fim",
+);
+
+expr_tests_should_panic!(
+    parse_error_computed_key_outside_comprehension: "{ x: 1 }",
+    parse_error_comprehension_after_element: "[1, x para cada x em [2]]",
+    parse_error_comprehension_without_iterable: "[x para cada x]",
+);